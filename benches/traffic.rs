@@ -0,0 +1,117 @@
+use std::convert::Infallible;
+
+use axum_prometheus::PrometheusMetricLayerBuilder;
+use bytes::Bytes;
+use criterion::{criterion_group, criterion_main, Criterion};
+use http::{Request, Response};
+use http_body_util::Empty;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+const GROUP_PATTERNS: &[&str] = &[
+    "/users/{id}",
+    "/users/{id}/posts",
+    "/users/{id}/posts/{post}",
+    "/users/{id}/comments",
+    "/users/{id}/comments/{comment}",
+    "/users/{id}/followers",
+    "/users/{id}/following",
+    "/users/{id}/settings/{key}",
+];
+const IGNORE_PATTERNS: &[&str] = &["/metrics", "/health", "/static/{*path}"];
+
+async fn handler(_req: Request<Empty<Bytes>>) -> Result<Response<Empty<Bytes>>, Infallible> {
+    Ok(Response::new(Empty::new()))
+}
+
+/// A request through the layer, with enough patterns configured that cloning them per request would show up.
+fn request(c: &mut Criterion) {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    // No recorder is installed, so the metrics macros are no-ops and only the layer's own overhead is measured.
+    let layer = PrometheusMetricLayerBuilder::new()
+        .with_ignore_patterns(IGNORE_PATTERNS)
+        .with_group_patterns_as("/users", GROUP_PATTERNS)
+        .build();
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(handler);
+
+    c.bench_function("request with group patterns", |b| {
+        b.iter(|| {
+            rt.block_on(async {
+                let req = Request::builder()
+                    .uri("/users/42/posts/7")
+                    .body(Empty::new())
+                    .unwrap();
+                service.ready().await.unwrap().call(req).await.unwrap()
+            })
+        })
+    });
+
+    let layer = PrometheusMetricLayerBuilder::new()
+        .with_ignore_patterns(IGNORE_PATTERNS)
+        .with_group_patterns_as("/users", GROUP_PATTERNS)
+        .with_grouping_cache(1024)
+        .build();
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(handler);
+
+    c.bench_function("request with group patterns and grouping cache", |b| {
+        b.iter(|| {
+            rt.block_on(async {
+                let req = Request::builder()
+                    .uri("/users/42/posts/7")
+                    .body(Empty::new())
+                    .unwrap();
+                service.ready().await.unwrap().call(req).await.unwrap()
+            })
+        })
+    });
+}
+
+/// A request through the layer without any ignore or group patterns, which is the most common configuration.
+fn request_without_patterns(c: &mut Criterion) {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let layer = PrometheusMetricLayerBuilder::new().build();
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(handler);
+
+    c.bench_function("request without patterns", |b| {
+        b.iter(|| {
+            rt.block_on(async {
+                let req = Request::builder()
+                    .uri("/users/42/posts/7")
+                    .body(Empty::new())
+                    .unwrap();
+                service.ready().await.unwrap().call(req).await.unwrap()
+            })
+        })
+    });
+}
+
+/// A request through the layer with an extra constant label, which is attached to every emitted metric.
+fn request_with_version_label(c: &mut Criterion) {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let layer = PrometheusMetricLayerBuilder::new()
+        .with_version_label("1.0.0")
+        .build();
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(handler);
+
+    c.bench_function("request with version label", |b| {
+        b.iter(|| {
+            rt.block_on(async {
+                let req = Request::builder().uri("/").body(Empty::new()).unwrap();
+                service.ready().await.unwrap().call(req).await.unwrap()
+            })
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    request,
+    request_without_patterns,
+    request_with_version_label
+);
+criterion_main!(benches);
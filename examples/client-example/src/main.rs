@@ -0,0 +1,48 @@
+//! Run with
+//!
+//! ```not_rust
+//! cd examples && cargo run -p client-example
+//! ```
+//!
+//! The server at `http://127.0.0.1:3000/proxy` calls `http://127.0.0.1:3000/upstream` with an instrumented HTTP
+//! client, so both the server and the client side metrics show up at `http://127.0.0.1:3000/metrics`.
+
+use std::net::SocketAddr;
+
+use axum::{body::Body, http::Request, routing::get, Router};
+use axum_prometheus::{ClientMetricLayer, PrometheusMetricLayer};
+use hyper_util::{
+    client::legacy::{connect::HttpConnector, Client},
+    rt::TokioExecutor,
+};
+use tower::{ServiceBuilder, ServiceExt};
+
+#[tokio::main]
+async fn main() {
+    let (metric_layer, metric_handle) = PrometheusMetricLayer::pair();
+
+    // The client side metrics are recorded into the same exporter.
+    let client = ServiceBuilder::new()
+        .layer(ClientMetricLayer::new())
+        .service(Client::builder(TokioExecutor::new()).build::<_, Body>(HttpConnector::new()));
+
+    let app = Router::new()
+        .route("/upstream", get(|| async { "Hello from upstream" }))
+        .route(
+            "/proxy",
+            get(move || async move {
+                let request = Request::get("http://127.0.0.1:3000/upstream")
+                    .body(Body::empty())
+                    .unwrap();
+                let response = client.oneshot(request).await.unwrap();
+                format!("upstream responded with {}", response.status())
+            }),
+        )
+        .route("/metrics", get(|| async move { metric_handle.render() }))
+        .layer(metric_layer);
+
+    let listener = tokio::net::TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 3000)))
+        .await
+        .unwrap();
+    axum::serve(listener, app).await.unwrap();
+}
@@ -0,0 +1,35 @@
+//! Run with
+//!
+//! ```not_rust
+//! cd examples && cargo run -p debug-file-example
+//! ```
+//!
+//! Then send some requests to `http://127.0.0.1:3000/fast` or `http://127.0.0.1:3000/slow`, and the metrics
+//! are printed to stdout every second.
+
+use std::{net::SocketAddr, time::Duration};
+
+use axum::{routing::get, Router};
+use axum_prometheus::{DebugFileRecorder, GenericMetricLayer};
+
+#[tokio::main]
+async fn main() {
+    // Use `DebugFileRecorder::file("metrics.txt")` to write the metrics to a file instead.
+    let recorder = DebugFileRecorder::stdout().interval(Duration::from_secs(1));
+    let (metric_layer, _metric_handle) = GenericMetricLayer::pair_from(recorder);
+
+    let app = Router::new()
+        .route("/fast", get(|| async {}))
+        .route(
+            "/slow",
+            get(|| async {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }),
+        )
+        .layer(metric_layer);
+
+    let listener = tokio::net::TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 3000)))
+        .await
+        .unwrap();
+    axum::serve(listener, app).await.unwrap();
+}
@@ -0,0 +1,58 @@
+//! Run with
+//!
+//! ```not_rust
+//! cd examples && cargo run -p exporter-otel-example
+//! ```
+//!
+//! The metrics are pushed to an OTLP collector listening on `http://localhost:4318` every few seconds.
+//! You can start one locally with `docker run -p 4318:4318 otel/opentelemetry-collector`.
+
+use axum::{routing::get, Router};
+use axum_prometheus::{GenericMetricLayer, OtelRecorder};
+use opentelemetry_otlp::MetricExporter;
+use opentelemetry_sdk::metrics::SdkMeterProvider;
+use std::net::SocketAddr;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| "exporter_otel_example=debug".into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    // The regular OpenTelemetry setup..
+    let exporter = MetricExporter::builder()
+        .with_http()
+        .build()
+        .expect("Could not create OTLP metric exporter");
+    let meter_provider = SdkMeterProvider::builder()
+        .with_periodic_exporter(exporter)
+        .build();
+    opentelemetry::global::set_meter_provider(meter_provider.clone());
+
+    // `OtelRecorder` reports into the globally registered meter provider by default, so `pair` works too.
+    // To use a specific meter, create the recorder with `OtelRecorder::new` and use `pair_from` instead.
+    let (metric_layer, _) = GenericMetricLayer::<'_, _, OtelRecorder>::pair();
+    let app = Router::new()
+        .route("/foo", get(|| async {}))
+        .route("/bar", get(|| async {}))
+        .layer(metric_layer);
+
+    let listener = tokio::net::TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 3000)))
+        .await
+        .unwrap();
+    tracing::debug!("listening on {}", listener.local_addr().unwrap());
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async {
+            tokio::signal::ctrl_c().await.unwrap();
+        })
+        .await
+        .unwrap();
+
+    // Flush the remaining metrics before exiting.
+    meter_provider.shutdown().unwrap();
+}
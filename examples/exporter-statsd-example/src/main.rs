@@ -5,7 +5,7 @@
 //! ```
 
 use axum::{routing::get, Router};
-use axum_prometheus::{metrics, GenericMetricLayer, MakeDefaultHandle};
+use axum_prometheus::{metrics, ExporterOpts, MakeDefaultHandle, MetricLayerBuilder};
 use metrics_exporter_statsd::StatsdBuilder;
 use std::net::SocketAddr;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
@@ -15,7 +15,18 @@ struct Recorder<'a> {
     port: u16,
     queue_size: usize,
     buffer_size: usize,
-    prefix: Option<&'a str>,
+}
+
+// `MetricLayerBuilder::with_default_metrics` requires the recorder to implement `Default`.
+impl Default for Recorder<'_> {
+    fn default() -> Self {
+        Self {
+            host: "127.0.0.1",
+            port: 8125,
+            queue_size: 5000,
+            buffer_size: 1024,
+        }
+    }
 }
 
 // In order to use this with `axum_prometheus`, we must implement `MakeDefaultHandle`.
@@ -25,11 +36,16 @@ impl<'a> MakeDefaultHandle for Recorder<'a> {
     type Out = ();
 
     fn make_default_handle(self) -> Self::Out {
+        self.make_default_handle_with_opts(&ExporterOpts::default())
+    }
+
+    // The prefix set with `MetricLayerBuilder::with_exporter_prefix` is passed in the options.
+    fn make_default_handle_with_opts(self, opts: &ExporterOpts) -> Self::Out {
         // The regular setup for StatsD..
         let recorder = StatsdBuilder::from(self.host, self.port)
             .with_queue_size(self.queue_size)
             .with_buffer_size(self.buffer_size)
-            .build(self.prefix)
+            .build(opts.prefix())
             .expect("Could not create StatsDRecorder");
 
         metrics::set_global_recorder(recorder).unwrap();
@@ -46,16 +62,12 @@ async fn main() {
         .with(tracing_subscriber::fmt::layer())
         .init();
 
-    // Use `GenericMetricLayer` instead of `PrometheusMetricLayer`.
-    // By using `pair_from`, you can inject any values into the recorder.
-    // `GenericMetricLayer::pair` is only callable if the recorder struct implements Default.
-    let (metric_layer, _) = GenericMetricLayer::pair_from(Recorder {
-        host: "127.0.0.1",
-        port: 8125,
-        queue_size: 5000,
-        buffer_size: 1024,
-        prefix: Some("prefix"),
-    });
+    // Use `MetricLayerBuilder` with the custom recorder instead of `PrometheusMetricLayerBuilder`.
+    // Alternatively, `GenericMetricLayer::pair_from` lets you inject any values into the recorder.
+    let (metric_layer, _) = MetricLayerBuilder::<'_, _, Recorder<'_>, _>::new()
+        .with_exporter_prefix("prefix")
+        .with_default_metrics()
+        .build_pair();
     let app = Router::new()
         .route("/foo", get(|| async {}))
         .route("/bar", get(|| async {}))
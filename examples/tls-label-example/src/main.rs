@@ -0,0 +1,73 @@
+//! Run with
+//!
+//! ```not_rust
+//! cd examples && cargo run -p tls-label-example
+//! ```
+//!
+//! Then send some requests to `http://127.0.0.1:3000/foo`, and check the `tls` label at
+//! `http://127.0.0.1:3000/metrics`.
+//!
+//! axum doesn't terminate TLS itself, so the negotiated TLS version has to be put into the request extensions by
+//! whatever accepts the connections. This example uses `into_make_service_with_connect_info` for that, with a
+//! connection type that would normally be produced by a TLS acceptor (like `tokio-rustls`). To keep the example
+//! self-contained, it serves plain HTTP and reports a fixed version.
+
+use std::net::SocketAddr;
+
+use axum::{
+    extract::{connect_info::Connected, ConnectInfo, Request},
+    middleware::{self, Next},
+    response::Response,
+    routing::get,
+    serve::IncomingStream,
+    Router,
+};
+use axum_prometheus::PrometheusMetricLayerBuilder;
+use tokio::net::TcpListener;
+
+/// The connection info of a TLS connection.
+#[derive(Clone, Debug)]
+struct TlsInfo {
+    version: &'static str,
+}
+
+impl Connected<IncomingStream<'_, TcpListener>> for TlsInfo {
+    fn connect_info(_stream: IncomingStream<'_, TcpListener>) -> Self {
+        // With a TLS acceptor, this is where the negotiated version would be read, e.g. from
+        // `tokio_rustls::server::TlsStream::get_ref().1.protocol_version()`.
+        TlsInfo { version: "TLSv1.3" }
+    }
+}
+
+/// axum stores the connection info as `ConnectInfo<TlsInfo>`, so expose it as a plain `TlsInfo` to the metric layer.
+async fn insert_tls_info(mut request: Request, next: Next) -> Response {
+    if let Some(ConnectInfo(info)) = request.extensions().get::<ConnectInfo<TlsInfo>>().cloned() {
+        request.extensions_mut().insert(info);
+    }
+    next.run(request).await
+}
+
+#[tokio::main]
+async fn main() {
+    let (metric_layer, metric_handle) = PrometheusMetricLayerBuilder::new()
+        .with_tls_label(|info: &TlsInfo| info.version.to_owned())
+        .with_default_metrics()
+        .build_pair();
+
+    let app = Router::new()
+        .route("/foo", get(|| async {}))
+        .route("/metrics", get(|| async move { metric_handle.render() }))
+        // The extension has to be inserted before the request reaches the metric layer, so this layer goes last.
+        .layer(metric_layer)
+        .layer(middleware::from_fn(insert_tls_info));
+
+    let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 3000)))
+        .await
+        .unwrap();
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<TlsInfo>(),
+    )
+    .await
+    .unwrap();
+}
@@ -0,0 +1,284 @@
+use std::sync::{Arc, Mutex, OnceLock};
+
+use metrics::{
+    Counter, Gauge, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder, SharedString, Unit,
+};
+use metrics_exporter_prometheus::{PrometheusHandle, PrometheusRecorder};
+
+use crate::{utils, ExporterOpts, MakeDefaultHandle, RenderError, Renderable};
+
+/// A [`MakeDefaultHandle`] implementation that derives the buckets of the histograms measured in seconds from the
+/// observed request durations, for services that don't know good bucket boundaries up front.
+///
+/// Prometheus buckets can't change once a histogram exists, so this works in two phases:
+///
+/// 1. During the warmup, metrics are recorded with the default buckets ([`utils::SECONDS_DURATION_BUCKETS`]), and
+///    the first `samples` request durations are collected.
+/// 2. Once they're collected, the buckets are placed at evenly spaced percentiles of them (with the last one at twice
+///    the slowest duration), and a new Prometheus recorder is set up with those buckets. Every metric is recorded
+///    into the new recorder from then on, which the handle renders.
+///
+/// This is best-effort: the buckets are only as good as the warmup traffic is representative, and they're never
+/// adjusted afterwards. Every metric recorded during the warmup is dropped when switching to the new recorder, so
+/// the counters reset (which `rate` and `increase` handle), and the metrics that are initialized when the layer is
+/// built only show up again once there's traffic for them. Descriptions are kept. Use fixed buckets (e.g.
+/// [`with_bucket_preset`]) once the distribution is known.
+///
+/// To use it with a namespaced layer, attach it with [`with_metrics_from`], so it looks for the request durations
+/// under the namespaced name.
+///
+/// # Example
+/// ```rust,no_run
+/// use axum_prometheus::{AdaptiveBucketsRecorder, GenericMetricLayer, Renderable};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let recorder = AdaptiveBucketsRecorder::new(1000).buckets(12);
+///     let (metric_layer, metric_handle) = GenericMetricLayer::pair_from(recorder);
+///     // Serve `metric_handle.render()` as usual.
+/// }
+/// ```
+///
+/// [`with_bucket_preset`]: crate::MetricLayerBuilder::with_bucket_preset
+/// [`with_metrics_from`]: crate::MetricLayerBuilder::with_metrics_from
+#[derive(Debug, Clone)]
+pub struct AdaptiveBucketsRecorder {
+    samples: usize,
+    buckets: usize,
+}
+
+impl AdaptiveBucketsRecorder {
+    /// Create a recorder that derives the buckets from the first `samples` request durations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `samples` is zero.
+    pub fn new(samples: usize) -> Self {
+        assert!(samples > 0, "the number of warmup samples must not be zero");
+        Self {
+            samples,
+            buckets: 10,
+        }
+    }
+
+    /// Set the number of buckets to derive (not counting `+Inf`). There may be fewer if the observed durations
+    /// repeat. Defaults to 10.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buckets` is zero.
+    pub fn buckets(mut self, buckets: usize) -> Self {
+        assert!(buckets > 0, "the number of buckets must not be zero");
+        self.buckets = buckets;
+        self
+    }
+}
+
+impl MakeDefaultHandle for AdaptiveBucketsRecorder {
+    type Out = AdaptiveBucketsHandle;
+
+    fn make_default_handle(self) -> Self::Out {
+        self.make_default_handle_with_opts(&ExporterOpts::default())
+    }
+
+    fn make_default_handle_with_opts(self, opts: &ExporterOpts) -> Self::Out {
+        let handle = AdaptiveBucketsHandle {
+            state: Arc::new(State {
+                samples: self.samples,
+                buckets: self.buckets,
+                reservoir: Mutex::new(Vec::with_capacity(self.samples)),
+                descriptions: Mutex::default(),
+                warmup: crate::default_prometheus_recorder(opts),
+                adapted: OnceLock::new(),
+                opts: opts.clone(),
+            }),
+        };
+        metrics::set_global_recorder(AdaptiveRecorder(Arc::clone(&handle.state)))
+            .expect("Failed to set global recorder");
+        handle
+    }
+
+    fn share_handle(handle: &Self::Out) -> Option<Self::Out> {
+        Some(handle.clone())
+    }
+}
+
+/// The handle of an [`AdaptiveBucketsRecorder`], to render the metrics from.
+#[derive(Debug, Clone)]
+pub struct AdaptiveBucketsHandle {
+    state: Arc<State>,
+}
+
+impl AdaptiveBucketsHandle {
+    /// Render the metrics of the current phase in the Prometheus text format.
+    pub fn render(&self) -> String {
+        self.state.recorder().handle().render()
+    }
+
+    /// The handle of the Prometheus recorder of the current phase. It's replaced when the warmup is over, so don't
+    /// keep it around.
+    pub fn prometheus_handle(&self) -> PrometheusHandle {
+        self.state.recorder().handle()
+    }
+
+    /// The derived buckets, or `None` while the warmup is still running.
+    pub fn buckets(&self) -> Option<&[f64]> {
+        self.state
+            .adapted
+            .get()
+            .map(|(buckets, _)| buckets.as_slice())
+    }
+}
+
+impl Renderable for AdaptiveBucketsHandle {
+    fn try_render(&self) -> Result<String, RenderError> {
+        Ok(self.render())
+    }
+}
+
+struct State {
+    samples: usize,
+    buckets: usize,
+    reservoir: Mutex<Vec<f64>>,
+    descriptions: Mutex<Vec<Description>>,
+    warmup: PrometheusRecorder,
+    adapted: OnceLock<(Vec<f64>, PrometheusRecorder)>,
+    /// The options of the layer, to find the request durations by their name, and to build the adapted recorder with.
+    opts: ExporterOpts,
+}
+
+impl std::fmt::Debug for State {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("State")
+            .field("samples", &self.samples)
+            .field("buckets", &self.buckets)
+            .field("adapted", &self.adapted.get().map(|(buckets, _)| buckets))
+            .finish_non_exhaustive()
+    }
+}
+
+impl State {
+    fn recorder(&self) -> &PrometheusRecorder {
+        self.adapted
+            .get()
+            .map_or(&self.warmup, |(_, recorder)| recorder)
+    }
+
+    /// Collect a request duration during the warmup, and switch to the adapted recorder once there are enough.
+    fn observe(&self, duration: f64) {
+        if self.adapted.get().is_some() {
+            return;
+        }
+        let mut reservoir = self.reservoir.lock().unwrap_or_else(|e| e.into_inner());
+        if reservoir.len() >= self.samples {
+            return;
+        }
+        reservoir.push(duration);
+        if reservoir.len() == self.samples {
+            let buckets = derive_buckets(std::mem::take(&mut *reservoir), self.buckets);
+            let recorder = crate::prometheus_recorder_with_buckets(&self.opts, &buckets);
+            let descriptions = self.descriptions.lock().unwrap_or_else(|e| e.into_inner());
+            for (kind, key, unit, description) in descriptions.iter().cloned() {
+                match kind {
+                    DescriptionKind::Counter => recorder.describe_counter(key, unit, description),
+                    DescriptionKind::Gauge => recorder.describe_gauge(key, unit, description),
+                    DescriptionKind::Histogram => {
+                        recorder.describe_histogram(key, unit, description);
+                    }
+                }
+            }
+            let _ = self.adapted.set((buckets, recorder));
+        }
+    }
+}
+
+/// The buckets at evenly spaced percentiles of `durations`, followed by twice the slowest one, without duplicates.
+fn derive_buckets(mut durations: Vec<f64>, count: usize) -> Vec<f64> {
+    durations.retain(|d| d.is_finite() && *d > 0.0);
+    durations.sort_by(f64::total_cmp);
+    let Some(&slowest) = durations.last() else {
+        return utils::SECONDS_DURATION_BUCKETS.to_vec();
+    };
+    let mut buckets: Vec<f64> = (1..count)
+        .map(|i| durations[(durations.len() - 1) * i / count])
+        .chain(std::iter::once(slowest * 2.0))
+        .collect();
+    buckets.dedup();
+    buckets
+}
+
+#[derive(Clone, Copy)]
+enum DescriptionKind {
+    Counter,
+    Gauge,
+    Histogram,
+}
+
+type Description = (DescriptionKind, KeyName, Option<Unit>, SharedString);
+
+struct AdaptiveRecorder(Arc<State>);
+
+impl AdaptiveRecorder {
+    /// Keep the description, so it can be registered with the adapted recorder too.
+    fn describe(
+        &self,
+        kind: DescriptionKind,
+        key: &KeyName,
+        unit: Option<Unit>,
+        description: &SharedString,
+    ) {
+        self.0
+            .descriptions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push((kind, key.clone(), unit, description.clone()));
+    }
+}
+
+struct Observed {
+    inner: Histogram,
+    state: Arc<State>,
+}
+
+impl HistogramFn for Observed {
+    fn record(&self, value: f64) {
+        self.inner.record(value);
+        self.state.observe(value);
+    }
+}
+
+impl Recorder for AdaptiveRecorder {
+    fn describe_counter(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.describe(DescriptionKind::Counter, &key, unit, &description);
+        self.0.recorder().describe_counter(key, unit, description);
+    }
+
+    fn describe_gauge(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.describe(DescriptionKind::Gauge, &key, unit, &description);
+        self.0.recorder().describe_gauge(key, unit, description);
+    }
+
+    fn describe_histogram(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.describe(DescriptionKind::Histogram, &key, unit, &description);
+        self.0.recorder().describe_histogram(key, unit, description);
+    }
+
+    fn register_counter(&self, key: &Key, metadata: &Metadata<'_>) -> Counter {
+        self.0.recorder().register_counter(key, metadata)
+    }
+
+    fn register_gauge(&self, key: &Key, metadata: &Metadata<'_>) -> Gauge {
+        self.0.recorder().register_gauge(key, metadata)
+    }
+
+    fn register_histogram(&self, key: &Key, metadata: &Metadata<'_>) -> Histogram {
+        let histogram = self.0.recorder().register_histogram(key, metadata);
+        if self.0.adapted.get().is_some() || key.name() != &*self.0.opts.names.requests_duration() {
+            return histogram;
+        }
+        Histogram::from_arc(Arc::new(Observed {
+            inner: histogram,
+            state: Arc::clone(&self.0),
+        }))
+    }
+}
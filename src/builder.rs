@@ -1,10 +1,23 @@
 use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
 use std::marker::PhantomData;
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+use std::time::Duration;
+
+use http::{Method, StatusCode};
 
 #[cfg(feature = "prometheus")]
 use metrics_exporter_prometheus::PrometheusHandle;
 
-use crate::{set_prefix, GenericMetricLayer, MakeDefaultHandle, Traffic};
+#[cfg(feature = "prometheus")]
+use crate::utils::BucketPreset;
+
+use crate::{
+    set_prefix, set_prefix_for, ExporterOpts, GenericMetricLayer, MakeDefaultHandle, MetricsData,
+    RequestSummary, Traffic,
+};
 
 #[doc(hidden)]
 mod sealed {
@@ -25,6 +38,12 @@ impl MetricBuilderState for LayerOnly {}
 pub enum EndpointLabel {
     /// The reported endpoint label is always the fully qualified uri path that has been requested.
     Exact,
+    /// The reported endpoint label is the path of [`axum::extract::OriginalUri`], which is the full path of the
+    /// request even when the layer is added to a [nested router] (where the uri path is relative to the nest point).
+    /// If it's missing, it falls back to [`EndpointLabel::Exact`] behavior.
+    ///
+    /// [nested router]: axum::Router::nest
+    OriginalUri,
     /// The reported endpoint label is determined by first trying to extract and return [`axum::extract::MatchedPath`],
     /// and if that fails (typically on [nested routes]) it falls back to [`EndpointLabel::Exact`] behavior. This is
     /// the default option.
@@ -35,6 +54,152 @@ pub enum EndpointLabel {
     /// Same as [`EndpointLabel::MatchedPath`], but instead of falling back to the exact uri called, it's given to a user-defined
     /// fallback function, that is expected to produce a String, which is then reported to Prometheus.
     MatchedPathWithFallbackFn(for<'f> fn(&'f str) -> String),
+    /// Same as [`EndpointLabel::MatchedPath`], but the HTTP method is prepended to the reported endpoint label,
+    /// e.g. `"GET /foo/{bar}"`. Group patterns are matched against the path only. The `method` label is still reported
+    /// separately.
+    MatchedPathWithMethod,
+    /// The reported endpoint label is produced by a user-defined function from the parts of the request. If it
+    /// returns `None`, the request is not tracked at all, as if it was ignored.
+    ///
+    /// This is useful to label and skip requests with a single hook, e.g. to label by tenant and skip requests
+    /// without one:
+    /// ```rust
+    /// use axum_prometheus::{EndpointLabel, PrometheusMetricLayerBuilder};
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .with_endpoint_label_type(EndpointLabel::TryDynamic(|parts| {
+    ///         let tenant = parts.headers.get("x-tenant")?.to_str().ok()?;
+    ///         Some(format!("{tenant}{}", parts.uri.path()))
+    ///     }))
+    ///     .build();
+    /// ```
+    /// The request parts are cloned for the function (including the headers and extensions), which comes with a cost
+    /// for every request.
+    TryDynamic(fn(&http::request::Parts) -> Option<String>),
+}
+
+/// Determines whether and how the client IP address is reported, see [`MetricLayerBuilder::with_client_ip_label`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum IpLabelMode {
+    /// The client IP address is not reported. This is the default option.
+    #[default]
+    None,
+    /// The client IP address is reported as the subnet it belongs to, keeping only its first `prefix_len` bits,
+    /// e.g. `203.0.113.0/24` for `Subnet(24)`. The same prefix length is used for IPv6 addresses, while IPv4-mapped
+    /// IPv6 addresses are treated as IPv4.
+    Subnet(u8),
+}
+
+/// The standard metrics that can be prefixed individually, see [`MetricLayerBuilder::with_prefix_for`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MetricKind {
+    /// `axum_http_requests_total`
+    RequestsTotal,
+    /// `axum_http_requests_pending`
+    RequestsPending,
+    /// `axum_http_requests_duration_seconds`
+    RequestsDuration,
+    /// `axum_http_response_body_size`
+    ResponseBodySize,
+}
+
+/// Where to read the domain error code of failed responses from, see [`MetricLayerBuilder::with_error_code_label`].
+#[derive(Clone)]
+pub struct ErrorCodeSource(pub(crate) ReadErrorCode);
+
+type ReadErrorCode =
+    Arc<dyn Fn(&http::HeaderMap, &http::Extensions) -> Option<String> + Send + Sync>;
+
+impl ErrorCodeSource {
+    /// Read the error code from the response header `header_name`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `header_name` is not a valid header name.
+    pub fn header(header_name: &str) -> Self {
+        let header_name =
+            http::HeaderName::from_bytes(header_name.as_bytes()).expect("a valid header name");
+        Self(Arc::new(move |headers, _| {
+            let value = headers.get(&header_name)?.to_str().ok()?;
+            Some(value.to_owned())
+        }))
+    }
+
+    /// Read the error code from an `E` in the response extensions with `extract`, e.g. one inserted by the
+    /// `IntoResponse` implementation of your error type.
+    pub fn extension<E>(extract: fn(&E) -> String) -> Self
+    where
+        E: Send + Sync + 'static,
+    {
+        Self(Arc::new(move |_, extensions| {
+            extensions.get::<E>().map(extract)
+        }))
+    }
+}
+
+impl fmt::Debug for ErrorCodeSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ErrorCodeSource").finish_non_exhaustive()
+    }
+}
+
+/// The keys of the built-in labels, see [`MetricLayerBuilder::with_label_keys`].
+///
+/// The default keys are `method`, `endpoint` and `status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LabelKeys {
+    /// The key of the HTTP method label.
+    pub method: &'static str,
+    /// The key of the endpoint label.
+    pub endpoint: &'static str,
+    /// The key of the response status code label.
+    pub status: &'static str,
+}
+
+impl Default for LabelKeys {
+    fn default() -> Self {
+        Self {
+            method: "method",
+            endpoint: "endpoint",
+            status: "status",
+        }
+    }
+}
+
+/// The error returned by the fallible builder methods (e.g. [`MetricLayerBuilder::try_with_ignore_pattern`]) when a
+/// route pattern can't be used. The underlying [`matchit::InsertError`] is available via [`Error::source`].
+///
+/// [`Error::source`]: std::error::Error::source
+#[derive(Debug, Clone)]
+pub struct RouteSpecError {
+    pattern: String,
+    source: matchit::InsertError,
+}
+
+impl RouteSpecError {
+    pub(crate) fn new(pattern: &str, source: matchit::InsertError) -> Self {
+        Self {
+            pattern: pattern.to_owned(),
+            source,
+        }
+    }
+
+    /// The route pattern that caused the error.
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+}
+
+impl fmt::Display for RouteSpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid route pattern `{}`", self.pattern)
+    }
+}
+
+impl std::error::Error for RouteSpecError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
 }
 
 /// A builder for [`GenericMetricLayer`] that enables further customizations.
@@ -58,8 +223,16 @@ pub struct MetricLayerBuilder<'a, T, M, S: MetricBuilderState> {
     pub(crate) traffic: Traffic<'a>,
     pub(crate) metric_handle: Option<T>,
     pub(crate) metric_prefix: Option<String>,
-    pub(crate) enable_body_size: bool,
+    // The metrics the prefix applies to, or all of them if `None`.
+    pub(crate) prefixed_metrics: Option<Vec<MetricKind>>,
+    pub(crate) failure_status_range: Option<RangeInclusive<u16>>,
+    pub(crate) preregistered_endpoints: &'a [(&'a str, &'a [Method])],
+    pub(crate) warmup: Option<Duration>,
+    pub(crate) exporter_opts: ExporterOpts,
+    pub(crate) descriptions: Option<Descriptions>,
     pub(crate) no_initialize_metrics: bool,
+    // Whether the exporter handle was supplied by the user, so the crate couldn't configure its buckets.
+    pub(crate) custom_handle: bool,
     pub(crate) _marker: PhantomData<(S, M)>,
 }
 
@@ -112,6 +285,37 @@ where
         self
     }
 
+    /// Same as [`with_ignore_pattern`], but returns an error instead of panicking if the pattern is malformed
+    /// or conflicts with a previously added one.
+    ///
+    /// ```rust
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
+    ///
+    /// let result = PrometheusMetricLayerBuilder::new().try_with_ignore_pattern("/foo/{bar");
+    /// assert!(result.is_err());
+    /// ```
+    ///
+    /// [`with_ignore_pattern`]: crate::MetricLayerBuilder::with_ignore_pattern
+    pub fn try_with_ignore_pattern(
+        mut self,
+        ignore_pattern: &'a str,
+    ) -> Result<Self, RouteSpecError> {
+        self.traffic.try_with_ignore_pattern(ignore_pattern)?;
+        Ok(self)
+    }
+
+    /// Same as [`with_ignore_patterns`], but returns an error instead of panicking if any of the patterns is
+    /// malformed or conflicts with a previously added one.
+    ///
+    /// [`with_ignore_patterns`]: crate::MetricLayerBuilder::with_ignore_patterns
+    pub fn try_with_ignore_patterns(
+        mut self,
+        ignore_patterns: &'a [&'a str],
+    ) -> Result<Self, RouteSpecError> {
+        self.traffic.try_with_ignore_patterns(ignore_patterns)?;
+        Ok(self)
+    }
+
     /// Group matching route patterns and report them under the given (arbitrary) endpoint.
     ///
     /// This feature is commonly useful for parametrized routes. Let's say you have these two routes:
@@ -138,189 +342,1749 @@ where
         self
     }
 
-    /// Determine how endpoints are reported. For more information, see [`EndpointLabel`].
+    /// Use custom keys for the built-in labels, e.g. `path` instead of `endpoint`, to match existing dashboard
+    /// conventions. See [`LabelKeys`].
     ///
-    /// [`EndpointLabel`]: crate::EndpointLabel
-    pub fn with_endpoint_label_type(mut self, endpoint_label: EndpointLabel) -> Self {
-        self.traffic.with_endpoint_label_type(endpoint_label);
+    /// ```rust
+    /// use axum_prometheus::{LabelKeys, PrometheusMetricLayerBuilder};
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .with_label_keys(LabelKeys {
+    ///         method: "http_method",
+    ///         endpoint: "path",
+    ///         ..Default::default()
+    ///     })
+    ///     .build();
+    /// ```
+    ///
+    /// [`LabelKeys`]: crate::LabelKeys
+    pub fn with_label_keys(mut self, label_keys: LabelKeys) -> Self {
+        self.traffic.with_label_keys(label_keys);
         self
     }
 
-    /// Enable response body size tracking.
+    /// Lowercase (ASCII only) the endpoint label, so `/Foo` and `/foo` are reported under the same endpoint.
     ///
-    /// #### Note:
-    /// This may introduce some performance overhead.
-    pub fn enable_response_body_size(mut self, enable: bool) -> Self {
-        self.enable_body_size = enable;
+    /// This only makes sense if the router genuinely treats paths case-insensitively, otherwise it merges endpoints
+    /// that are actually different. Note that `axum`'s router is case-sensitive.
+    pub fn lowercase_endpoints(mut self, enable: bool) -> Self {
+        self.traffic.with_lowercase_endpoints(enable);
         self
     }
 
-    /// By default, all metrics are initialized via `metrics::describe_*` macros, setting descriptions and units.
+    /// Report every request that didn't match a route (i.e. has no [`axum::extract::MatchedPath`], like requests
+    /// handled by the fallback handler) under the fixed endpoint label `label`, regardless of the response status.
     ///
-    /// This function disables this initialization.
-    pub fn no_initialize_metrics(mut self) -> Self {
-        self.no_initialize_metrics = true;
+    /// Without this, unmatched requests are reported under their exact path, so scanners and typos can create an
+    /// unbounded number of endpoint labels. This is the recommended setting for public facing services.
+    ///
+    /// ```rust
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .fallback_endpoint_label("unmatched")
+    ///     .build();
+    /// ```
+    pub fn fallback_endpoint_label(mut self, label: &'a str) -> Self {
+        self.traffic.with_fallback_endpoint_label(label);
         self
     }
-}
 
-impl<'a, T, M> MetricLayerBuilder<'a, T, M, LayerOnly> {
-    /// Initialize the builder.
-    pub fn new() -> MetricLayerBuilder<'a, T, M, LayerOnly> {
-        MetricLayerBuilder {
-            _marker: PhantomData,
-            traffic: Traffic::new(),
-            metric_handle: None,
-            no_initialize_metrics: false,
-            metric_prefix: None,
-            enable_body_size: false,
-        }
+    /// Report a human-readable operation name (e.g. `"list_users"`) in an `operation` label on every metric, similar to
+    /// OpenAPI's `operationId`.
+    ///
+    /// `operation_names` maps matched path templates (as in [`axum::extract::MatchedPath`]) to operation names. Requests
+    /// whose matched path isn't mapped (or that have no matched path) use their endpoint label as the operation name.
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .with_operation_names(HashMap::from([
+    ///         ("/users", "list_users"),
+    ///         ("/users/{id}", "get_user"),
+    ///     ]))
+    ///     .build();
+    /// ```
+    pub fn with_operation_names(mut self, operation_names: HashMap<&'a str, &'a str>) -> Self {
+        self.traffic.with_operation_names(operation_names);
+        self
     }
 
-    /// Use a prefix for the metrics instead of `axum`. This will use the following
-    /// metric names:
-    ///  - `{prefix}_http_requests_total`
-    ///  - `{prefix}_http_requests_pending`
-    ///  - `{prefix}_http_requests_duration_seconds`
+    /// Cache which group pattern each endpoint resolves to, so the group patterns are only matched once per
+    /// endpoint instead of on every request. This helps if there are many group patterns.
     ///
-    /// ..and will also use `{prefix}_http_response_body_size`, if response body size tracking is enabled.
+    /// At most `capacity` endpoints are cached. Once the cache is full, endpoints that aren't cached yet are
+    /// matched against the group patterns on every request, as without the cache.
     ///
-    /// This method will take precedence over environment variables.
+    /// ```rust
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
     ///
-    /// ## Note
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .with_group_patterns_as("/foo", &["/foo/{bar}", "/foo/{bar}/{baz}"])
+    ///     .with_grouping_cache(1024)
+    ///     .build();
+    /// ```
+    pub fn with_grouping_cache(mut self, capacity: usize) -> Self {
+        self.traffic.with_grouping_cache(capacity);
+        self
+    }
+
+    /// Same as [`with_group_patterns_as`], but returns an error instead of panicking if any of the patterns is
+    /// malformed or conflicts with a previously added one.
     ///
-    /// This function inherently changes the metric names, beware to use the appropriate names.
-    /// There're functions in the `utils` module to get them at runtime.
+    /// [`with_group_patterns_as`]: crate::MetricLayerBuilder::with_group_patterns_as
+    pub fn try_with_group_patterns_as(
+        mut self,
+        group_pattern: &'a str,
+        patterns: &'a [&'a str],
+    ) -> Result<Self, RouteSpecError> {
+        self.traffic
+            .try_with_group_patterns_as(group_pattern, patterns)?;
+        Ok(self)
+    }
+
+    /// Determine how endpoints are reported. For more information, see [`EndpointLabel`].
     ///
-    /// [`utils`]: crate::utils
-    pub fn with_prefix(mut self, prefix: impl Into<Cow<'a, str>>) -> Self {
-        self.metric_prefix = Some(prefix.into().into_owned());
+    /// [`EndpointLabel`]: crate::EndpointLabel
+    pub fn with_endpoint_label_type(mut self, endpoint_label: EndpointLabel) -> Self {
+        self.traffic.with_endpoint_label_type(endpoint_label);
         self
     }
-}
-impl<'a, T, M> MetricLayerBuilder<'a, T, M, LayerOnly>
-where
-    M: MakeDefaultHandle<Out = T>,
-{
-    /// Finalize the builder and get the previously registered metric handle out of it.
-    pub fn build(self) -> GenericMetricLayer<'a, T, M> {
-        GenericMetricLayer::from_builder(self)
+
+    /// Enable a requests total counter without any dynamic labels (only the constant ones, like the `version` label).
+    ///
+    /// Aggregating the labeled requests total over all of its series can be expensive for simple "total RPS" dashboard
+    /// panels. This single series counter is cheap to query instead.
+    pub fn enable_unlabeled_total(mut self, enable: bool) -> Self {
+        self.traffic.with_unlabeled_total(enable);
+        self
     }
-}
 
-impl<'a, T, M> MetricLayerBuilder<'a, T, M, LayerOnly>
-where
-    M: MakeDefaultHandle<Out = T> + Default,
-{
-    /// Attach the default exporter handle to the builder. This is similar to
-    /// initializing with [`GenericMetricLayer::pair`].
+    /// Label the requests total counter with the negotiated TLS version (or cipher), read from an `E` in the request
+    /// extensions by `extract`. Requests without an `E` (e.g. plain HTTP connections) are labeled with `none`.
     ///
-    /// After calling this function you can finalize with the [`build_pair`] method, and
-    /// can no longer call [`build`].
+    /// axum doesn't terminate TLS itself, so the acceptor (or a middleware that has access to the connection info)
+    /// has to insert the `E` before the request reaches this layer. See the `tls-label-example` for a full setup.
+    /// ```rust
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
     ///
-    /// [`build`]: crate::MetricLayerBuilder::build
-    /// [`build_pair`]: crate::MetricLayerBuilder::build_pair
-    pub fn with_default_metrics(self) -> MetricLayerBuilder<'a, T, M, Paired> {
-        let mut builder = MetricLayerBuilder::<'_, _, _, Paired>::from_layer_only(self);
-        builder.metric_handle = Some(M::make_default_handle(M::default()));
-        builder
-    }
-}
-impl<'a, T, M> MetricLayerBuilder<'a, T, M, LayerOnly> {
-    /// Attach a custom built exporter handle to the builder that's returned from the passed
-    /// in closure.
+    /// #[derive(Clone)]
+    /// struct TlsInfo {
+    ///     version: &'static str,
+    /// }
     ///
-    /// ## Example
-    /// ```rust,no_run
-    /// use axum_prometheus::{
-    ///        PrometheusMetricLayerBuilder, AXUM_HTTP_REQUESTS_DURATION_SECONDS, utils::SECONDS_DURATION_BUCKETS,
-    /// };
-    /// use metrics_exporter_prometheus::{Matcher, PrometheusBuilder};
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .with_tls_label(|info: &TlsInfo| info.version.to_owned())
+    ///     .build();
+    /// ```
+    /// The label should only take a few distinct values, so don't label with anything connection specific.
+    pub fn with_tls_label<E>(mut self, extract: fn(&E) -> String) -> Self
+    where
+        E: Send + Sync + 'static,
+    {
+        self.traffic.with_tls_label(Arc::new(move |extensions| {
+            extensions.get::<E>().map(extract)
+        }));
+        self
+    }
+
+    /// Count in-flight requests with the `axum_http_requests_started_total` and `axum_http_requests_finished_total`
+    /// counters, instead of the `axum_http_requests_pending` gauge. A request is finished at the same point it would
+    /// stop being pending, and both counters have the same labels as the gauge would.
     ///
-    /// let (metric_layer, metric_handle) = PrometheusMetricLayerBuilder::new()
-    ///     .with_metrics_from_fn(|| {
-    ///         PrometheusBuilder::new()
-    ///             .set_buckets_for_metric(
-    ///                 Matcher::Full(AXUM_HTTP_REQUESTS_DURATION_SECONDS.to_string()),
-    ///                 SECONDS_DURATION_BUCKETS,
-    ///             )
-    ///             .unwrap()
-    ///             .install_recorder()
-    ///             .unwrap()
-    ///     })
-    ///     .build_pair();
+    /// Counters are monotonic, so they don't drift if the process crashes with requests in-flight, and some monitoring
+    /// systems handle them better than gauges. The number of in-flight requests can be derived with a query like:
+    /// ```text
+    /// sum by (endpoint) (axum_http_requests_started_total) - sum by (endpoint) (axum_http_requests_finished_total)
     /// ```
-    /// After calling this function you can finalize with the [`build_pair`] method, and
-    /// can no longer call [`build`].
+    pub fn pending_as_counters(mut self, enable: bool) -> Self {
+        self.traffic.with_pending_as_counters(enable);
+        self
+    }
+
+    /// Enable the `axum_http_requests_failed_total` counter, which counts the requests that failed with an error
+    /// (instead of producing a response, or while streaming the response body) by a `reason` label:
+    ///  - `reset`: the HTTP/2 stream was reset (`RST_STREAM`)
+    ///  - `timeout`: the request timed out, e.g. in a `TimeoutLayer` inside of this layer
+    ///  - `connection`: any other connection error
+    ///  - `other`: everything else
     ///
-    /// [`build`]: crate::MetricLayerBuilder::build
-    /// [`build_pair`]: crate::MetricLayerBuilder::build_pair
-    pub fn with_metrics_from_fn(
-        self,
-        f: impl FnOnce() -> T,
-    ) -> MetricLayerBuilder<'a, T, M, Paired> {
-        let mut builder = MetricLayerBuilder::<'_, _, _, Paired>::from_layer_only(self);
-        builder.metric_handle = Some(f());
-        builder
+    /// The reason is detected from the error message kept by the classifier, so it's best-effort. Responses with an error status code are
+    /// not failures in this sense, those are counted by the requests total as usual.
+    pub fn enable_failure_reasons(mut self, enable: bool) -> Self {
+        self.traffic.with_failure_reasons(enable);
+        self
     }
-}
 
-impl<'a, T, M> MetricLayerBuilder<'a, T, M, Paired> {
-    pub(crate) fn from_layer_only(layer_only: MetricLayerBuilder<'a, T, M, LayerOnly>) -> Self {
-        if let Some(prefix) = layer_only.metric_prefix.as_ref() {
-            set_prefix(prefix);
-        }
-        if !layer_only.no_initialize_metrics {
-            describe_metrics(layer_only.enable_body_size);
-        }
-        MetricLayerBuilder {
-            _marker: PhantomData,
-            traffic: layer_only.traffic,
-            metric_handle: layer_only.metric_handle,
-            no_initialize_metrics: layer_only.no_initialize_metrics,
-            metric_prefix: layer_only.metric_prefix,
-            enable_body_size: layer_only.enable_body_size,
-        }
+    /// Enable the `axum_http_method_duration_seconds` histogram, which records the same response times as the
+    /// duration histogram, but labeled by the method only. This is a cheap global latency view for services with a
+    /// lot of endpoints.
+    pub fn enable_method_histogram(mut self, enable: bool) -> Self {
+        self.traffic.with_method_histogram(enable);
+        self
     }
-}
 
-impl<'a, T, M> MetricLayerBuilder<'a, T, M, Paired>
-where
-    M: MakeDefaultHandle<Out = T> + Default,
-{
-    /// Finalize the builder and get out the [`GenericMetricLayer`] and the
-    /// exporter handle out of it as a tuple.
-    pub fn build_pair(self) -> (GenericMetricLayer<'a, T, M>, T) {
-        GenericMetricLayer::pair_from_builder(self)
+    /// Enable the `axum_http_request_headers_bytes` histogram, which records the total size of the request headers
+    /// (the lengths of all header names and values), labelled by method and endpoint.
+    ///
+    /// This helps diagnosing header bloat, like large cookies or tokens.
+    pub fn enable_request_header_size(mut self, enable: bool) -> Self {
+        self.traffic.with_request_header_size(enable);
+        self
     }
-}
 
-#[cfg(feature = "prometheus")]
-/// A builder for [`crate::PrometheusMetricLayer`] that enables further customizations.
-pub type PrometheusMetricLayerBuilder<'a, S> =
-    MetricLayerBuilder<'a, PrometheusHandle, crate::Handle, S>;
+    /// Enable the `axum_http_requests_concurrency` histogram, which records the number of in-flight requests (including
+    /// the arriving one) every time a request arrives. Unlike the pending requests gauge, which is only a point-in-time
+    /// value at each scrape, this captures the distribution of concurrency between scrapes.
+    ///
+    /// Like [`GenericMetricLayer::pending_count`], this counts the requests of this layer only, and ignored requests
+    /// are not counted.
+    ///
+    /// [`GenericMetricLayer::pending_count`]: crate::GenericMetricLayer::pending_count
+    pub fn enable_concurrency(mut self, enable: bool) -> Self {
+        self.traffic.with_concurrency(enable);
+        self
+    }
 
-fn describe_metrics(enable_body_size: bool) {
-    metrics::describe_counter!(
-        crate::utils::requests_total_name(),
-        metrics::Unit::Count,
-        "The number of times a HTTP request was processed."
-    );
-    metrics::describe_gauge!(
-        crate::utils::requests_pending_name(),
-        metrics::Unit::Count,
-        "The number of currently in-flight requests."
-    );
-    metrics::describe_histogram!(
-        crate::utils::requests_duration_name(),
-        metrics::Unit::Seconds,
-        "The distribution of HTTP response times."
-    );
-    if enable_body_size {
+    /// Enable the `axum_http_requests_grouped_total` counter, which is incremented with a `group` label every time
+    /// a group pattern rewrites the endpoint of a request. Useful to verify that the grouping rules set by
+    /// [`with_group_patterns_as`] match as intended in production.
+    ///
+    /// [`with_group_patterns_as`]: MetricLayerBuilder::with_group_patterns_as
+    pub fn enable_grouped_total(mut self, enable: bool) -> Self {
+        self.traffic.with_grouped_total(enable);
+        self
+    }
+
+    /// Register a callback that's called with a [`RequestSummary`] every time a response is recorded, for side
+    /// effects that the built-in metrics don't cover, like logging slow requests.
+    ///
+    /// The callback runs synchronously on the response path, so it should return quickly. It's not called for
+    /// ignored requests.
+    ///
+    /// ```rust
+    /// use std::{sync::Arc, time::Duration};
+    ///
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .on_request_complete(Arc::new(|summary| {
+    ///         if summary.duration > Duration::from_secs(1) {
+    ///             eprintln!("slow request: {} {}", summary.method, summary.endpoint);
+    ///         }
+    ///     }))
+    ///     .build();
+    /// ```
+    ///
+    /// [`RequestSummary`]: crate::RequestSummary
+    pub fn on_request_complete(
+        mut self,
+        callback: Arc<dyn Fn(&RequestSummary<'_>) + Send + Sync>,
+    ) -> Self {
+        self.traffic.with_on_request_complete(callback);
+        self
+    }
+
+    /// Register a callback that's called with the duration of every request in seconds, right after it's recorded in
+    /// the requests duration histogram, to record it into additional histograms with custom labels.
+    ///
+    /// The callback runs synchronously on the response path of every request, so it should be cheap. It's not called
+    /// when the duration isn't recorded (for ignored requests, or failures with [`duration_only_for_success`]).
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    ///
+    /// use axum_prometheus::{metrics::histogram, PrometheusMetricLayerBuilder};
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .on_duration(Arc::new(|seconds, data, status| {
+    ///         let class = if status.is_server_error() { "error" } else { "ok" };
+    ///         let endpoint = data.endpoint.clone();
+    ///         histogram!("my_app_latency_seconds", "endpoint" => endpoint, "class" => class).record(seconds);
+    ///     }))
+    ///     .build();
+    /// ```
+    ///
+    /// [`duration_only_for_success`]: Self::duration_only_for_success
+    #[allow(clippy::type_complexity)]
+    pub fn on_duration(
+        mut self,
+        callback: Arc<dyn Fn(f64, &MetricsData, StatusCode) + Send + Sync>,
+    ) -> Self {
+        self.traffic.with_on_duration(callback);
+        self
+    }
+
+    /// Don't count responses with any of the given status codes in the requests total counter, e.g. `101` for
+    /// protocol upgrades. Unlike [`ignore_status_range`], these responses are still recorded in every other metric.
+    ///
+    /// ```rust
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .exclude_statuses(&[101])
+    ///     .build();
+    /// ```
+    ///
+    /// [`ignore_status_range`]: crate::MetricLayerBuilder::ignore_status_range
+    pub fn exclude_statuses(mut self, statuses: &'a [u16]) -> Self {
+        self.traffic.with_excluded_statuses(statuses);
+        self
+    }
+
+    /// Count responses with the given status code in a dedicated counter named `metric_name` (used as is, without
+    /// any prefix), labeled with the method and the endpoint. Can be called multiple times to register several
+    /// counters, even for the same status code.
+    ///
+    /// This gives purpose-built metrics to alert on, e.g. for rate-limited requests, instead of filtering the
+    /// requests total by the `status` label:
+    ///
+    /// ```rust
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .count_status(429, "my_app_rate_limited_total")
+    ///     .count_status(503, "my_app_unavailable_total")
+    ///     .build();
+    /// ```
+    ///
+    /// Responses with a status in the range set with [`ignore_status_range`] are not counted.
+    ///
+    /// [`ignore_status_range`]: crate::MetricLayerBuilder::ignore_status_range
+    pub fn count_status(mut self, status: u16, metric_name: &str) -> Self {
+        self.traffic.with_status_counter(status, metric_name);
+        self
+    }
+
+    /// Set the range of status codes that are classified as failures. By default, all `4xx` and `5xx`
+    /// responses are considered failures.
+    ///
+    /// For example, to only consider server errors as failures:
+    /// ```rust
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .with_failure_status_range(500..=599)
+    ///     .build();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Finalizing the builder panics if the start or end of the range isn't a valid status code.
+    pub fn with_failure_status_range(mut self, range: RangeInclusive<u16>) -> Self {
+        self.failure_status_range = Some(range);
+        self
+    }
+
+    /// Don't record responses whose status code falls in `range` at all, e.g. `100..=199` to skip
+    /// informational responses.
+    ///
+    /// Unlike [`with_failure_status_range`], which only affects how responses are classified, matching responses
+    /// are dropped from every metric recorded once the response is produced, like the requests total, the duration
+    /// histogram and the response body metrics. The metrics recorded when the request comes in, before its status is
+    /// known, still include them:
+    ///  - the pending requests gauge, which is still decremented (or the started and finished counters with
+    ///    [`pending_as_counters`])
+    ///  - the concurrency histogram of [`enable_concurrency`]
+    ///  - the inter-arrival histogram of [`enable_inter_arrival`]
+    ///  - the request header size histogram of [`enable_request_header_size`]
+    ///  - the grouped requests total of [`enable_grouped_total`]
+    ///
+    /// ```rust
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .ignore_status_range(300..=399)
+    ///     .build();
+    /// ```
+    ///
+    /// [`with_failure_status_range`]: crate::MetricLayerBuilder::with_failure_status_range
+    /// [`pending_as_counters`]: crate::MetricLayerBuilder::pending_as_counters
+    /// [`enable_concurrency`]: crate::MetricLayerBuilder::enable_concurrency
+    /// [`enable_inter_arrival`]: crate::MetricLayerBuilder::enable_inter_arrival
+    /// [`enable_request_header_size`]: crate::MetricLayerBuilder::enable_request_header_size
+    /// [`enable_grouped_total`]: crate::MetricLayerBuilder::enable_grouped_total
+    pub fn ignore_status_range(mut self, range: RangeInclusive<u16>) -> Self {
+        self.traffic.with_ignore_status_range(range);
+        self
+    }
+
+    /// Only record the request duration histogram for responses that are classified as successful.
+    ///
+    /// Error responses (e.g. instant `400`s or hung `504`s) can skew the latency distribution used for SLO latency
+    /// budgets. With this option enabled, the duration histogram only describes successful requests, while the requests
+    /// total counter still records every request. Responses are classified as failures if their status code is
+    /// `4xx` or `5xx`, unless configured otherwise with [`with_failure_status_range`].
+    ///
+    /// This is disabled by default.
+    ///
+    /// [`with_failure_status_range`]: crate::MetricLayerBuilder::with_failure_status_range
+    pub fn duration_only_for_success(mut self, enable: bool) -> Self {
+        self.traffic.with_duration_only_for_success(enable);
+        self
+    }
+
+    /// Attach a `content_type` label to the requests total counter and the request duration histogram, based on the
+    /// response `Content-Type` header. The header is normalized to a short token (e.g. `application/json` becomes
+    /// `json`) to keep the cardinality low, see [`utils::content_type_label`]. Off by default.
+    ///
+    /// [`utils::content_type_label`]: crate::utils::content_type_label
+    pub fn with_content_type_label(mut self, enable: bool) -> Self {
+        self.traffic.with_content_type_label(enable);
+        self
+    }
+
+    /// Attach the values of the listed request headers to every metric of a request, as `(header name, label key)`
+    /// pairs. Missing headers are reported as `none`.
+    ///
+    /// ## Warning
+    ///
+    /// Every distinct header value creates a new time series for __all__ metrics, so only list headers with a small,
+    /// fixed set of values (like a client or region header set by your own proxy). Never list headers controlled by
+    /// arbitrary clients, like `User-Agent`, or ones carrying identifiers, like `Authorization` or `Cookie`. Values are
+    /// sanitized and truncated to 64 bytes, but that doesn't bound the number of distinct values.
+    ///
+    /// ```rust
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .with_header_labels(&[("x-client", "client"), ("x-region", "region")])
+    ///     .build();
+    /// ```
+    pub fn with_header_labels(mut self, headers: &'a [(&'a str, &'static str)]) -> Self {
+        self.traffic.with_header_labels(headers);
+        self
+    }
+
+    /// Use the path in the [`OriginalPath`] request extension (if present) in place of the request URI path, so
+    /// metrics are keyed to the path as it was before being rewritten by middleware like `NormalizePath`.
+    ///
+    /// The middleware that rewrites the path must insert the [`OriginalPath`] first, and the metric layer has to be
+    /// applied inside of it:
+    /// ```rust
+    /// use axum::{extract::Request, middleware::Next, response::Response, routing::get, Router};
+    /// use axum_prometheus::{EndpointLabel, OriginalPath, PrometheusMetricLayerBuilder};
+    ///
+    /// async fn strip_version(mut request: Request, next: Next) -> Response {
+    ///     let path = request.uri().path().to_owned();
+    ///     if let Some(stripped) = path.strip_prefix("/v1") {
+    ///         *request.uri_mut() = stripped.parse().unwrap();
+    ///         request.extensions_mut().insert(OriginalPath(path));
+    ///     }
+    ///     next.run(request).await
+    /// }
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .with_endpoint_label_type(EndpointLabel::Exact)
+    ///     .with_original_path_from_extension()
+    ///     .build();
+    /// let app = Router::<()>::new()
+    ///     .route("/foo", get(|| async {}))
+    ///     .layer(metric_layer);
+    /// // The path is rewritten before routing.
+    /// let app = tower::Layer::layer(&axum::middleware::from_fn::<_, (Request,)>(strip_version), app);
+    /// ```
+    /// The original path replaces the exact path everywhere: in [`EndpointLabel::Exact`] labels, the fallback of the
+    /// other endpoint label types, and when matching ignore and body size patterns. The [`MatchedPath`] of a request
+    /// is still determined by the router from the rewritten path.
+    ///
+    /// [`OriginalPath`]: crate::OriginalPath
+    /// [`MatchedPath`]: axum::extract::MatchedPath
+    pub fn with_original_path_from_extension(mut self) -> Self {
+        self.traffic.with_original_path_from_extension();
+        self
+    }
+
+    /// Label the requests total counter with whether the request is a retry (`retry="true"`) or a fresh request
+    /// (`retry="false"`), so replayed requests can be told apart from new traffic.
+    ///
+    /// A retry layer above this one replays requests through it, so every attempt is counted. To label them, the retry
+    /// layer has to insert a [`RetryCount`] with the number of previous attempts into the extensions of every request
+    /// it sends. Requests without a [`RetryCount`] (or with a count of `0`) are reported as fresh.
+    ///
+    /// ```rust
+    /// use axum_prometheus::{PrometheusMetricLayerBuilder, RetryCount};
+    /// use http::Request;
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .with_retry_label()
+    ///     .build();
+    ///
+    /// // In the retry layer, when replaying a request for the second time:
+    /// let mut request = Request::new(());
+    /// request.extensions_mut().insert(RetryCount(2));
+    /// ```
+    ///
+    /// [`RetryCount`]: crate::RetryCount
+    pub fn with_retry_label(mut self) -> Self {
+        self.traffic.with_retry_label();
+        self
+    }
+
+    /// Label the requests total counter with the subnet of the client IP address under the `client_subnet` label,
+    /// to help spotting noisy networks (e.g. for abuse detection). Off by default.
+    ///
+    /// The IP address is read from the [`ConnectInfo<SocketAddr>`] request extension, so the server has to be
+    /// started with [`into_make_service_with_connect_info`]. Behind a proxy, use [`with_client_ip_header`] instead.
+    /// Requests without a known IP address are labeled with `unknown`.
+    ///
+    /// ```rust,no_run
+    /// use std::net::SocketAddr;
+    ///
+    /// use axum::{routing::get, Router};
+    /// use axum_prometheus::{IpLabelMode, PrometheusMetricLayerBuilder};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (metric_layer, metric_handle) = PrometheusMetricLayerBuilder::new()
+    ///         .with_client_ip_label(IpLabelMode::Subnet(24))
+    ///         .with_default_metrics()
+    ///         .build_pair();
+    ///     let app = Router::new().route("/", get(|| async {})).layer(metric_layer);
+    ///
+    ///     let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
+    ///     axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+    ///         .await
+    ///         .unwrap();
+    /// }
+    /// ```
+    ///
+    /// ## Privacy
+    ///
+    /// Individual addresses are never reported, only their subnet. Still, the longer the prefix, the fewer clients
+    /// share a subnet, so a long prefix (or a network with a single client) may make the label identify a person,
+    /// which can be personal data under regulations like the GDPR. Every distinct subnet also creates a new time
+    /// series, so keep the prefix short to protect privacy and bound the cardinality.
+    ///
+    /// [`ConnectInfo<SocketAddr>`]: axum::extract::ConnectInfo
+    /// [`into_make_service_with_connect_info`]: axum::Router::into_make_service_with_connect_info
+    /// [`with_client_ip_header`]: MetricLayerBuilder::with_client_ip_header
+    pub fn with_client_ip_label(mut self, mode: IpLabelMode) -> Self {
+        self.traffic.with_client_ip_label(mode);
+        self
+    }
+
+    /// Read the client IP address for [`with_client_ip_label`] from the `header` set by a reverse proxy (e.g.
+    /// `x-forwarded-for` or `x-real-ip`) instead of the connection. For headers listing several addresses, the first
+    /// one is used. Requests without the header fall back to the [`ConnectInfo`] extension.
+    ///
+    /// Clients can set these headers themselves, so only rely on them if the proxy overwrites them.
+    ///
+    /// [`with_client_ip_label`]: MetricLayerBuilder::with_client_ip_label
+    /// [`ConnectInfo`]: axum::extract::ConnectInfo
+    pub fn with_client_ip_header(mut self, header: &'a str) -> Self {
+        self.traffic.with_client_ip_header(header);
+        self
+    }
+
+    /// Emit the standard metrics of the given endpoint and method combinations with zero values when the layer is
+    /// built, so they exist from startup instead of appearing with the first request. This avoids gaps and missed
+    /// first increments in `rate()` for low-traffic routes.
+    ///
+    /// The endpoints are registered with a `200` status, exactly as given (e.g. `/users/{id}`), so they should match
+    /// the labels that are actually reported.
+    ///
+    /// ```rust,no_run
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
+    /// use http::Method;
+    ///
+    /// let (metric_layer, metric_handle) = PrometheusMetricLayerBuilder::new()
+    ///     .preregister_endpoints(&[
+    ///         ("/users", &[Method::GET, Method::POST]),
+    ///         ("/users/{id}", &[Method::GET]),
+    ///     ])
+    ///     .with_default_metrics()
+    ///     .build_pair();
+    /// ```
+    ///
+    /// The metrics are only registered if the exporter is installed by the time the layer is built, which is always
+    /// the case with [`build_pair`].
+    ///
+    /// [`build_pair`]: crate::MetricLayerBuilder::build_pair
+    pub fn preregister_endpoints(mut self, endpoints: &'a [(&'a str, &'a [Method])]) -> Self {
+        self.preregistered_endpoints = endpoints;
+        self
+    }
+
+    /// Record no metrics for requests that arrive within `warmup` after the layer is built, to keep startup noise
+    /// (like cold caches or connection pools being filled) out of the steady-state numbers.
+    ///
+    /// Requests during the warmup are not tracked at all, as if they were ignored, so the counters start from zero
+    /// once it's over.
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .with_warmup(Duration::from_secs(30))
+    ///     .build();
+    /// ```
+    pub fn with_warmup(mut self, warmup: Duration) -> Self {
+        self.warmup = Some(warmup);
+        self
+    }
+
+    /// Record metrics only for the requests that `sampled` returns `true` for, typically by reading the sampling
+    /// decision that a tracing layer inserted into the request extensions. This keeps the metrics aligned with the
+    /// sampled traces, for dashboards that correlate the two.
+    ///
+    /// Unsampled requests are not tracked at all, as if they were ignored, so every metric (including the requests
+    /// total) only covers the sampled fraction of the traffic. Don't use it for the metrics that need the overall
+    /// request counts, e.g. for alerting on error rates. The layer must run inside the layer that makes the sampling
+    /// decision: after it in a `ServiceBuilder` (outermost first), or with a `Router::layer` call preceding the sampling
+    /// layer's.
+    ///
+    /// The request parts are cloned for the function (including the headers and extensions), which comes with a cost
+    /// for every request.
+    ///
+    /// ```rust
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
+    ///
+    /// #[derive(Clone)]
+    /// struct Sampled(bool);
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .record_only_sampled(|parts| parts.extensions.get::<Sampled>().is_some_and(|s| s.0))
+    ///     .build();
+    /// ```
+    pub fn record_only_sampled(mut self, sampled: fn(&http::request::Parts) -> bool) -> Self {
+        self.traffic.with_sampled(sampled);
+        self
+    }
+
+    /// Cap the recorded request durations at `max`. Off by default.
+    ///
+    /// Durations are measured with a monotonic clock, which may keep running while the system is suspended, so a
+    /// request in flight during a suspend can be recorded as taking hours. Clamping keeps such artifacts from landing
+    /// as huge outliers in the top bucket of the duration histogram.
+    ///
+    /// The tradeoff is that genuinely slow requests taking longer than `max` are recorded as taking exactly `max`, so
+    /// pick a value well above any latency you expect to observe (e.g. above your request timeout).
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .clamp_duration(Duration::from_secs(120))
+    ///     .build();
+    /// ```
+    pub fn clamp_duration(mut self, max: Duration) -> Self {
+        self.traffic.with_max_duration(max);
+        self
+    }
+
+    /// Set a latency SLO of `max` for the endpoints matching `pattern`, and count the requests to them that take
+    /// longer in the `axum_http_slo_violations_total` counter (labels: endpoint). Can be called multiple times to set
+    /// SLOs for several patterns.
+    ///
+    /// This gives a direct SLO burn signal, without deriving it from the duration histogram (whose buckets rarely
+    /// line up with the SLO). The pattern is matched against the path the endpoint label is derived from (e.g. the
+    /// matched path, or the group pattern if grouping is used), before the method, the root label, lowercasing, the
+    /// fallback label or the label sanitizer are applied. So it should use the same syntax as the routes (e.g.
+    /// `/users/{id}`), even with [`EndpointLabel::MatchedPathWithMethod`].
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .with_slo("/users/{id}", Duration::from_millis(100))
+    ///     .with_slo("/export/{*rest}", Duration::from_secs(5))
+    ///     .build();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pattern` is malformed or conflicts with a previously added one.
+    ///
+    /// [`EndpointLabel::MatchedPathWithMethod`]: crate::EndpointLabel::MatchedPathWithMethod
+    pub fn with_slo(mut self, pattern: &str, max: Duration) -> Self {
+        self.traffic.with_slo(pattern, max);
+        self
+    }
+
+    /// Same as [`with_slo`], but returns an error instead of panicking if the pattern is malformed or conflicts with
+    /// a previously added one.
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
+    ///
+    /// let result = PrometheusMetricLayerBuilder::new().try_with_slo("/users/{id", Duration::from_millis(100));
+    /// assert!(result.is_err());
+    /// ```
+    ///
+    /// [`with_slo`]: crate::MetricLayerBuilder::with_slo
+    pub fn try_with_slo(mut self, pattern: &str, max: Duration) -> Result<Self, RouteSpecError> {
+        self.traffic.try_with_slo(pattern, max)?;
+        Ok(self)
+    }
+
+    /// Label the requests total counter of failed responses with the domain error code read from `source`, under
+    /// the `error_code` label. Failed responses without an error code are labeled with `none`.
+    ///
+    /// Successful responses (as determined by the classifier, see [`with_failure_status_range`]) are never labeled, to
+    /// keep the cardinality in check. Streaming responses are classified once the stream ends, so they're never labeled
+    /// either.
+    ///
+    /// ```rust
+    /// use axum_prometheus::{ErrorCodeSource, PrometheusMetricLayerBuilder};
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .with_error_code_label(ErrorCodeSource::header("x-error-code"))
+    ///     .build();
+    /// ```
+    ///
+    /// [`with_failure_status_range`]: crate::MetricLayerBuilder::with_failure_status_range
+    pub fn with_error_code_label(mut self, source: ErrorCodeSource) -> Self {
+        self.traffic.with_error_code_label(source);
+        self
+    }
+
+    /// Read the status of each request from the response trailer `header_name` (e.g. `grpc-status`), and count the
+    /// requests by that status in the `axum_grpc_requests_total` counter, under the `grpc_status` label.
+    ///
+    /// gRPC responses always have a `200` HTTP status, and carry the actual status in the trailers, so this is
+    /// useful for gRPC services (like `tonic`) served through `axum`. Trailers-only responses, which carry the status in
+    /// the headers instead, are counted too.
+    ///
+    /// ```rust
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .with_trailer_status("grpc-status")
+    ///     .build();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `header_name` is not a valid header name.
+    pub fn with_trailer_status(mut self, header_name: &str) -> Self {
+        self.traffic.with_trailer_status(header_name);
+        self
+    }
+
+    /// Attach an `encoding` label to the response body size metric, read from the response `Content-Encoding` header
+    /// (`gzip`, `br`, `deflate`, `zstd`, `identity` if the header is missing, or `other`). Off by default.
+    ///
+    /// #### Note:
+    /// The recorded size depends on the order of the layers. If this layer wraps a compression layer (like
+    /// `tower_http::compression::CompressionLayer`), it sees the compressed body, and this label tells which encoding
+    /// it's in. If the compression layer wraps this one instead, the recorded size is the uncompressed size, and the
+    /// label is always `identity`.
+    pub fn with_content_encoding_label(mut self, enable: bool) -> Self {
+        self.traffic.with_content_encoding_label(enable);
+        self
+    }
+
+    /// Attach a `depth` label to all metrics, with the number of path segments in the endpoint (after grouping),
+    /// e.g. `2` for `/users/{id}`. Useful to spot hotspots in deeply nested routers. Off by default.
+    pub fn with_depth_label(mut self, enable: bool) -> Self {
+        self.traffic.with_depth_label(enable);
+        self
+    }
+
+    /// Attach a `query_keys` label to all metrics, with the keys of the request's query parameters (never their
+    /// values), e.g. `limit,offset` for `/items?offset=10&limit=5`. Useful for APIs that behave differently depending
+    /// on which parameters are present. The keys are sorted and deduplicated, so the label doesn't depend on their
+    /// order in the URI, see [`utils::query_keys_label`]. Off by default.
+    ///
+    /// ## Warning
+    ///
+    /// Query parameter keys are chosen by clients, and every distinct combination creates a new time series for
+    /// __all__ metrics. Only enable it if unknown parameters are rejected before reaching this layer.
+    ///
+    /// [`utils::query_keys_label`]: crate::utils::query_keys_label
+    pub fn with_query_key_label(mut self, enable: bool) -> Self {
+        self.traffic.with_query_key_label(enable);
+        self
+    }
+
+    /// Attach a `cache` label to the requests total counter, based on the response header `header_name` set by a
+    /// caching middleware (e.g. `X-Cache`). To keep the cardinality low, the label is normalized to one of three
+    /// values: `hit` if the header value contains "hit" (ignoring case), `miss` for any other value, and `none`
+    /// if the header is missing. Off by default.
+    ///
+    /// ```rust
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .with_cache_label("x-cache")
+    ///     .build();
+    /// ```
+    pub fn with_cache_label(mut self, header_name: &'a str) -> Self {
+        self.traffic.with_cache_label(header_name);
+        self
+    }
+
+    /// Attach a `retry_after` label to the requests total counter, which is `true` if the response has a
+    /// `Retry-After` header, and `false` otherwise. Rate limiters and services in maintenance set this header, so
+    /// it tracks how often the server is shedding load. Off by default.
+    pub fn with_retry_after_label(mut self, enable: bool) -> Self {
+        self.traffic.with_retry_after_label(enable);
+        self
+    }
+
+    /// Attach a `has_body` label to the requests duration histogram, which is `true` if the request has a body (a
+    /// `Transfer-Encoding` header, or a non-zero `Content-Length`), and `false` otherwise. This splits the latency of
+    /// uploads from body-less requests without tracking the request body size. Off by default.
+    pub fn label_has_body(mut self, enable: bool) -> Self {
+        self.traffic.with_has_body_label(enable);
+        self
+    }
+
+    /// Drop the `method` label from the pending requests gauge, to reduce the number of its series.
+    pub fn pending_without_method(mut self, enable: bool) -> Self {
+        self.traffic.with_pending_without_method(enable);
+        self
+    }
+
+    /// Drop the `status` label from the request duration histogram, while keeping it on the requests total counter.
+    ///
+    /// Every distinct status code multiplies the number of histogram bucket series, which is usually the biggest
+    /// cardinality cost of this crate. With this option, you still get request counts by status, but latency is
+    /// only broken down by `method` and `endpoint`.
+    pub fn histogram_without_status(mut self, enable: bool) -> Self {
+        self.traffic.with_histogram_without_status(enable);
+        self
+    }
+
+    /// Enable request-scoped phase timing.
+    ///
+    /// A [`PhaseTimer`] is inserted into the extensions of every tracked request, which handlers can use to record
+    /// when a phase of their work is done. The recorded phases are reported in the `axum_http_requests_phase_seconds`
+    /// histogram (labels: endpoint, method, phase). See [`PhaseTimer`] for an example.
+    ///
+    /// [`PhaseTimer`]: crate::PhaseTimer
+    pub fn enable_phase_timing(mut self) -> Self {
+        self.traffic.with_phase_timing(true);
+        self
+    }
+
+    /// Let handlers report the duration of their requests themselves, via a [`DurationOverride`] request extension.
+    /// This is an escape hatch for handlers whose wall-clock time doesn't reflect the meaningful work, see
+    /// [`DurationOverride`] for the caveats and an example.
+    ///
+    /// [`DurationOverride`]: crate::DurationOverride
+    pub fn enable_duration_override(mut self) -> Self {
+        self.traffic.with_duration_override(true);
+        self
+    }
+
+    /// Record how long requests waited for the wrapped service to become ready, e.g. for a permit of a
+    /// `tower::limit::ConcurrencyLimit` layer.
+    ///
+    /// The wait is measured from the first `poll_ready` call on the metric layer until the request is passed to
+    /// `call`, and reported in the `axum_http_requests_phase_seconds` histogram under the `limiter_wait` phase. It's
+    /// recorded independently of [`enable_phase_timing`].
+    ///
+    /// The measurement is only meaningful if the metric layer sits *above* the limiter, so that the limiter's
+    /// `poll_ready` is what the metric layer waits on. Add the limiter before the metric layer in a
+    /// `ServiceBuilder` (outermost first), or with a `Router::layer` call preceding the metric layer's:
+    ///
+    /// ```rust,no_run
+    /// use axum::{routing::get, Router};
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
+    /// use tower::{limit::ConcurrencyLimitLayer, ServiceBuilder};
+    ///
+    /// let (metric_layer, metric_handle) = PrometheusMetricLayerBuilder::new()
+    ///     .enable_limiter_wait()
+    ///     .with_default_metrics()
+    ///     .build_pair();
+    /// let app = Router::<()>::new().route("/", get(|| async {})).layer(
+    ///     ServiceBuilder::new()
+    ///         .layer(metric_layer)
+    ///         .layer(ConcurrencyLimitLayer::new(64)),
+    /// );
+    /// ```
+    ///
+    /// Note that `Router::layer` wraps every route separately, so each route gets its own limit here. Wrap the
+    /// whole `Router` instead to share a single limit between all routes.
+    ///
+    /// [`enable_phase_timing`]: MetricLayerBuilder::enable_phase_timing
+    pub fn enable_limiter_wait(mut self) -> Self {
+        self.traffic.with_limiter_wait(true);
+        self
+    }
+
+    /// Report endpoints that only differ in a trailing slash (e.g. `/foo` and `/foo/`) under the same label.
+    ///
+    /// When enabled, a single trailing slash is stripped from the endpoint label (except for the root path `/`)
+    /// after it's determined by [`EndpointLabel`], but before any group pattern is applied.
+    ///
+    /// ```rust
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .normalize_trailing_slash(true)
+    ///     .build();
+    /// ```
+    pub fn normalize_trailing_slash(mut self, normalize: bool) -> Self {
+        self.traffic.with_normalize_trailing_slash(normalize);
+        self
+    }
+
+    /// Report requests to the root path `/` under the endpoint label `root_label` instead, e.g. `"root"` or `"index"`.
+    ///
+    /// Only the exact `/` endpoint is relabeled, after trailing slash normalization and group patterns are applied.
+    ///
+    /// ```rust
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .with_root_label("index")
+    ///     .build();
+    /// ```
+    pub fn with_root_label(mut self, root_label: &'a str) -> Self {
+        self.traffic.with_root_label(root_label);
+        self
+    }
+
+    /// Sanitize the endpoint label with `sanitizer` before it's reported, so characters that the exporter would
+    /// otherwise escape or mangle are replaced in a predictable way.
+    ///
+    /// The sanitizer runs last, after every other endpoint label transformation. [`utils::sanitize_label`] is a
+    /// built-in implementation that replaces characters requiring escaping in the Prometheus text format.
+    ///
+    /// ```rust
+    /// use axum_prometheus::{utils, PrometheusMetricLayerBuilder};
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .with_label_sanitizer(utils::sanitize_label)
+    ///     .build();
+    /// ```
+    ///
+    /// [`utils::sanitize_label`]: crate::utils::sanitize_label
+    pub fn with_label_sanitizer(mut self, sanitizer: for<'s> fn(&'s str) -> Cow<'s, str>) -> Self {
+        self.traffic.with_label_sanitizer(sanitizer);
+        self
+    }
+
+    /// Enable response body size tracking.
+    ///
+    /// Besides the body size histogram, the total number of bytes sent is counted in the
+    /// `axum_http_response_body_bytes_total` counter (labels: endpoint), which suits throughput dashboards better.
+    ///
+    /// With the default Prometheus recorder, body sizes are rendered as a summary with the exporter's default
+    /// quantiles. To use other quantiles, see [`with_body_size_as_summary`]. To use buckets, set them on a
+    /// `PrometheusBuilder` and pass its handle to [`with_metrics_from_recorder`].
+    ///
+    /// #### Note:
+    /// This may introduce some performance overhead.
+    ///
+    /// [`with_body_size_as_summary`]: MetricLayerBuilder::with_body_size_as_summary
+    /// [`with_metrics_from_recorder`]: MetricLayerBuilder::with_metrics_from_recorder
+    pub fn enable_response_body_size(mut self, enable: bool) -> Self {
+        self.traffic.with_body_size(enable);
+        self
+    }
+
+    /// Leave the bodies of responses classified as failures out of the response body size histogram, to keep its
+    /// distribution focused on real payloads. Their bytes are still counted in the response body bytes total. Off by
+    /// default.
+    ///
+    /// Responses are classified as failures if their status code is `4xx` or `5xx`, unless configured otherwise with
+    /// [`with_failure_status_range`].
+    ///
+    /// This only has an effect if the response body size is tracked, see [`enable_response_body_size`].
+    ///
+    /// [`with_failure_status_range`]: MetricLayerBuilder::with_failure_status_range
+    /// [`enable_response_body_size`]: MetricLayerBuilder::enable_response_body_size
+    pub fn body_size_success_only(mut self, enable: bool) -> Self {
+        self.traffic.with_body_size_success_only(enable);
+        self
+    }
+
+    /// Enable response body size tracking, but only for requests whose path matches any of `patterns`. Can be
+    /// called repeatedly to add more patterns.
+    ///
+    /// Like ignore patterns, these are matched against the requested path, and support the same features as
+    /// `axum`'s Router.
+    ///
+    /// ```rust
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .body_size_for_patterns(&["/download/{file}", "/export"])
+    ///     .build();
+    /// ```
+    ///
+    /// #### Note:
+    /// Response bodies of other endpoints are still wrapped, but the wrapper doesn't record anything for them,
+    /// so it only adds minimal overhead.
+    pub fn body_size_for_patterns(mut self, patterns: &'a [&'a str]) -> Self {
+        self.traffic.with_body_size_patterns(patterns);
+        self
+    }
+
+    /// Same as [`body_size_for_patterns`], but returns an error instead of panicking if any of the patterns is
+    /// malformed or conflicts with a previously added one.
+    ///
+    /// [`body_size_for_patterns`]: crate::MetricLayerBuilder::body_size_for_patterns
+    pub fn try_body_size_for_patterns(
+        mut self,
+        patterns: &'a [&'a str],
+    ) -> Result<Self, RouteSpecError> {
+        self.traffic.try_with_body_size_patterns(patterns)?;
+        Ok(self)
+    }
+
+    /// Enable tracking the number of data chunks each response body produced.
+    ///
+    /// The count is recorded in a histogram once the response body has been fully streamed, which
+    /// helps diagnosing streaming efficiency (e.g. too many tiny chunks).
+    ///
+    /// #### Note:
+    /// This may introduce some performance overhead.
+    pub fn enable_response_chunks(mut self, enable: bool) -> Self {
+        self.traffic.with_response_chunks(enable);
+        self
+    }
+
+    /// Enable tracking how long it took to stream the response body in the `axum_http_stream_duration_seconds`
+    /// histogram (labels: endpoint, method), measured from when the response was generated until the end of the body.
+    ///
+    /// The requests duration histogram only measures the time until the response is generated, so for streaming
+    /// responses this separates handler latency from streaming time. Bodies that are never fully streamed (e.g. because
+    /// the client disconnected) are not recorded.
+    ///
+    /// #### Note:
+    /// This may introduce some performance overhead.
+    pub fn enable_stream_duration(mut self, enable: bool) -> Self {
+        self.traffic.with_stream_duration(enable);
+        self
+    }
+
+    /// Enable tracking the time between consecutive requests to the same endpoint (with the same method) in the
+    /// `axum_http_inter_arrival_seconds` histogram (labels: endpoint, method), which is useful for traffic-shape
+    /// analysis and capacity planning.
+    ///
+    /// The first request to an endpoint is not recorded, since there's no previous request to compare to.
+    ///
+    /// #### Note:
+    /// This keeps the time of the last request for every reported endpoint and method in memory, for at most 1024 of
+    /// them. Once that many are tracked, requests to any other endpoint aren't recorded. The map is shared by all requests and locked by every one of them, so it may become a
+    /// point of contention under high concurrency.
+    pub fn enable_inter_arrival(mut self, enable: bool) -> Self {
+        self.traffic.with_inter_arrival(enable);
+        self
+    }
+
+    /// Report the metrics of this layer under `namespace`, using the following metric names:
+    ///  - `{namespace}_http_requests_total`
+    ///  - `{namespace}_http_requests_pending`
+    ///  - `{namespace}_http_requests_duration_seconds`
+    ///
+    /// ..and the names of any optional metrics enabled on the builder (e.g. `{namespace}_http_response_body_size`)
+    /// are namespaced the same way.
+    ///
+    /// Unlike [`with_prefix`], the namespace is stored on the layer itself rather than in a global, so multiple
+    /// layers in the same process (e.g. a public and an internal API) can report independently:
+    /// ```rust,no_run
+    /// use axum::{routing::get, Router};
+    /// use axum_prometheus::{PrometheusMetricLayer, PrometheusMetricLayerBuilder};
+    ///
+    /// let (public_layer, metric_handle) = PrometheusMetricLayerBuilder::new()
+    ///     .with_namespace("public")
+    ///     .with_default_metrics()
+    ///     .build_pair();
+    /// // The recorder is already installed, so the second layer is built without a handle.
+    /// let internal_layer = PrometheusMetricLayerBuilder::new()
+    ///     .with_namespace("internal")
+    ///     .build();
+    ///
+    /// let public_api = Router::<()>::new().route("/", get(|| async {})).layer(public_layer);
+    /// let internal_api = Router::<()>::new().route("/", get(|| async {})).layer(internal_layer);
+    /// ```
+    /// The namespace takes precedence over both [`with_prefix`] and the environment variables.
+    ///
+    /// ## Note
+    ///
+    /// The exporters attached with [`with_default_metrics`], [`with_metrics_from`] and [`with_bucket_preset`] configure
+    /// their histogram buckets for the namespaced names. Exporters built with [`with_metrics_from_fn`] have to do so
+    /// themselves, e.g. with `Matcher::Suffix("_http_requests_duration_seconds".to_string())`.
+    ///
+    /// [`with_default_metrics`]: crate::MetricLayerBuilder::with_default_metrics
+    /// [`with_metrics_from`]: crate::MetricLayerBuilder::with_metrics_from
+    /// [`with_bucket_preset`]: crate::MetricLayerBuilder::with_bucket_preset
+    /// [`with_prefix`]: crate::MetricLayerBuilder::with_prefix
+    /// [`with_metrics_from_fn`]: crate::MetricLayerBuilder::with_metrics_from_fn
+    pub fn with_namespace(mut self, namespace: &str) -> Self {
+        self.traffic.with_namespace(namespace);
+        self
+    }
+
+    /// Attach a constant `version` label to all emitted metrics.
+    ///
+    /// This is useful to correlate changes in metrics with deploys, for example:
+    /// ```rust
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .with_version_label(env!("CARGO_PKG_VERSION"))
+    ///     .build();
+    /// ```
+    pub fn with_version_label(mut self, version: impl Into<String>) -> Self {
+        self.traffic.with_global_label("version", version.into());
+        self
+    }
+
+    /// Attach a constant `version` label to all emitted metrics, read from the environment variable `var`.
+    ///
+    /// Unlike [`env!`], the variable is read at runtime when this method is called, so it's suitable for values
+    /// injected at deploy time (e.g. `GIT_SHA`). If the variable is not set (or is not valid unicode), no label is attached.
+    ///
+    /// ```rust
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .with_version_from_env("GIT_SHA")
+    ///     .build();
+    /// ```
+    pub fn with_version_from_env(self, var: &str) -> Self {
+        match std::env::var(var) {
+            Ok(version) => self.with_version_label(version),
+            Err(_) => self,
+        }
+    }
+
+    /// By default, all metrics are initialized via `metrics::describe_*` macros, setting descriptions and units.
+    ///
+    /// This function disables this initialization.
+    pub fn no_initialize_metrics(mut self) -> Self {
+        self.no_initialize_metrics = true;
+        self
+    }
+
+    /// Override the descriptions (the `# HELP` text in Prometheus) of the standard metrics.
+    ///
+    /// These descriptions are registered even if [`no_initialize_metrics`] is set, in which case only the standard
+    /// metrics (the requests total, the request duration, the pending requests and the response body size) are
+    /// described, and none of the optional ones.
+    ///
+    /// ```rust
+    /// use axum_prometheus::{Descriptions, PrometheusMetricLayerBuilder};
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .with_descriptions(Descriptions {
+    ///         total: "Requests served by the checkout service.",
+    ///         duration: "Latency of the checkout service.",
+    ///         ..Default::default()
+    ///     })
+    ///     .build();
+    /// ```
+    ///
+    /// [`no_initialize_metrics`]: MetricLayerBuilder::no_initialize_metrics
+    pub fn with_descriptions(mut self, descriptions: Descriptions) -> Self {
+        self.descriptions = Some(descriptions);
+        self
+    }
+}
+
+impl<'a, T, M> MetricLayerBuilder<'a, T, M, LayerOnly> {
+    /// Initialize the builder.
+    pub fn new() -> MetricLayerBuilder<'a, T, M, LayerOnly> {
+        MetricLayerBuilder {
+            _marker: PhantomData,
+            traffic: Traffic::new(),
+            metric_handle: None,
+            no_initialize_metrics: false,
+            custom_handle: false,
+            metric_prefix: None,
+            prefixed_metrics: None,
+            failure_status_range: None,
+            preregistered_endpoints: &[],
+            warmup: None,
+            exporter_opts: ExporterOpts::default(),
+            descriptions: None,
+        }
+    }
+
+    /// Use a prefix for the metrics instead of `axum`. This will use the following
+    /// metric names:
+    ///  - `{prefix}_http_requests_total`
+    ///  - `{prefix}_http_requests_pending`
+    ///  - `{prefix}_http_requests_duration_seconds`
+    ///
+    /// ..and the names of any optional metrics enabled on the builder (e.g. `{prefix}_http_response_body_size`)
+    /// are prefixed the same way.
+    ///
+    /// This method will take precedence over environment variables.
+    ///
+    /// ## Note
+    ///
+    /// This function inherently changes the metric names, beware to use the appropriate names.
+    /// There're functions in the `utils` module to get them at runtime.
+    ///
+    /// [`utils`]: crate::utils
+    pub fn with_prefix(mut self, prefix: impl Into<Cow<'a, str>>) -> Self {
+        self.metric_prefix = Some(prefix.into().into_owned());
+        self.prefixed_metrics = None;
+        self
+    }
+
+    /// Same as [`with_prefix`], but only the given `metrics` are prefixed, e.g. to keep the standard
+    /// `axum_http_requests_total` name for existing dashboards while prefixing the others:
+    /// ```rust
+    /// use axum_prometheus::{MetricKind, PrometheusMetricLayerBuilder};
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new()
+    ///     .with_prefix_for(&[MetricKind::RequestsPending, MetricKind::RequestsDuration], "my_app")
+    ///     .build();
+    /// ```
+    /// The selected metrics take the prefix over their environment variables, while the rest keep the names from the
+    /// environment variables (or the defaults). Optional metrics other than [`MetricKind::ResponseBodySize`] are
+    /// never prefixed. This replaces any prefix previously set by [`with_prefix`].
+    ///
+    /// [`with_prefix`]: crate::MetricLayerBuilder::with_prefix
+    pub fn with_prefix_for(
+        mut self,
+        metrics: &[MetricKind],
+        prefix: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        self.metric_prefix = Some(prefix.into().into_owned());
+        self.prefixed_metrics = Some(metrics.to_vec());
+        self
+    }
+}
+impl<'a, T, M> MetricLayerBuilder<'a, T, M, LayerOnly>
+where
+    M: MakeDefaultHandle<Out = T>,
+{
+    /// Finalize the builder and get the previously registered metric handle out of it.
+    pub fn build(self) -> GenericMetricLayer<'a, T, M> {
+        GenericMetricLayer::from_builder(self)
+    }
+}
+
+impl<'a, T, M> MetricLayerBuilder<'a, T, M, LayerOnly>
+where
+    M: MakeDefaultHandle<Out = T>,
+{
+    /// Attach the exporter handle of `m` to the builder, initialized with the options of the builder like the
+    /// namespace. This is similar to initializing with [`GenericMetricLayer::pair_from`], and the counterpart of
+    /// [`with_default_metrics`] for exporters without a `Default` implementation.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use axum_prometheus::{AdaptiveBucketsRecorder, MetricLayerBuilder};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let (metric_layer, metric_handle) = MetricLayerBuilder::new()
+    ///     .with_namespace("api")
+    ///     .with_metrics_from(AdaptiveBucketsRecorder::new(1000))
+    ///     .build_pair();
+    /// # }
+    /// ```
+    /// After calling this function you can finalize with the [`build_pair`] method, and
+    /// can no longer call [`build`].
+    ///
+    /// [`GenericMetricLayer::pair_from`]: crate::GenericMetricLayer::pair_from
+    /// [`with_default_metrics`]: crate::MetricLayerBuilder::with_default_metrics
+    /// [`build`]: crate::MetricLayerBuilder::build
+    /// [`build_pair`]: crate::MetricLayerBuilder::build_pair
+    pub fn with_metrics_from(self, m: M) -> MetricLayerBuilder<'a, T, M, Paired> {
+        let opts = self.exporter_opts();
+        MetricLayerBuilder::<'_, _, _, Paired>::from_layer_only(self, || {
+            m.make_default_handle_with_opts(&opts)
+        })
+    }
+
+    /// The options to initialize the exporter with, including the metric names of the layer.
+    fn exporter_opts(&self) -> ExporterOpts {
+        ExporterOpts {
+            names: self.traffic.names.clone(),
+            ..self.exporter_opts.clone()
+        }
+    }
+}
+
+impl<'a, T, M> MetricLayerBuilder<'a, T, M, LayerOnly>
+where
+    M: MakeDefaultHandle<Out = T> + Default,
+{
+    /// Attach the default exporter handle to the builder. This is similar to
+    /// initializing with [`GenericMetricLayer::pair`].
+    ///
+    /// After calling this function you can finalize with the [`build_pair`] method, and
+    /// can no longer call [`build`].
+    ///
+    /// [`build`]: crate::MetricLayerBuilder::build
+    /// [`build_pair`]: crate::MetricLayerBuilder::build_pair
+    pub fn with_default_metrics(self) -> MetricLayerBuilder<'a, T, M, Paired> {
+        let opts = self.exporter_opts();
+        MetricLayerBuilder::<'_, _, _, Paired>::from_layer_only(self, || {
+            M::default_handle_with_opts(&opts)
+        })
+    }
+
+    /// Set the prefix the exporter should put in front of every metric name, passed to it in the [`ExporterOpts`]
+    /// of [`MakeDefaultHandle::make_default_handle_with_opts`] by [`with_default_metrics`].
+    ///
+    /// Unlike [`with_prefix`], this doesn't rename the metrics recorded by the layer, so it's meant for exporters
+    /// with their own prefix setting, like StatsD. Exporters that don't override
+    /// [`MakeDefaultHandle::make_default_handle_with_opts`] ignore it.
+    ///
+    /// [`with_default_metrics`]: MetricLayerBuilder::with_default_metrics
+    /// [`with_prefix`]: MetricLayerBuilder::with_prefix
+    /// [`ExporterOpts`]: crate::ExporterOpts
+    pub fn with_exporter_prefix(mut self, prefix: &str) -> Self {
+        self.exporter_opts.prefix = Some(prefix.to_owned());
+        self
+    }
+}
+impl<'a, T, M> MetricLayerBuilder<'a, T, M, LayerOnly> {
+    /// Attach a custom built exporter handle to the builder that's returned from the passed
+    /// in closure.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use axum_prometheus::{
+    ///        PrometheusMetricLayerBuilder, AXUM_HTTP_REQUESTS_DURATION_SECONDS, utils::SECONDS_DURATION_BUCKETS,
+    /// };
+    /// use metrics_exporter_prometheus::{Matcher, PrometheusBuilder};
+    ///
+    /// let (metric_layer, metric_handle) = PrometheusMetricLayerBuilder::new()
+    ///     .with_metrics_from_fn(|| {
+    ///         PrometheusBuilder::new()
+    ///             .set_buckets_for_metric(
+    ///                 Matcher::Full(AXUM_HTTP_REQUESTS_DURATION_SECONDS.to_string()),
+    ///                 SECONDS_DURATION_BUCKETS,
+    ///             )
+    ///             .unwrap()
+    ///             .install_recorder()
+    ///             .unwrap()
+    ///     })
+    ///     .build_pair();
+    /// ```
+    /// After calling this function you can finalize with the [`build_pair`] method, and
+    /// can no longer call [`build`].
+    ///
+    /// [`build`]: crate::MetricLayerBuilder::build
+    /// [`build_pair`]: crate::MetricLayerBuilder::build_pair
+    pub fn with_metrics_from_fn(
+        self,
+        f: impl FnOnce() -> T,
+    ) -> MetricLayerBuilder<'a, T, M, Paired> {
+        let mut builder = MetricLayerBuilder::<'_, _, _, Paired>::from_layer_only(self, f);
+        builder.custom_handle = true;
+        builder
+    }
+
+    /// Attach an already built exporter handle to the builder.
+    ///
+    /// Unlike [`with_metrics_from_fn`], this doesn't run any initialization, which is useful when the recorder has
+    /// already been installed by another part of your application, and you only want the layer to use its handle.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
+    /// use metrics_exporter_prometheus::PrometheusBuilder;
+    ///
+    /// // Installed elsewhere, e.g. by another subsystem.
+    /// let handle = PrometheusBuilder::new().install_recorder().unwrap();
+    ///
+    /// let (metric_layer, metric_handle) = PrometheusMetricLayerBuilder::new()
+    ///     .with_metrics_from_recorder(handle)
+    ///     .build_pair();
+    /// ```
+    /// After calling this function you can finalize with the [`build_pair`] method, and
+    /// can no longer call [`build`].
+    ///
+    /// [`with_metrics_from_fn`]: crate::MetricLayerBuilder::with_metrics_from_fn
+    /// [`build`]: crate::MetricLayerBuilder::build
+    /// [`build_pair`]: crate::MetricLayerBuilder::build_pair
+    pub fn with_metrics_from_recorder(self, handle: T) -> MetricLayerBuilder<'a, T, M, Paired> {
+        self.with_metrics_from_fn(|| handle)
+    }
+}
+
+impl<'a, T, M> MetricLayerBuilder<'a, T, M, Paired> {
+    /// Attach the exporter handle returned by `make_handle`, and emit the build info into the exporter.
+    ///
+    /// The prefix is set before `make_handle` runs, since the default exporters configure their buckets by the
+    /// (prefixed) metric names.
+    pub(crate) fn from_layer_only(
+        layer_only: MetricLayerBuilder<'a, T, M, LayerOnly>,
+        make_handle: impl FnOnce() -> T,
+    ) -> Self {
+        match (&layer_only.metric_prefix, &layer_only.prefixed_metrics) {
+            (Some(prefix), Some(metrics)) => set_prefix_for(metrics, prefix),
+            (Some(prefix), None) => set_prefix(prefix),
+            (None, _) => {}
+        }
+        let builder = MetricLayerBuilder {
+            _marker: PhantomData,
+            traffic: layer_only.traffic,
+            metric_handle: Some(make_handle()),
+            no_initialize_metrics: layer_only.no_initialize_metrics,
+            custom_handle: layer_only.custom_handle,
+            metric_prefix: layer_only.metric_prefix,
+            prefixed_metrics: layer_only.prefixed_metrics,
+            failure_status_range: layer_only.failure_status_range,
+            preregistered_endpoints: layer_only.preregistered_endpoints,
+            warmup: layer_only.warmup,
+            exporter_opts: layer_only.exporter_opts,
+            descriptions: layer_only.descriptions,
+        };
+        // The exporter is installed at this point, so the build info isn't lost.
+        builder.emit_build_info();
+        builder
+    }
+
+    /// Emit the `axum_prometheus_build_info` gauge, which describes how this layer is configured.
+    fn emit_build_info(&self) {
+        // Only keep the name of the type, e.g. `FanoutRecorder` instead of `axum_prometheus::FanoutRecorder<..>`.
+        let exporter = std::any::type_name::<M>();
+        let exporter = exporter.split('<').next().unwrap_or(exporter);
+        let exporter = exporter.rsplit("::").next().unwrap_or(exporter);
+        metrics::gauge!(
+            "axum_prometheus_build_info",
+            "body_size" => self.traffic.body_size.to_string(),
+            "pending" => (!self.traffic.pending_as_counters).to_string(),
+            "prefix" => self.metric_prefix.clone().unwrap_or_default(),
+            "exporter" => exporter.to_owned(),
+        )
+        .set(1);
+    }
+}
+
+impl<'a, T, M> MetricLayerBuilder<'a, T, M, Paired>
+where
+    M: MakeDefaultHandle<Out = T>,
+{
+    /// Describe the metrics, unless disabled. This has to happen after the exporter is installed, otherwise the
+    /// descriptions are lost.
+    pub(crate) fn describe_metrics(&self) {
+        match (&self.descriptions, self.no_initialize_metrics) {
+            (descriptions, false) => {
+                describe_metrics(&self.traffic, &descriptions.clone().unwrap_or_default())
+            }
+            (Some(descriptions), true) => describe_standard_metrics(&self.traffic, descriptions),
+            (None, true) => {}
+        }
+    }
+
+    /// Warn about configurations that produce useless metrics, but can't be rejected at compile time.
+    pub(crate) fn validate(&self) {
+        if self.custom_handle && self.traffic.body_size {
+            tracing::warn!(
+                metric = %self.traffic.names.response_body_size(),
+                "response body sizes are recorded with a custom exporter handle, so the crate can't configure \
+                 byte-scale buckets for them. Unless the exporter sets buckets for this metric (e.g. \
+                 `utils::BYTES_SIZE_BUCKETS`), it uses its default ones, which are often duration-scale."
+            );
+        }
+    }
+
+    /// Finalize the builder and get out the [`GenericMetricLayer`] and the
+    /// exporter handle out of it as a tuple.
+    pub fn build_pair(self) -> (GenericMetricLayer<'a, T, M>, T) {
+        GenericMetricLayer::pair_from_builder(self)
+    }
+}
+
+#[cfg(feature = "prometheus")]
+/// A builder for [`crate::PrometheusMetricLayer`] that enables further customizations.
+pub type PrometheusMetricLayerBuilder<'a, S> =
+    MetricLayerBuilder<'a, PrometheusHandle, crate::Handle, S>;
+
+#[cfg(feature = "prometheus")]
+impl<'a> PrometheusMetricLayerBuilder<'a, LayerOnly> {
+    /// Attach the default Prometheus exporter handle to the builder, but with the buckets of `preset` for the
+    /// histograms measured in seconds (request duration, phases, inter-arrival times, stream durations and the method
+    /// histogram).
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use axum_prometheus::{utils::BucketPreset, PrometheusMetricLayerBuilder};
+    ///
+    /// let (metric_layer, metric_handle) = PrometheusMetricLayerBuilder::new()
+    ///     .with_bucket_preset(BucketPreset::FastApi)
+    ///     .build_pair();
+    /// ```
+    /// After calling this function you can finalize with the [`build_pair`] method, and
+    /// can no longer call [`build`].
+    ///
+    /// [`build`]: crate::MetricLayerBuilder::build
+    /// [`build_pair`]: crate::MetricLayerBuilder::build_pair
+    pub fn with_bucket_preset(
+        self,
+        preset: BucketPreset,
+    ) -> PrometheusMetricLayerBuilder<'a, Paired> {
+        let opts = self.exporter_opts();
+        MetricLayerBuilder::<'_, _, _, Paired>::from_layer_only(self, || {
+            crate::install_prometheus_recorder(crate::prometheus_recorder_with_buckets(
+                &opts,
+                preset.buckets(),
+            ))
+        })
+    }
+
+    /// Enable response body size tracking (see [`enable_response_body_size`]), and render the body sizes as a
+    /// summary with `quantiles` (e.g. `&[0.5, 0.99]` for the median and p99 response size) in the default Prometheus
+    /// exporter, attached with [`with_default_metrics`] or [`with_bucket_preset`].
+    ///
+    /// Summaries and histograms are mixed per metric: the histograms measured in seconds (like the request duration)
+    /// keep their buckets, while every histogram without buckets is rendered as a summary. The Prometheus exporter
+    /// only supports setting quantiles globally, so the other histograms without buckets (like the request header
+    /// sizes or the response chunks) use these quantiles too.
+    ///
+    /// ```rust,no_run
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
+    ///
+    /// let (metric_layer, metric_handle) = PrometheusMetricLayerBuilder::new()
+    ///     .with_body_size_as_summary(&[0.5, 0.9, 0.99])
+    ///     .with_default_metrics()
+    ///     .build_pair();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `quantiles` is empty.
+    ///
+    /// [`enable_response_body_size`]: crate::MetricLayerBuilder::enable_response_body_size
+    /// [`with_default_metrics`]: crate::MetricLayerBuilder::with_default_metrics
+    /// [`with_bucket_preset`]: crate::MetricLayerBuilder::with_bucket_preset
+    pub fn with_body_size_as_summary(mut self, quantiles: &[f64]) -> Self {
+        assert!(!quantiles.is_empty(), "the quantiles must not be empty");
+        self.exporter_opts.quantiles = Some(quantiles.to_vec());
+        self.enable_response_body_size(true)
+    }
+}
+
+#[cfg(feature = "prometheus")]
+impl PrometheusMetricLayerBuilder<'static, Paired> {
+    /// Finalize the builder, and return `base` with the metric layer applied and the metrics served at `/metrics`.
+    ///
+    /// This replaces the usual three steps of building the pair, adding the `/metrics` route and applying the layer:
+    /// ```rust,no_run
+    /// use axum::{routing::get, Router};
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
+    ///
+    /// let app = PrometheusMetricLayerBuilder::new()
+    ///     .with_default_metrics()
+    ///     .build_router(Router::<()>::new().route("/", get(|| async {})));
+    /// ```
+    /// Use [`build_router_at`] to serve the metrics at a different path. Requests to the metrics endpoint are
+    /// recorded too, ignore them with [`with_ignore_pattern`] if needed.
+    ///
+    /// [`build_router_at`]: MetricLayerBuilder::build_router_at
+    /// [`with_ignore_pattern`]: MetricLayerBuilder::with_ignore_pattern
+    pub fn build_router<S>(self, base: axum::Router<S>) -> axum::Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        self.build_router_at(base, "/metrics")
+    }
+
+    /// Same as [`build_router`], but serves the metrics at `path`.
+    ///
+    /// [`build_router`]: MetricLayerBuilder::build_router
+    pub fn build_router_at<S>(self, base: axum::Router<S>, path: &str) -> axum::Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        let (metric_layer, metric_handle) = self.build_pair();
+        base.route(
+            path,
+            axum::routing::get(move || async move {
+                (
+                    [(http::header::CONTENT_TYPE, crate::utils::TEXT_FORMAT)],
+                    metric_handle.render(),
+                )
+            }),
+        )
+        .layer(metric_layer)
+    }
+}
+
+/// The help texts of the standard metrics, see [`MetricLayerBuilder::with_descriptions`].
+///
+/// The [`Default`] implementation holds the crate's own descriptions, so only the ones to override need to be set:
+/// ```rust
+/// use axum_prometheus::Descriptions;
+///
+/// let descriptions = Descriptions {
+///     total: "Requests served by the checkout service.",
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Descriptions {
+    /// The description of the requests total counter.
+    pub total: &'static str,
+    /// The description of the request duration histogram.
+    pub duration: &'static str,
+    /// The description of the pending requests gauge.
+    pub pending: &'static str,
+    /// The description of the response body size histogram.
+    pub body_size: &'static str,
+}
+
+impl Default for Descriptions {
+    fn default() -> Self {
+        Self {
+            total: "The number of times a HTTP request was processed.",
+            duration: "The distribution of HTTP response times.",
+            pending: "The number of currently in-flight requests.",
+            body_size: "The distribution of HTTP response body sizes.",
+        }
+    }
+}
+
+/// Describe the standard metrics covered by [`Descriptions`].
+fn describe_standard_metrics(traffic: &Traffic<'_>, descriptions: &Descriptions) {
+    metrics::describe_counter!(
+        traffic.names.requests_total(),
+        metrics::Unit::Count,
+        descriptions.total
+    );
+    if !traffic.pending_as_counters {
+        metrics::describe_gauge!(
+            traffic.names.requests_pending(),
+            metrics::Unit::Count,
+            descriptions.pending
+        );
+    }
+    metrics::describe_histogram!(
+        traffic.names.requests_duration(),
+        metrics::Unit::Seconds,
+        descriptions.duration
+    );
+    if traffic.body_size {
+        metrics::describe_histogram!(
+            traffic.names.response_body_size(),
+            metrics::Unit::Count,
+            descriptions.body_size
+        );
+    }
+}
+
+fn describe_metrics(traffic: &Traffic<'_>, descriptions: &Descriptions) {
+    describe_standard_metrics(traffic, descriptions);
+    if traffic.pending_as_counters {
+        metrics::describe_counter!(
+            traffic.names.requests_started(),
+            metrics::Unit::Count,
+            "The number of HTTP requests that started processing."
+        );
+        metrics::describe_counter!(
+            traffic.names.requests_finished(),
+            metrics::Unit::Count,
+            "The number of HTTP requests that finished processing."
+        );
+    }
+    metrics::describe_counter!(
+        traffic.names.requests_cancelled(),
+        metrics::Unit::Count,
+        "The number of HTTP requests that were cancelled before a response was produced."
+    );
+    if traffic.unlabeled_total {
+        metrics::describe_counter!(
+            traffic.names.requests_total_unlabeled(),
+            metrics::Unit::Count,
+            "The number of times a HTTP request was processed, without dynamic labels."
+        );
+    }
+    if traffic.concurrency {
+        metrics::describe_histogram!(
+            traffic.names.requests_concurrency(),
+            metrics::Unit::Count,
+            "The distribution of the number of in-flight HTTP requests when a request arrives."
+        );
+    }
+    if traffic.failure_reasons {
+        metrics::describe_counter!(
+            traffic.names.requests_failed(),
+            metrics::Unit::Count,
+            "The number of HTTP requests that failed with an error."
+        );
+    }
+    if traffic.method_histogram {
+        metrics::describe_histogram!(
+            traffic.names.method_duration(),
+            metrics::Unit::Seconds,
+            "The distribution of HTTP response times by method."
+        );
+    }
+    if traffic.request_header_size {
+        metrics::describe_histogram!(
+            traffic.names.request_headers(),
+            metrics::Unit::Bytes,
+            "The distribution of HTTP request header sizes."
+        );
+    }
+    if traffic.trailer_status.is_some() {
+        metrics::describe_counter!(
+            traffic.names.grpc_requests_total(),
+            metrics::Unit::Count,
+            "The number of HTTP requests by the status read from the response trailers."
+        );
+    }
+    if traffic.grouped_total {
+        metrics::describe_counter!(
+            traffic.names.requests_grouped(),
+            metrics::Unit::Count,
+            "The number of HTTP requests whose endpoint was rewritten by a group pattern."
+        );
+    }
+    if traffic.stream_duration {
+        metrics::describe_histogram!(
+            traffic.names.stream_duration(),
+            metrics::Unit::Seconds,
+            "The distribution of HTTP response body streaming times."
+        );
+    }
+    if traffic.inter_arrival {
+        metrics::describe_histogram!(
+            traffic.names.inter_arrival(),
+            metrics::Unit::Seconds,
+            "The distribution of the time between consecutive HTTP requests to an endpoint."
+        );
+    }
+    for (status, name) in &traffic.status_counters {
+        metrics::describe_counter!(
+            name.clone(),
+            metrics::Unit::Count,
+            format!("The number of HTTP responses with status {status}.")
+        );
+    }
+    if traffic.has_slos() {
+        metrics::describe_counter!(
+            traffic.names.slo_violations(),
+            metrics::Unit::Count,
+            "The number of HTTP requests that took longer than the latency SLO of their endpoint."
+        );
+    }
+    if traffic.phase_timing || traffic.limiter_wait {
+        metrics::describe_histogram!(
+            traffic.names.requests_phase(),
+            metrics::Unit::Seconds,
+            "The distribution of the time it took to reach a phase of a HTTP request."
+        );
+    }
+    if traffic.body_size {
+        metrics::describe_counter!(
+            traffic.names.response_body_bytes_total(),
+            metrics::Unit::Bytes,
+            "The total number of bytes sent in HTTP response bodies."
+        );
+    }
+    if traffic.response_chunks {
         metrics::describe_histogram!(
-            crate::utils::response_body_size_name(),
+            traffic.names.response_chunks(),
             metrics::Unit::Count,
-            "The distribution of HTTP response body sizes."
+            "The distribution of the number of data chunks in HTTP response bodies."
         );
     }
 }
@@ -0,0 +1,141 @@
+use std::time::Instant;
+
+use bytes::Buf;
+use http::{Request, Response};
+use metrics::{counter, histogram, SharedString};
+use tower::Layer;
+use tower_http::classify::{ClassifiedResponse, SharedClassifier, StatusInRangeAsFailures};
+
+use crate::lifecycle::{
+    layer::LifeCycleLayer, service::LifeCycle, Callbacks, FailedAt, OnBodyChunk,
+};
+use crate::{utils, MetricNames};
+
+/// A tower middleware layer for recording the metrics of outbound HTTP requests, made by clients built on `tower`.
+///
+/// Clients have no matched route, so the requests are labeled by the `host` of the request URI (or the `Host` header,
+/// or `none`), the `method` and the response `status` instead, in the following metrics:
+///
+/// - [`AXUM_HTTP_CLIENT_REQUESTS_TOTAL`]
+/// - [`AXUM_HTTP_CLIENT_REQUESTS_DURATION_SECONDS`]
+///
+/// Requests that fail without a response (e.g. connection errors) are counted with an `error` status.
+///
+/// ```rust
+/// use axum_prometheus::ClientMetricLayer;
+/// use tower::ServiceBuilder;
+///
+/// # let client = tower::service_fn(|_req: http::Request<String>| async {
+/// #     Ok::<_, std::convert::Infallible>(http::Response::new(String::new()))
+/// # });
+/// let client = ServiceBuilder::new()
+///     .layer(ClientMetricLayer::new())
+///     .service(client);
+/// ```
+///
+/// [`AXUM_HTTP_CLIENT_REQUESTS_TOTAL`]: crate::AXUM_HTTP_CLIENT_REQUESTS_TOTAL
+/// [`AXUM_HTTP_CLIENT_REQUESTS_DURATION_SECONDS`]: crate::AXUM_HTTP_CLIENT_REQUESTS_DURATION_SECONDS
+#[derive(Clone)]
+pub struct ClientMetricLayer {
+    inner_layer:
+        LifeCycleLayer<SharedClassifier<StatusInRangeAsFailures>, ClientTraffic, ClientTraffic>,
+}
+
+impl ClientMetricLayer {
+    /// Create a new layer. Like the server side [`GenericMetricLayer::new`], this doesn't install an exporter.
+    ///
+    /// [`GenericMetricLayer::new`]: crate::GenericMetricLayer::new
+    pub fn new() -> Self {
+        Self {
+            inner_layer: LifeCycleLayer::new(
+                crate::default_classifier(),
+                ClientTraffic::default(),
+                ClientTraffic::default(),
+            ),
+        }
+    }
+}
+
+impl Default for ClientMetricLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Layer<S> for ClientMetricLayer {
+    type Service =
+        LifeCycle<S, SharedClassifier<StatusInRangeAsFailures>, ClientTraffic, ClientTraffic>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        self.inner_layer.layer(inner)
+    }
+}
+
+/// The callbacks of the [`ClientMetricLayer`].
+#[derive(Clone, Default)]
+pub struct ClientTraffic {
+    names: MetricNames,
+}
+
+/// The data of an outbound request, tracked by the [`ClientMetricLayer`].
+#[derive(Clone)]
+pub struct ClientRequestData {
+    start: Instant,
+    method: &'static str,
+    host: SharedString,
+}
+
+impl ClientTraffic {
+    fn record(&self, data: &ClientRequestData, status: &'static str) {
+        let labels = [
+            ("method", SharedString::const_str(data.method)),
+            ("host", data.host.clone()),
+            ("status", SharedString::const_str(status)),
+        ];
+        counter!(self.names.client_requests_total(), &labels).increment(1);
+        histogram!(self.names.client_requests_duration(), &labels)
+            .record(data.start.elapsed().as_secs_f64());
+    }
+}
+
+impl<FailureClass> Callbacks<FailureClass> for ClientTraffic {
+    type Data = ClientRequestData;
+
+    fn prepare<B>(&mut self, request: &Request<B>) -> Self::Data {
+        let host = request
+            .uri()
+            .host()
+            .or_else(|| request.headers().get(http::header::HOST)?.to_str().ok())
+            .unwrap_or("none");
+        ClientRequestData {
+            start: Instant::now(),
+            method: utils::as_label(request.method()),
+            host: SharedString::from_owned(host.to_owned()),
+        }
+    }
+
+    fn on_response<B>(
+        &mut self,
+        response: &Response<B>,
+        _classification: ClassifiedResponse<FailureClass, ()>,
+        data: &mut Self::Data,
+    ) {
+        self.record(data, utils::status_label(response.status().as_u16()));
+    }
+
+    fn on_failure(
+        self,
+        failed_at: FailedAt,
+        _failure_classification: FailureClass,
+        data: &mut Self::Data,
+    ) {
+        // Body errors happen after the response was already recorded.
+        if let FailedAt::Response = failed_at {
+            self.record(data, "error");
+        }
+    }
+}
+
+impl<B: Buf> OnBodyChunk<B> for ClientTraffic {
+    type Data = ClientRequestData;
+}
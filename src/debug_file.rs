@@ -0,0 +1,105 @@
+use std::{path::PathBuf, time::Duration};
+
+use metrics_exporter_prometheus::PrometheusHandle;
+use tokio::time::MissedTickBehavior;
+
+use crate::{ExporterOpts, MakeDefaultHandle};
+
+/// A [`MakeDefaultHandle`] implementation that periodically writes the rendered metrics to stdout or a file, for
+/// seeing the metrics locally in environments without a Prometheus scraper (like CLI tools or cron jobs).
+///
+/// It installs the same recorder as [`GenericMetricLayer::pair`], and spawns a background task that writes
+/// `handle.render()` every 10 seconds by default. A file is overwritten with the latest metrics every time. If writing
+/// takes longer than the interval, the next write is delayed instead of catching up.
+///
+/// # Example
+/// ```rust,no_run
+/// use std::time::Duration;
+///
+/// use axum_prometheus::{DebugFileRecorder, GenericMetricLayer};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let recorder = DebugFileRecorder::file("metrics.txt").interval(Duration::from_secs(1));
+///     let (metric_layer, metric_handle) = GenericMetricLayer::pair_from(recorder);
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Installing the recorder (e.g. with [`GenericMetricLayer::pair_from`]) panics if it's not called from within a
+/// Tokio runtime, since the metrics are written by a background task.
+///
+/// [`GenericMetricLayer::pair`]: crate::GenericMetricLayer::pair
+/// [`GenericMetricLayer::pair_from`]: crate::GenericMetricLayer::pair_from
+#[derive(Debug, Clone)]
+pub struct DebugFileRecorder {
+    path: Option<PathBuf>,
+    interval: Duration,
+}
+
+impl DebugFileRecorder {
+    /// Write the metrics to stdout.
+    pub fn stdout() -> Self {
+        Self {
+            path: None,
+            interval: Duration::from_secs(10),
+        }
+    }
+
+    /// Write the metrics to the file at `path`.
+    pub fn file(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: Some(path.into()),
+            ..Self::stdout()
+        }
+    }
+
+    /// Set how often the metrics are written. Defaults to 10 seconds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        assert!(!interval.is_zero(), "the interval must not be zero");
+        self.interval = interval;
+        self
+    }
+}
+
+impl MakeDefaultHandle for DebugFileRecorder {
+    type Out = PrometheusHandle;
+
+    fn make_default_handle(self) -> Self::Out {
+        self.make_default_handle_with_opts(&ExporterOpts::default())
+    }
+
+    fn make_default_handle_with_opts(self, opts: &ExporterOpts) -> Self::Out {
+        let handle = crate::install_prometheus_recorder(crate::default_prometheus_recorder(opts));
+
+        let dump_handle = handle.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.interval);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            // The first tick completes right away, before anything is recorded.
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                let rendered = dump_handle.render();
+                match &self.path {
+                    Some(path) => {
+                        if let Err(err) = tokio::fs::write(path, rendered).await {
+                            eprintln!("failed to write metrics to {}: {err}", path.display());
+                        }
+                    }
+                    None => println!("{rendered}"),
+                }
+            }
+        });
+        handle
+    }
+
+    fn share_handle(handle: &Self::Out) -> Option<Self::Out> {
+        Some(handle.clone())
+    }
+}
@@ -0,0 +1,57 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// A request extension that lets handlers report the duration of a request themselves, instead of the measured one.
+///
+/// This is an advanced escape hatch for handlers whose wall-clock time doesn't reflect the meaningful work, e.g. ones
+/// that spend most of their time waiting on something the duration shouldn't include. Overriding the duration makes the requests duration histogram (and everything derived
+/// from it, like the SLO violations) report whatever the handler sets, so the numbers are only as accurate as the
+/// handler's own measurement.
+///
+/// When enabled with [`MetricLayerBuilder::enable_duration_override`], the layer inserts a `DurationOverride` into the
+/// extensions of every tracked request. The override must be set before the response is generated, since that's when
+/// the duration is recorded. If it's set multiple times, the last value wins. Work that carries on after the response
+/// (e.g. in a task spawned by the handler) can't be reported: setting the override afterwards has no effect.
+///
+/// # Example
+/// ```rust,no_run
+/// use std::time::{Duration, Instant};
+///
+/// use axum::{routing::post, Extension, Router};
+/// use axum_prometheus::{DurationOverride, PrometheusMetricLayerBuilder};
+///
+/// async fn handler(Extension(duration): Extension<DurationOverride>) {
+///     // .. wait for a slot in a queue, which shouldn't be part of the duration
+///     let start = Instant::now();
+///     // .. do the actual work
+///     duration.set_duration_override(start.elapsed());
+/// }
+///
+/// let (metric_layer, metric_handle) = PrometheusMetricLayerBuilder::new()
+///     .enable_duration_override()
+///     .with_default_metrics()
+///     .build_pair();
+/// let app = Router::<()>::new()
+///     .route("/", post(handler))
+///     .layer(metric_layer);
+/// ```
+///
+/// Since the extension is only present for tracked requests (e.g. not for ignored ones), prefer extracting
+/// `Option<Extension<DurationOverride>>` if the handler may be called for an ignored route.
+///
+/// [`MetricLayerBuilder::enable_duration_override`]: crate::MetricLayerBuilder::enable_duration_override
+#[derive(Debug, Clone, Default)]
+pub struct DurationOverride(Arc<Mutex<Option<Duration>>>);
+
+impl DurationOverride {
+    /// Report `duration` as the duration of the request, instead of the measured one.
+    pub fn set_duration_override(&self, duration: Duration) {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = Some(duration);
+    }
+
+    pub(crate) fn get(&self) -> Option<Duration> {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
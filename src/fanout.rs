@@ -0,0 +1,69 @@
+use metrics::Recorder;
+use metrics_util::layers::FanoutBuilder;
+
+use crate::MakeDefaultHandle;
+
+/// A [`MakeDefaultHandle`] implementation that records metrics into multiple recorders simultaneously.
+///
+/// Every metric emitted by the layer is forwarded to all of the added recorders. The handle returned from
+/// [`MakeDefaultHandle::make_default_handle`] is the one passed to [`FanoutRecorder::new`], so only a single
+/// exporter (typically Prometheus) can provide a meaningful `render()` — the others are expected to push their
+/// metrics on their own.
+///
+/// # Example
+/// ```rust,no_run
+/// use axum_prometheus::{FanoutRecorder, GenericMetricLayer};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     // Add any other recorders with `add_recorder`, e.g. a StatsD recorder.
+///     let fanout = FanoutRecorder::prometheus();
+///     let (metric_layer, metric_handle) = GenericMetricLayer::pair_from(fanout);
+///     // `metric_handle` is a `PrometheusHandle`, so `metric_handle.render()` can be exposed as usual.
+/// }
+/// ```
+pub struct FanoutRecorder<T> {
+    fanout: FanoutBuilder,
+    handle: T,
+}
+
+impl<T> FanoutRecorder<T> {
+    /// Create a new fanout without any recorders, that'll return `handle` once installed.
+    pub fn new(handle: T) -> Self {
+        Self {
+            fanout: FanoutBuilder::default(),
+            handle,
+        }
+    }
+
+    /// Add a recorder to the fanout.
+    pub fn add_recorder<R>(mut self, recorder: R) -> Self
+    where
+        R: Recorder + Sync + 'static,
+    {
+        self.fanout = self.fanout.add_recorder(recorder);
+        self
+    }
+}
+
+#[cfg(feature = "prometheus")]
+impl FanoutRecorder<metrics_exporter_prometheus::PrometheusHandle> {
+    /// Create a new fanout that contains the default Prometheus recorder, which is the same that
+    /// [`GenericMetricLayer::pair`] installs. The returned handle can be used to render the Prometheus metrics.
+    ///
+    /// [`GenericMetricLayer::pair`]: crate::GenericMetricLayer::pair
+    pub fn prometheus() -> Self {
+        let recorder = crate::default_prometheus_recorder(&crate::ExporterOpts::default());
+        let handle = recorder.handle();
+        Self::new(handle).add_recorder(recorder)
+    }
+}
+
+impl<T> MakeDefaultHandle for FanoutRecorder<T> {
+    type Out = T;
+
+    fn make_default_handle(self) -> Self::Out {
+        metrics::set_global_recorder(self.fanout.build()).expect("Failed to set global recorder");
+        self.handle
+    }
+}
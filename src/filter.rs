@@ -0,0 +1,98 @@
+//! Rendering the Prometheus metrics with parts of the histograms stripped, for scrapers that can't ingest them.
+//!
+//! This is plain post-processing of the text returned by `PrometheusHandle::render`. Stripping lines __breaks
+//! compatibility__ with Prometheus: without `_sum` and `_count` averages and rates of observations can't be computed,
+//! and without the `+Inf` bucket `histogram_quantile` returns no result. Only use it for ingestion pipelines that need
+//! it, and serve the unfiltered metrics to Prometheus itself.
+use std::collections::HashSet;
+
+use metrics_exporter_prometheus::PrometheusHandle;
+
+/// Which lines [`render_filtered`] strips from the rendered histograms. By default nothing is stripped.
+#[derive(Debug, Clone, Default)]
+pub struct RenderOptions {
+    strip_sum_and_count: bool,
+    stripped_buckets: Vec<String>,
+}
+
+impl RenderOptions {
+    /// Create options that don't strip anything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Strip the `_sum` and `_count` lines of every histogram and summary.
+    pub fn without_sum_and_count(mut self) -> Self {
+        self.strip_sum_and_count = true;
+        self
+    }
+
+    /// Strip the bucket with the upper bound `le` from every histogram. The bound is compared to the `le` label as
+    /// rendered, e.g. `"0.005"`, `"1"` or `"+Inf"`.
+    pub fn without_bucket(mut self, le: impl Into<String>) -> Self {
+        self.stripped_buckets.push(le.into());
+        self
+    }
+}
+
+/// Render the metrics held by `handle`, without the lines stripped by `options`. See the [module docs](self) for
+/// the caveats.
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{routing::get, Router};
+/// use axum_prometheus::{
+///     filter::{render_filtered, RenderOptions},
+///     PrometheusMetricLayer,
+/// };
+///
+/// let (metric_layer, metric_handle) = PrometheusMetricLayer::pair();
+/// let options = RenderOptions::new().without_sum_and_count().without_bucket("+Inf");
+/// let app = Router::<()>::new()
+///     .route("/metrics/buckets", get(|| async move { render_filtered(&metric_handle, &options) }))
+///     .layer(metric_layer);
+/// ```
+pub fn render_filtered(handle: &PrometheusHandle, options: &RenderOptions) -> String {
+    filter_exposition(&handle.render(), options)
+}
+
+/// Strip the lines of a Prometheus text exposition as described in [`render_filtered`].
+fn filter_exposition(text: &str, options: &RenderOptions) -> String {
+    // The families whose `_sum`, `_count` and `_bucket` lines may be stripped, so a counter that happens to be called
+    // `..._count` is left alone.
+    let mut histograms = HashSet::new();
+    let mut filtered = String::with_capacity(text.len());
+    for line in text.split_inclusive('\n') {
+        if let Some(declaration) = line.strip_prefix("# TYPE ") {
+            let mut parts = declaration.split_whitespace();
+            if let (Some(name), Some("histogram" | "summary")) = (parts.next(), parts.next()) {
+                histograms.insert(name);
+            }
+        } else if !line.starts_with('#') && strips(line, &histograms, options) {
+            continue;
+        }
+        filtered.push_str(line);
+    }
+    filtered
+}
+
+fn strips(line: &str, histograms: &HashSet<&str>, options: &RenderOptions) -> bool {
+    let name_end = line.find(['{', ' ']).unwrap_or(line.len());
+    let (name, rest) = line.split_at(name_end);
+    let is_part_of = |suffix| {
+        name.strip_suffix(suffix)
+            .is_some_and(|family| histograms.contains(family))
+    };
+    if options.strip_sum_and_count && (is_part_of("_sum") || is_part_of("_count")) {
+        return true;
+    }
+    if options.stripped_buckets.is_empty() || !is_part_of("_bucket") {
+        return false;
+    }
+    // The exporter renders `le` as the last label.
+    let le = rest.rfind("le=\"").and_then(|start| {
+        let value = &rest[start + 4..];
+        value.find('"').map(|end| &value[..end])
+    });
+    le.is_some_and(|le| options.stripped_buckets.iter().any(|bucket| bucket == le))
+}
@@ -0,0 +1,88 @@
+//! Serving gzip-compressed metrics, to reduce the scrape bandwidth of large exports.
+//!
+//! Prometheus requests gzip-encoded scrapes by default (via `Accept-Encoding: gzip`), so this is mostly useful for
+//! high-cardinality `/metrics` endpoints.
+use std::io::Write;
+
+use axum::{
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use flate2::{write::GzEncoder, Compression};
+use metrics_exporter_prometheus::PrometheusHandle;
+
+use crate::utils::TEXT_FORMAT;
+
+/// Render the metrics held by `handle`, and gzip-compress them. Returns the compressed payload along with the
+/// `Content-Encoding` header value to serve it with.
+pub fn render_gzip(handle: &PrometheusHandle) -> (Vec<u8>, HeaderValue) {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(handle.render().as_bytes())
+        .expect("writing to a `Vec` doesn't fail");
+    let body = encoder.finish().expect("writing to a `Vec` doesn't fail");
+    (body, HeaderValue::from_static("gzip"))
+}
+
+/// Render the metrics held by `handle` as a response, which is gzip-compressed if the request `headers` accept it.
+pub fn render_response(handle: &PrometheusHandle, headers: &HeaderMap) -> Response {
+    if !accepts_gzip(headers) {
+        return ([(header::CONTENT_TYPE, TEXT_FORMAT)], handle.render()).into_response();
+    }
+    let (body, encoding) = render_gzip(handle);
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, TEXT_FORMAT),
+            (header::CONTENT_ENCODING, encoding),
+            (header::VARY, HeaderValue::from_static("accept-encoding")),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+/// A router serving the metrics held by `handle` at `path`, gzip-compressed if the request accepts it.
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{routing::get, Router};
+/// use axum_prometheus::{gzip, PrometheusMetricLayer};
+///
+/// let (metric_layer, metric_handle) = PrometheusMetricLayer::pair();
+/// let app = Router::<()>::new()
+///     .route("/", get(|| async {}))
+///     .merge(gzip::router("/metrics", metric_handle))
+///     .layer(metric_layer);
+/// ```
+pub fn router<S>(path: &str, handle: PrometheusHandle) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    Router::new().route(
+        path,
+        get(move |headers: HeaderMap| async move { render_response(&handle, &headers) }),
+    )
+}
+
+/// Whether the `Accept-Encoding` header allows gzip, i.e. lists `gzip` (or `*`) without `q=0`.
+fn accepts_gzip(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT_ENCODING)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|coding| {
+            let mut params = coding.split(';').map(str::trim);
+            let name = params.next().unwrap_or_default();
+            let rejected = params.any(|param| {
+                param
+                    .strip_prefix("q=")
+                    .and_then(|q| q.parse::<f32>().ok())
+                    .is_some_and(|q| q == 0.0)
+            });
+            (name.eq_ignore_ascii_case("gzip") || name == "*") && !rejected
+        })
+}
@@ -0,0 +1,146 @@
+//! Rendering metrics as JSON, for consumers that can't scrape the Prometheus text format.
+//!
+//! This is a convenience for embedding metrics in admin APIs and similar tooling. The output is
+//! __not__ a Prometheus-compatible format, and its shape may change between releases.
+use metrics_exporter_prometheus::PrometheusHandle;
+use serde_json::{Map, Value};
+
+/// Render the metrics held by `handle` as a JSON object of metric families.
+///
+/// Each metric family is keyed by its name, and looks like this:
+/// ```json
+/// {
+///   "type": "counter",
+///   "help": "The number of times a HTTP request was processed.",
+///   "samples": [
+///     {
+///       "name": "axum_http_requests_total",
+///       "labels": { "endpoint": "/", "method": "GET", "status": "200" },
+///       "value": 1.0
+///     }
+///   ]
+/// }
+/// ```
+/// Non-finite sample values (such as the `+Inf` bucket boundary) are represented as strings.
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{routing::get, Json, Router};
+/// use axum_prometheus::{json::render_json, PrometheusMetricLayer};
+///
+/// let (metric_layer, metric_handle) = PrometheusMetricLayer::pair();
+/// let app = Router::<()>::new()
+///     .route("/admin/metrics", get(|| async move { Json(render_json(&metric_handle)) }))
+///     .layer(metric_layer);
+/// ```
+pub fn render_json(handle: &PrometheusHandle) -> Value {
+    parse_exposition(&handle.render())
+}
+
+/// Parse a Prometheus text exposition into the JSON structure described in [`render_json`].
+pub fn parse_exposition(exposition: &str) -> Value {
+    let mut families = Map::new();
+
+    for line in exposition.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        if let Some(comment) = line.strip_prefix('#') {
+            let mut parts = comment.trim_start().splitn(3, ' ');
+            let (Some(kind), Some(name)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            let text = parts.next().unwrap_or_default();
+            let key = match kind {
+                "HELP" => "help",
+                "TYPE" => "type",
+                _ => continue,
+            };
+            family_entry(&mut families, name).insert(key.to_owned(), Value::from(text));
+            continue;
+        }
+
+        let Some((name, labels, value)) = parse_sample(line) else {
+            continue;
+        };
+        let family = family_name(&families, name);
+        let samples = family_entry(&mut families, &family)
+            .entry("samples")
+            .or_insert_with(|| Value::Array(Vec::new()));
+        if let Value::Array(samples) = samples {
+            let mut sample = Map::new();
+            sample.insert("name".to_owned(), Value::from(name));
+            sample.insert("labels".to_owned(), Value::Object(labels));
+            sample.insert("value".to_owned(), value);
+            samples.push(Value::Object(sample));
+        }
+    }
+
+    Value::Object(families)
+}
+
+fn family_entry<'m>(
+    families: &'m mut Map<String, Value>,
+    name: &str,
+) -> &'m mut Map<String, Value> {
+    let family = families
+        .entry(name)
+        .or_insert_with(|| Value::Object(Map::new()));
+    match family {
+        Value::Object(family) => family,
+        _ => unreachable!("metric families are always objects"),
+    }
+}
+
+/// Histogram and summary samples carry a suffix, but belong to the family without it.
+fn family_name(families: &Map<String, Value>, sample_name: &str) -> String {
+    ["_bucket", "_sum", "_count"]
+        .iter()
+        .filter_map(|suffix| sample_name.strip_suffix(suffix))
+        .find(|base| families.contains_key(*base))
+        .unwrap_or(sample_name)
+        .to_owned()
+}
+
+fn parse_sample(line: &str) -> Option<(&str, Map<String, Value>, Value)> {
+    let name_end = line.find(['{', ' '])?;
+    let name = &line[..name_end];
+    let mut rest = &line[name_end..];
+    let mut labels = Map::new();
+
+    if let Some(label_str) = rest.strip_prefix('{') {
+        let (parsed, remainder) = parse_labels(label_str)?;
+        labels = parsed;
+        rest = remainder;
+    }
+
+    // A sample may be followed by an optional timestamp, which we ignore.
+    let value = rest.split_whitespace().next()?;
+    let value = match value.parse::<f64>() {
+        Ok(v) if v.is_finite() => Value::from(v),
+        _ => Value::from(value),
+    };
+    Some((name, labels, value))
+}
+
+fn parse_labels(mut input: &str) -> Option<(Map<String, Value>, &str)> {
+    let mut labels = Map::new();
+    loop {
+        input = input.trim_start_matches([',', ' ']);
+        if let Some(rest) = input.strip_prefix('}') {
+            return Some((labels, rest));
+        }
+        let (key, rest) = input.split_once("=\"")?;
+        let mut value = String::new();
+        let mut chars = rest.char_indices();
+        let end = loop {
+            match chars.next()? {
+                (_, '\\') => match chars.next()?.1 {
+                    'n' => value.push('\n'),
+                    other => value.push(other),
+                },
+                (i, '"') => break i,
+                (_, c) => value.push(c),
+            }
+        };
+        labels.insert(key.trim().to_owned(), Value::from(value));
+        input = &rest[end + 1..];
+    }
+}
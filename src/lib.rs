@@ -9,7 +9,14 @@
 //! - `axum_http_requests_duration_seconds` (labels: endpoint, method, status): the request duration for all HTTP requests handled (histogram)
 //! - `axum_http_requests_pending` (labels: endpoint, method): the number of currently in-flight requests (gauge)
 //!
-//! This crate also allows to track response body sizes as a histogram — see [`PrometheusMetricLayerBuilder::enable_response_body_size`].
+//! Requests that are dropped before a response is produced (e.g. by a timeout layer above) are counted in `axum_http_requests_cancelled_total` (labels: endpoint, method).
+//!
+//! Builders also emit `axum_prometheus_build_info` (labels: body_size, pending, prefix, exporter) once the exporter is attached (e.g. with `with_default_metrics`), a gauge that is always `1` and describes how the layer is configured.
+//!
+//! Handlers may also report intermediate timings of a request via [`PhaseTimer`] — see [`PrometheusMetricLayerBuilder::enable_phase_timing`].
+//!
+//! This crate also allows to track response body sizes as a histogram — see [`PrometheusMetricLayerBuilder::enable_response_body_size`],
+//! and the number of data chunks each response body produced — see [`PrometheusMetricLayerBuilder::enable_response_chunks`].
 //!
 //! ### Renaming Metrics
 //!
@@ -17,7 +24,25 @@
 //! - `AXUM_HTTP_REQUESTS_TOTAL`
 //! - `AXUM_HTTP_REQUESTS_DURATION_SECONDS`
 //! - `AXUM_HTTP_REQUESTS_PENDING`
+//! - `AXUM_HTTP_REQUESTS_CANCELLED_TOTAL`
 //! - `AXUM_HTTP_RESPONSE_BODY_SIZE` (if body size tracking is enabled)
+//! - `AXUM_HTTP_RESPONSE_CHUNKS` (if response chunk tracking is enabled)
+//! - `AXUM_HTTP_REQUESTS_TOTAL_UNLABELED` (if the unlabeled requests total is enabled)
+//! - `AXUM_HTTP_REQUESTS_PHASE_SECONDS` (if phase timing is enabled)
+//! - `AXUM_HTTP_INTER_ARRIVAL_SECONDS` (if inter-arrival tracking is enabled)
+//! - `AXUM_HTTP_STREAM_DURATION_SECONDS` (if stream duration tracking is enabled)
+//! - `AXUM_HTTP_REQUESTS_GROUPED_TOTAL` (if the grouped requests total is enabled)
+//! - `AXUM_HTTP_REQUESTS_CONCURRENCY` (if the requests concurrency is recorded)
+//! - `AXUM_GRPC_REQUESTS_TOTAL` (if the trailer status is enabled)
+//! - `AXUM_HTTP_REQUEST_HEADERS_BYTES` (if the request headers size is recorded)
+//! - `AXUM_HTTP_METHOD_DURATION_SECONDS` (if the method histogram is enabled)
+//! - `AXUM_HTTP_REQUESTS_FAILED_TOTAL` (if failure reasons are enabled)
+//! - `AXUM_HTTP_REQUESTS_STARTED_TOTAL` (if pending requests are counted)
+//! - `AXUM_HTTP_REQUESTS_FINISHED_TOTAL` (if pending requests are counted)
+//! - `AXUM_HTTP_CLIENT_REQUESTS_TOTAL` (if the `ClientMetricLayer` is used)
+//! - `AXUM_HTTP_CLIENT_REQUESTS_DURATION_SECONDS` (if the `ClientMetricLayer` is used)
+//! - `AXUM_HTTP_RESPONSE_BODY_BYTES_TOTAL` (if body size tracking is enabled)
+//! - `AXUM_HTTP_SLO_VIOLATIONS_TOTAL` (if an SLO is set)
 //!
 //! These environmental variables can be set in your `.cargo/config.toml` since Cargo 1.56:
 //! ```toml
@@ -25,7 +50,25 @@
 //! AXUM_HTTP_REQUESTS_TOTAL = "my_app_requests_total"
 //! AXUM_HTTP_REQUESTS_DURATION_SECONDS = "my_app_requests_duration_seconds"
 //! AXUM_HTTP_REQUESTS_PENDING = "my_app_requests_pending"
+//! AXUM_HTTP_REQUESTS_CANCELLED_TOTAL = "my_app_requests_cancelled_total"
 //! AXUM_HTTP_RESPONSE_BODY_SIZE = "my_app_response_body_size"
+//! AXUM_HTTP_RESPONSE_CHUNKS = "my_app_response_chunks"
+//! AXUM_HTTP_REQUESTS_TOTAL_UNLABELED = "my_app_requests_total_unlabeled"
+//! AXUM_HTTP_REQUESTS_PHASE_SECONDS = "my_app_requests_phase_seconds"
+//! AXUM_HTTP_INTER_ARRIVAL_SECONDS = "my_app_inter_arrival_seconds"
+//! AXUM_HTTP_STREAM_DURATION_SECONDS = "my_app_stream_duration_seconds"
+//! AXUM_HTTP_REQUESTS_GROUPED_TOTAL = "my_app_requests_grouped_total"
+//! AXUM_HTTP_REQUESTS_CONCURRENCY = "my_app_requests_concurrency"
+//! AXUM_GRPC_REQUESTS_TOTAL = "my_app_grpc_requests_total"
+//! AXUM_HTTP_REQUEST_HEADERS_BYTES = "my_app_request_headers_bytes"
+//! AXUM_HTTP_METHOD_DURATION_SECONDS = "my_app_method_duration_seconds"
+//! AXUM_HTTP_REQUESTS_FAILED_TOTAL = "my_app_requests_failed_total"
+//! AXUM_HTTP_REQUESTS_STARTED_TOTAL = "my_app_requests_started_total"
+//! AXUM_HTTP_REQUESTS_FINISHED_TOTAL = "my_app_requests_finished_total"
+//! AXUM_HTTP_CLIENT_REQUESTS_TOTAL = "my_app_client_requests_total"
+//! AXUM_HTTP_CLIENT_REQUESTS_DURATION_SECONDS = "my_app_client_requests_duration_seconds"
+//! AXUM_HTTP_RESPONSE_BODY_BYTES_TOTAL = "my_app_response_body_bytes_total"
+//! AXUM_HTTP_SLO_VIOLATIONS_TOTAL = "my_app_slo_violations_total"
 //! ```
 //!
 //! ..or optionally use [`PrometheusMetricLayerBuilder::with_prefix`] function.
@@ -88,6 +131,49 @@
 //! axum_http_requests_duration_seconds_count{method="GET",status="200",endpoint="/metrics"} 4
 //! ```
 //!
+//! ## Recording into multiple exporters
+//!
+//! The `fanout` feature provides [`FanoutRecorder`], a [`MakeDefaultHandle`] implementation that installs a
+//! fanout over several recorders, for example to serve a local Prometheus scrape endpoint and push to StatsD at the same time.
+//!
+//! ## OpenTelemetry feature
+//!
+//! The `otel` feature provides [`OtelRecorder`], a [`MakeDefaultHandle`] implementation that forwards all metrics to an
+//! OpenTelemetry `Meter`, so they can be exported via OTLP. See the `exporter-otel-example` for a complete setup.
+//!
+//! ## JSON feature
+//! For tooling that can't consume the Prometheus text format, the `json` feature provides [`json::render_json`],
+//! which renders the metrics of a `PrometheusHandle` as a JSON object of metric families. This is a convenience,
+//! not a Prometheus-compatible format.
+//!
+//! ## Debug file feature
+//! For seeing the metrics locally without a Prometheus scraper, the `debug-file` feature provides
+//! [`DebugFileRecorder`], a [`MakeDefaultHandle`] implementation that periodically writes the metrics to stdout or a
+//! file. See the `debug-file-example` for a complete setup.
+//!
+//! ## Adaptive buckets
+//! For services that don't know good bucket boundaries up front, [`AdaptiveBucketsRecorder`] is a
+//! [`MakeDefaultHandle`] implementation that derives the buckets from the request durations observed during a warmup.
+//! It's best-effort, see its docs for the caveats.
+//!
+//! ## Filtered rendering
+//! For ingestion pipelines that only accept some of the histogram lines, [`filter::render_filtered`] renders the
+//! Prometheus metrics without the `_sum`/`_count` lines or specific buckets. The output is no longer fully
+//! Prometheus-compatible, see the [`filter`] module for the caveats.
+//!
+//! ## Ring buffer feature
+//! For dashboards embedded into the application (like CLI or TUI apps), the `ring-buffer` feature provides
+//! [`RingBufferRecorder`], a [`MakeDefaultHandle`] implementation that keeps the last samples of every metric in a
+//! bounded in-memory buffer, and returns them from [`ring_buffer::RingBufferHandle::snapshot`].
+//!
+//! ## Gzip feature
+//! For large exports, the `gzip` feature provides [`gzip::router`], which serves the metrics gzip-compressed when the
+//! scrape request accepts it, and [`gzip::render_gzip`] to compress them manually.
+//!
+//! ## Testing feature
+//! The `testing` feature provides [`testing::drive_once`], which runs a single request through a layer and returns
+//! the recorded metrics, without installing a global recorder.
+//!
 //! ## Prometheus push gateway feature
 //! This crate currently has no higher level API for the `push-gateway` feature. If you plan to use it, enable the
 //! `push-gateway` feature in `axum-prometheus`, use `BaseMetricLayer`, and setup your recorder manually, similar to
@@ -136,6 +222,8 @@
 //! }
 //! ```
 //!
+//! If the recorder is expensive to construct, `GenericMetricLayer::pair_with` accepts a closure that returns it instead.
+//!
 //! It's also possible to use `GenericMetricLayer::pair`, however it's only callable if the recorder struct implements `Default` as well.
 //!
 //! ```rust,ignore
@@ -192,6 +280,148 @@ pub const AXUM_HTTP_RESPONSE_BODY_SIZE: &str = match option_env!("AXUM_HTTP_RESP
     None => "axum_http_response_body_size",
 };
 
+/// Identifies the histogram used for the number of data chunks per response body. Defaults to `axum_http_response_chunks`,
+/// but can be changed by setting the `AXUM_HTTP_RESPONSE_CHUNKS` env at compile time.
+pub const AXUM_HTTP_RESPONSE_CHUNKS: &str = match option_env!("AXUM_HTTP_RESPONSE_CHUNKS") {
+    Some(n) => n,
+    None => "axum_http_response_chunks",
+};
+
+/// Identifies the counter used for requests total without any dynamic labels. Defaults to `axum_http_requests_total_unlabeled`,
+/// but can be changed by setting the `AXUM_HTTP_REQUESTS_TOTAL_UNLABELED` env at compile time.
+pub const AXUM_HTTP_REQUESTS_TOTAL_UNLABELED: &str =
+    match option_env!("AXUM_HTTP_REQUESTS_TOTAL_UNLABELED") {
+        Some(n) => n,
+        None => "axum_http_requests_total_unlabeled",
+    };
+
+/// Identifies the histogram/summary used for request phase timings. Defaults to `axum_http_requests_phase_seconds`,
+/// but can be changed by setting the `AXUM_HTTP_REQUESTS_PHASE_SECONDS` env at compile time.
+pub const AXUM_HTTP_REQUESTS_PHASE_SECONDS: &str =
+    match option_env!("AXUM_HTTP_REQUESTS_PHASE_SECONDS") {
+        Some(n) => n,
+        None => "axum_http_requests_phase_seconds",
+    };
+
+/// Identifies the counter used for requests that were cancelled before producing a response. Defaults to `axum_http_requests_cancelled_total`,
+/// but can be changed by setting the `AXUM_HTTP_REQUESTS_CANCELLED_TOTAL` env at compile time.
+pub const AXUM_HTTP_REQUESTS_CANCELLED_TOTAL: &str =
+    match option_env!("AXUM_HTTP_REQUESTS_CANCELLED_TOTAL") {
+        Some(n) => n,
+        None => "axum_http_requests_cancelled_total",
+    };
+
+/// Identifies the histogram/summary used for the time between consecutive requests to an endpoint. Defaults to `axum_http_inter_arrival_seconds`,
+/// but can be changed by setting the `AXUM_HTTP_INTER_ARRIVAL_SECONDS` env at compile time.
+pub const AXUM_HTTP_INTER_ARRIVAL_SECONDS: &str =
+    match option_env!("AXUM_HTTP_INTER_ARRIVAL_SECONDS") {
+        Some(n) => n,
+        None => "axum_http_inter_arrival_seconds",
+    };
+
+/// Identifies the histogram/summary used for the response body streaming duration. Defaults to `axum_http_stream_duration_seconds`,
+/// but can be changed by setting the `AXUM_HTTP_STREAM_DURATION_SECONDS` env at compile time.
+pub const AXUM_HTTP_STREAM_DURATION_SECONDS: &str =
+    match option_env!("AXUM_HTTP_STREAM_DURATION_SECONDS") {
+        Some(n) => n,
+        None => "axum_http_stream_duration_seconds",
+    };
+
+/// Identifies the counter used for requests whose endpoint was rewritten by a group pattern. Defaults to `axum_http_requests_grouped_total`,
+/// but can be changed by setting the `AXUM_HTTP_REQUESTS_GROUPED_TOTAL` env at compile time.
+pub const AXUM_HTTP_REQUESTS_GROUPED_TOTAL: &str =
+    match option_env!("AXUM_HTTP_REQUESTS_GROUPED_TOTAL") {
+        Some(n) => n,
+        None => "axum_http_requests_grouped_total",
+    };
+
+/// Identifies the histogram used for the number of in-flight requests when a request arrives. Defaults to `axum_http_requests_concurrency`,
+/// but can be changed by setting the `AXUM_HTTP_REQUESTS_CONCURRENCY` env at compile time.
+pub const AXUM_HTTP_REQUESTS_CONCURRENCY: &str = match option_env!("AXUM_HTTP_REQUESTS_CONCURRENCY")
+{
+    Some(n) => n,
+    None => "axum_http_requests_concurrency",
+};
+
+/// Identifies the counter used for requests by the status read from the response trailers. Defaults to `axum_grpc_requests_total`,
+/// but can be changed by setting the `AXUM_GRPC_REQUESTS_TOTAL` env at compile time.
+pub const AXUM_GRPC_REQUESTS_TOTAL: &str = match option_env!("AXUM_GRPC_REQUESTS_TOTAL") {
+    Some(n) => n,
+    None => "axum_grpc_requests_total",
+};
+
+/// Identifies the histogram used for the size of request headers in bytes. Defaults to `axum_http_request_headers_bytes`,
+/// but can be changed by setting the `AXUM_HTTP_REQUEST_HEADERS_BYTES` env at compile time.
+pub const AXUM_HTTP_REQUEST_HEADERS_BYTES: &str =
+    match option_env!("AXUM_HTTP_REQUEST_HEADERS_BYTES") {
+        Some(n) => n,
+        None => "axum_http_request_headers_bytes",
+    };
+
+/// Identifies the histogram used for response latency by method only. Defaults to `axum_http_method_duration_seconds`,
+/// but can be changed by setting the `AXUM_HTTP_METHOD_DURATION_SECONDS` env at compile time.
+pub const AXUM_HTTP_METHOD_DURATION_SECONDS: &str =
+    match option_env!("AXUM_HTTP_METHOD_DURATION_SECONDS") {
+        Some(n) => n,
+        None => "axum_http_method_duration_seconds",
+    };
+
+/// Identifies the counter used for requests that failed with an error, instead of producing a response. Defaults to `axum_http_requests_failed_total`,
+/// but can be changed by setting the `AXUM_HTTP_REQUESTS_FAILED_TOTAL` env at compile time.
+pub const AXUM_HTTP_REQUESTS_FAILED_TOTAL: &str =
+    match option_env!("AXUM_HTTP_REQUESTS_FAILED_TOTAL") {
+        Some(n) => n,
+        None => "axum_http_requests_failed_total",
+    };
+
+/// Identifies the counter used for started requests, in place of the pending requests gauge. Defaults to `axum_http_requests_started_total`,
+/// but can be changed by setting the `AXUM_HTTP_REQUESTS_STARTED_TOTAL` env at compile time.
+pub const AXUM_HTTP_REQUESTS_STARTED_TOTAL: &str =
+    match option_env!("AXUM_HTTP_REQUESTS_STARTED_TOTAL") {
+        Some(n) => n,
+        None => "axum_http_requests_started_total",
+    };
+
+/// Identifies the counter used for finished requests, in place of the pending requests gauge. Defaults to `axum_http_requests_finished_total`,
+/// but can be changed by setting the `AXUM_HTTP_REQUESTS_FINISHED_TOTAL` env at compile time.
+pub const AXUM_HTTP_REQUESTS_FINISHED_TOTAL: &str =
+    match option_env!("AXUM_HTTP_REQUESTS_FINISHED_TOTAL") {
+        Some(n) => n,
+        None => "axum_http_requests_finished_total",
+    };
+
+/// Identifies the counter used for outbound requests recorded by the `ClientMetricLayer`. Defaults to `axum_http_client_requests_total`,
+/// but can be changed by setting the `AXUM_HTTP_CLIENT_REQUESTS_TOTAL` env at compile time.
+pub const AXUM_HTTP_CLIENT_REQUESTS_TOTAL: &str =
+    match option_env!("AXUM_HTTP_CLIENT_REQUESTS_TOTAL") {
+        Some(n) => n,
+        None => "axum_http_client_requests_total",
+    };
+
+/// Identifies the histogram used for outbound request latencies recorded by the `ClientMetricLayer`. Defaults to `axum_http_client_requests_duration_seconds`,
+/// but can be changed by setting the `AXUM_HTTP_CLIENT_REQUESTS_DURATION_SECONDS` env at compile time.
+pub const AXUM_HTTP_CLIENT_REQUESTS_DURATION_SECONDS: &str =
+    match option_env!("AXUM_HTTP_CLIENT_REQUESTS_DURATION_SECONDS") {
+        Some(n) => n,
+        None => "axum_http_client_requests_duration_seconds",
+    };
+
+/// Identifies the counter used for the total number of response body bytes. Defaults to `axum_http_response_body_bytes_total`,
+/// but can be changed by setting the `AXUM_HTTP_RESPONSE_BODY_BYTES_TOTAL` env at compile time.
+pub const AXUM_HTTP_RESPONSE_BODY_BYTES_TOTAL: &str =
+    match option_env!("AXUM_HTTP_RESPONSE_BODY_BYTES_TOTAL") {
+        Some(n) => n,
+        None => "axum_http_response_body_bytes_total",
+    };
+
+/// Identifies the counter used for requests exceeding their latency SLO. Defaults to `axum_http_slo_violations_total`,
+/// but can be changed by setting the `AXUM_HTTP_SLO_VIOLATIONS_TOTAL` env at compile time.
+pub const AXUM_HTTP_SLO_VIOLATIONS_TOTAL: &str = match option_env!("AXUM_HTTP_SLO_VIOLATIONS_TOTAL")
+{
+    Some(n) => n,
+    None => "axum_http_slo_violations_total",
+};
+
 #[doc(hidden)]
 pub static PREFIXED_HTTP_REQUESTS_TOTAL: OnceLock<String> = OnceLock::new();
 #[doc(hidden)]
@@ -200,33 +430,121 @@ pub static PREFIXED_HTTP_REQUESTS_DURATION_SECONDS: OnceLock<String> = OnceLock:
 pub static PREFIXED_HTTP_REQUESTS_PENDING: OnceLock<String> = OnceLock::new();
 #[doc(hidden)]
 pub static PREFIXED_HTTP_RESPONSE_BODY_SIZE: OnceLock<String> = OnceLock::new();
+#[doc(hidden)]
+pub static PREFIXED_HTTP_RESPONSE_CHUNKS: OnceLock<String> = OnceLock::new();
+#[doc(hidden)]
+pub static PREFIXED_HTTP_REQUESTS_TOTAL_UNLABELED: OnceLock<String> = OnceLock::new();
+#[doc(hidden)]
+pub static PREFIXED_HTTP_REQUESTS_PHASE_SECONDS: OnceLock<String> = OnceLock::new();
+#[doc(hidden)]
+pub static PREFIXED_HTTP_REQUESTS_CANCELLED_TOTAL: OnceLock<String> = OnceLock::new();
+#[doc(hidden)]
+pub static PREFIXED_HTTP_INTER_ARRIVAL_SECONDS: OnceLock<String> = OnceLock::new();
+#[doc(hidden)]
+pub static PREFIXED_HTTP_STREAM_DURATION_SECONDS: OnceLock<String> = OnceLock::new();
+#[doc(hidden)]
+pub static PREFIXED_HTTP_REQUESTS_GROUPED_TOTAL: OnceLock<String> = OnceLock::new();
+#[doc(hidden)]
+pub static PREFIXED_HTTP_REQUESTS_CONCURRENCY: OnceLock<String> = OnceLock::new();
+#[doc(hidden)]
+pub static PREFIXED_GRPC_REQUESTS_TOTAL: OnceLock<String> = OnceLock::new();
+#[doc(hidden)]
+pub static PREFIXED_HTTP_REQUEST_HEADERS_BYTES: OnceLock<String> = OnceLock::new();
+#[doc(hidden)]
+pub static PREFIXED_HTTP_METHOD_DURATION_SECONDS: OnceLock<String> = OnceLock::new();
+#[doc(hidden)]
+pub static PREFIXED_HTTP_REQUESTS_FAILED_TOTAL: OnceLock<String> = OnceLock::new();
+#[doc(hidden)]
+pub static PREFIXED_HTTP_REQUESTS_STARTED_TOTAL: OnceLock<String> = OnceLock::new();
+#[doc(hidden)]
+pub static PREFIXED_HTTP_REQUESTS_FINISHED_TOTAL: OnceLock<String> = OnceLock::new();
+#[doc(hidden)]
+pub static PREFIXED_HTTP_CLIENT_REQUESTS_TOTAL: OnceLock<String> = OnceLock::new();
+#[doc(hidden)]
+pub static PREFIXED_HTTP_CLIENT_REQUESTS_DURATION_SECONDS: OnceLock<String> = OnceLock::new();
+#[doc(hidden)]
+pub static PREFIXED_HTTP_RESPONSE_BODY_BYTES_TOTAL: OnceLock<String> = OnceLock::new();
+#[doc(hidden)]
+pub static PREFIXED_HTTP_SLO_VIOLATIONS_TOTAL: OnceLock<String> = OnceLock::new();
 
 use std::borrow::Cow;
 use std::collections::HashMap;
+use std::future::Future;
 use std::marker::PhantomData;
-use std::sync::atomic::AtomicBool;
-use std::sync::{Arc, OnceLock};
+use std::net::SocketAddr;
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::Duration;
 use std::time::Instant;
 
+#[cfg(feature = "prometheus")]
+mod adaptive_buckets;
 mod builder;
+mod client;
+#[cfg(feature = "debug-file")]
+mod debug_file;
+mod duration_override;
+#[cfg(feature = "fanout")]
+mod fanout;
+#[cfg(feature = "prometheus")]
+pub mod filter;
+#[cfg(feature = "gzip")]
+pub mod gzip;
+#[cfg(feature = "json")]
+pub mod json;
 pub mod lifecycle;
+mod noop;
+#[cfg(feature = "otel")]
+mod otel;
+mod phase;
+mod render;
+#[cfg(feature = "ring-buffer")]
+pub mod ring_buffer;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod utils;
-use axum::extract::MatchedPath;
+#[cfg(feature = "prometheus")]
+pub use adaptive_buckets::{AdaptiveBucketsHandle, AdaptiveBucketsRecorder};
+use axum::extract::{ConnectInfo, MatchedPath, OriginalUri};
+pub use builder::Descriptions;
 pub use builder::EndpointLabel;
+pub use builder::ErrorCodeSource;
+pub use builder::IpLabelMode;
+pub use builder::LabelKeys;
+pub use builder::MetricKind;
 pub use builder::MetricLayerBuilder;
 #[cfg(feature = "prometheus")]
 pub use builder::PrometheusMetricLayerBuilder;
-use builder::{LayerOnly, Paired};
+pub use builder::RouteSpecError;
+use builder::{MetricBuilderState, Paired};
+pub use client::{ClientMetricLayer, ClientRequestData, ClientTraffic};
+#[cfg(feature = "debug-file")]
+pub use debug_file::DebugFileRecorder;
+pub use duration_override::DurationOverride;
+#[cfg(feature = "fanout")]
+pub use fanout::FanoutRecorder;
+use http::{HeaderName, HeaderValue};
 use lifecycle::layer::LifeCycleLayer;
 use lifecycle::OnBodyChunk;
 use lifecycle::{service::LifeCycle, Callbacks};
-use metrics::{counter, gauge, histogram, Gauge};
+use metrics::{counter, gauge, histogram, Counter, Gauge, SharedString};
+pub use noop::NoopMetricLayer;
+#[cfg(feature = "prometheus")]
+pub use noop::NoopPrometheusMetricLayer;
+#[cfg(feature = "otel")]
+pub use otel::OtelRecorder;
+pub use phase::{Phase, PhaseTimer};
+pub use render::{RenderError, Renderable};
+#[cfg(feature = "ring-buffer")]
+pub use ring_buffer::RingBufferRecorder;
 use tower::Layer;
 use tower_http::classify::{ClassifiedResponse, SharedClassifier, StatusInRangeAsFailures};
 
 #[cfg(feature = "prometheus")]
-use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use metrics_exporter_prometheus::{
+    Matcher, PrometheusBuilder, PrometheusHandle, PrometheusRecorder,
+};
 
 pub use metrics;
 #[cfg(feature = "prometheus")]
@@ -256,14 +574,402 @@ fn set_prefix(prefix: impl AsRef<str>) {
     PREFIXED_HTTP_RESPONSE_BODY_SIZE
         .set(format!("{}_http_response_body_size", prefix.as_ref()))
         .expect("the prefix has already been set, and can only be set once.");
+    PREFIXED_HTTP_RESPONSE_CHUNKS
+        .set(format!("{}_http_response_chunks", prefix.as_ref()))
+        .expect("the prefix has already been set, and can only be set once.");
+    PREFIXED_HTTP_REQUESTS_TOTAL_UNLABELED
+        .set(format!("{}_http_requests_total_unlabeled", prefix.as_ref()))
+        .expect("the prefix has already been set, and can only be set once.");
+    PREFIXED_HTTP_REQUESTS_PHASE_SECONDS
+        .set(format!("{}_http_requests_phase_seconds", prefix.as_ref()))
+        .expect("the prefix has already been set, and can only be set once.");
+    PREFIXED_HTTP_REQUESTS_CANCELLED_TOTAL
+        .set(format!("{}_http_requests_cancelled_total", prefix.as_ref()))
+        .expect("the prefix has already been set, and can only be set once.");
+    PREFIXED_HTTP_INTER_ARRIVAL_SECONDS
+        .set(format!("{}_http_inter_arrival_seconds", prefix.as_ref()))
+        .expect("the prefix has already been set, and can only be set once.");
+    PREFIXED_HTTP_STREAM_DURATION_SECONDS
+        .set(format!("{}_http_stream_duration_seconds", prefix.as_ref()))
+        .expect("the prefix has already been set, and can only be set once.");
+    PREFIXED_HTTP_REQUESTS_GROUPED_TOTAL
+        .set(format!("{}_http_requests_grouped_total", prefix.as_ref()))
+        .expect("the prefix has already been set, and can only be set once.");
+    PREFIXED_HTTP_REQUESTS_CONCURRENCY
+        .set(format!("{}_http_requests_concurrency", prefix.as_ref()))
+        .expect("the prefix has already been set, and can only be set once.");
+    PREFIXED_GRPC_REQUESTS_TOTAL
+        .set(format!("{}_grpc_requests_total", prefix.as_ref()))
+        .expect("the prefix has already been set, and can only be set once.");
+    PREFIXED_HTTP_REQUEST_HEADERS_BYTES
+        .set(format!("{}_http_request_headers_bytes", prefix.as_ref()))
+        .expect("the prefix has already been set, and can only be set once.");
+    PREFIXED_HTTP_METHOD_DURATION_SECONDS
+        .set(format!("{}_http_method_duration_seconds", prefix.as_ref()))
+        .expect("the prefix has already been set, and can only be set once.");
+    PREFIXED_HTTP_REQUESTS_FAILED_TOTAL
+        .set(format!("{}_http_requests_failed_total", prefix.as_ref()))
+        .expect("the prefix has already been set, and can only be set once.");
+    PREFIXED_HTTP_REQUESTS_STARTED_TOTAL
+        .set(format!("{}_http_requests_started_total", prefix.as_ref()))
+        .expect("the prefix has already been set, and can only be set once.");
+    PREFIXED_HTTP_REQUESTS_FINISHED_TOTAL
+        .set(format!("{}_http_requests_finished_total", prefix.as_ref()))
+        .expect("the prefix has already been set, and can only be set once.");
+    PREFIXED_HTTP_CLIENT_REQUESTS_TOTAL
+        .set(format!("{}_http_client_requests_total", prefix.as_ref()))
+        .expect("the prefix has already been set, and can only be set once.");
+    PREFIXED_HTTP_CLIENT_REQUESTS_DURATION_SECONDS
+        .set(format!(
+            "{}_http_client_requests_duration_seconds",
+            prefix.as_ref()
+        ))
+        .expect("the prefix has already been set, and can only be set once.");
+    PREFIXED_HTTP_RESPONSE_BODY_BYTES_TOTAL
+        .set(format!(
+            "{}_http_response_body_bytes_total",
+            prefix.as_ref()
+        ))
+        .expect("the prefix has already been set, and can only be set once.");
+    PREFIXED_HTTP_SLO_VIOLATIONS_TOTAL
+        .set(format!("{}_http_slo_violations_total", prefix.as_ref()))
+        .expect("the prefix has already been set, and can only be set once.");
+}
+
+fn set_prefix_for(metrics: &[MetricKind], prefix: impl AsRef<str>) {
+    for metric in metrics {
+        let (name, suffix) = match metric {
+            MetricKind::RequestsTotal => (&PREFIXED_HTTP_REQUESTS_TOTAL, "http_requests_total"),
+            MetricKind::RequestsPending => {
+                (&PREFIXED_HTTP_REQUESTS_PENDING, "http_requests_pending")
+            }
+            MetricKind::RequestsDuration => (
+                &PREFIXED_HTTP_REQUESTS_DURATION_SECONDS,
+                "http_requests_duration_seconds",
+            ),
+            MetricKind::ResponseBodySize => {
+                (&PREFIXED_HTTP_RESPONSE_BODY_SIZE, "http_response_body_size")
+            }
+        };
+        let prefixed = format!("{}_{suffix}", prefix.as_ref());
+        // Listing a metric twice is harmless, so only fail if it was prefixed differently before.
+        if name.get() != Some(&prefixed) {
+            name.set(prefixed)
+                .expect("the prefix has already been set, and can only be set once.");
+        }
+    }
+}
+
+/// The metric names a single layer reports under.
+///
+/// Unless a namespace is set, names are resolved from the global prefix (or the compile time
+/// environment variables) at the time the metric is emitted.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct MetricNames(Option<Arc<NamespacedNames>>);
+
+#[derive(Debug)]
+struct NamespacedNames {
+    requests_total: Arc<str>,
+    requests_duration: Arc<str>,
+    requests_pending: Arc<str>,
+    response_body_size: Arc<str>,
+    response_chunks: Arc<str>,
+    requests_total_unlabeled: Arc<str>,
+    requests_phase: Arc<str>,
+    requests_cancelled: Arc<str>,
+    inter_arrival: Arc<str>,
+    stream_duration: Arc<str>,
+    requests_grouped: Arc<str>,
+    requests_concurrency: Arc<str>,
+    grpc_requests_total: Arc<str>,
+    request_headers: Arc<str>,
+    method_duration: Arc<str>,
+    requests_failed: Arc<str>,
+    requests_started: Arc<str>,
+    requests_finished: Arc<str>,
+    client_requests_total: Arc<str>,
+    client_requests_duration: Arc<str>,
+    response_body_bytes_total: Arc<str>,
+    slo_violations: Arc<str>,
+}
+
+impl MetricNames {
+    pub(crate) fn namespaced(namespace: &str) -> Self {
+        Self(Some(Arc::new(NamespacedNames {
+            requests_total: format!("{namespace}_http_requests_total").into(),
+            requests_duration: format!("{namespace}_http_requests_duration_seconds").into(),
+            requests_pending: format!("{namespace}_http_requests_pending").into(),
+            response_body_size: format!("{namespace}_http_response_body_size").into(),
+            response_chunks: format!("{namespace}_http_response_chunks").into(),
+            requests_total_unlabeled: format!("{namespace}_http_requests_total_unlabeled").into(),
+            requests_phase: format!("{namespace}_http_requests_phase_seconds").into(),
+            requests_cancelled: format!("{namespace}_http_requests_cancelled_total").into(),
+            inter_arrival: format!("{namespace}_http_inter_arrival_seconds").into(),
+            stream_duration: format!("{namespace}_http_stream_duration_seconds").into(),
+            requests_grouped: format!("{namespace}_http_requests_grouped_total").into(),
+            requests_concurrency: format!("{namespace}_http_requests_concurrency").into(),
+            grpc_requests_total: format!("{namespace}_grpc_requests_total").into(),
+            request_headers: format!("{namespace}_http_request_headers_bytes").into(),
+            method_duration: format!("{namespace}_http_method_duration_seconds").into(),
+            requests_failed: format!("{namespace}_http_requests_failed_total").into(),
+            requests_started: format!("{namespace}_http_requests_started_total").into(),
+            requests_finished: format!("{namespace}_http_requests_finished_total").into(),
+            client_requests_total: format!("{namespace}_http_client_requests_total").into(),
+            client_requests_duration: format!("{namespace}_http_client_requests_duration_seconds")
+                .into(),
+            response_body_bytes_total: format!("{namespace}_http_response_body_bytes_total").into(),
+            slo_violations: format!("{namespace}_http_slo_violations_total").into(),
+        })))
+    }
+
+    fn resolve(
+        &self,
+        namespaced: fn(&NamespacedNames) -> &Arc<str>,
+        global: fn() -> &'static str,
+    ) -> SharedString {
+        match &self.0 {
+            Some(names) => SharedString::from_shared(Arc::clone(namespaced(names))),
+            None => SharedString::from(global()),
+        }
+    }
+
+    pub(crate) fn requests_total(&self) -> SharedString {
+        self.resolve(|n| &n.requests_total, utils::requests_total_name)
+    }
+
+    pub(crate) fn requests_duration(&self) -> SharedString {
+        self.resolve(|n| &n.requests_duration, utils::requests_duration_name)
+    }
+
+    pub(crate) fn requests_pending(&self) -> SharedString {
+        self.resolve(|n| &n.requests_pending, utils::requests_pending_name)
+    }
+
+    pub(crate) fn response_body_size(&self) -> SharedString {
+        self.resolve(|n| &n.response_body_size, utils::response_body_size_name)
+    }
+
+    pub(crate) fn response_chunks(&self) -> SharedString {
+        self.resolve(|n| &n.response_chunks, utils::response_chunks_name)
+    }
+
+    pub(crate) fn requests_total_unlabeled(&self) -> SharedString {
+        self.resolve(
+            |n| &n.requests_total_unlabeled,
+            utils::requests_total_unlabeled_name,
+        )
+    }
+
+    pub(crate) fn requests_phase(&self) -> SharedString {
+        self.resolve(|n| &n.requests_phase, utils::requests_phase_name)
+    }
+
+    pub(crate) fn requests_cancelled(&self) -> SharedString {
+        self.resolve(|n| &n.requests_cancelled, utils::requests_cancelled_name)
+    }
+
+    pub(crate) fn inter_arrival(&self) -> SharedString {
+        self.resolve(|n| &n.inter_arrival, utils::inter_arrival_name)
+    }
+
+    pub(crate) fn stream_duration(&self) -> SharedString {
+        self.resolve(|n| &n.stream_duration, utils::stream_duration_name)
+    }
+
+    pub(crate) fn requests_grouped(&self) -> SharedString {
+        self.resolve(|n| &n.requests_grouped, utils::requests_grouped_name)
+    }
+
+    pub(crate) fn requests_concurrency(&self) -> SharedString {
+        self.resolve(
+            |n| &n.requests_concurrency,
+            utils::requests_concurrency_name,
+        )
+    }
+
+    pub(crate) fn grpc_requests_total(&self) -> SharedString {
+        self.resolve(|n| &n.grpc_requests_total, utils::grpc_requests_total_name)
+    }
+
+    pub(crate) fn request_headers(&self) -> SharedString {
+        self.resolve(|n| &n.request_headers, utils::request_headers_name)
+    }
+
+    pub(crate) fn method_duration(&self) -> SharedString {
+        self.resolve(|n| &n.method_duration, utils::method_duration_name)
+    }
+
+    pub(crate) fn requests_failed(&self) -> SharedString {
+        self.resolve(|n| &n.requests_failed, utils::requests_failed_name)
+    }
+
+    pub(crate) fn requests_started(&self) -> SharedString {
+        self.resolve(|n| &n.requests_started, utils::requests_started_name)
+    }
+
+    pub(crate) fn requests_finished(&self) -> SharedString {
+        self.resolve(|n| &n.requests_finished, utils::requests_finished_name)
+    }
+
+    pub(crate) fn client_requests_total(&self) -> SharedString {
+        self.resolve(
+            |n| &n.client_requests_total,
+            utils::client_requests_total_name,
+        )
+    }
+
+    pub(crate) fn client_requests_duration(&self) -> SharedString {
+        self.resolve(
+            |n| &n.client_requests_duration,
+            utils::client_requests_duration_name,
+        )
+    }
+
+    pub(crate) fn response_body_bytes_total(&self) -> SharedString {
+        self.resolve(
+            |n| &n.response_body_bytes_total,
+            utils::response_body_bytes_total_name,
+        )
+    }
+
+    pub(crate) fn slo_violations(&self) -> SharedString {
+        self.resolve(|n| &n.slo_violations, utils::slo_violations_name)
+    }
 }
 
 /// A marker struct that implements the [`lifecycle::Callbacks`] trait.
 #[derive(Clone, Default)]
 pub struct Traffic<'a> {
-    ignore_patterns: matchit::Router<()>,
-    group_patterns: HashMap<&'a str, matchit::Router<()>>,
+    // The routers are only modified while building the layer, and shared afterwards, so cloning `Traffic` for
+    // every request stays cheap.
+    ignore_patterns: Arc<matchit::Router<()>>,
+    // `matchit::Router` can't tell if it's empty, so this is tracked separately to skip the lookup.
+    has_ignore_patterns: bool,
+    group_patterns: Arc<HashMap<&'a str, matchit::Router<()>>>,
     endpoint_label: EndpointLabel,
+    pub(crate) body_size: bool,
+    body_size_success_only: bool,
+    body_size_patterns: Option<Arc<matchit::Router<()>>>,
+    pub(crate) response_chunks: bool,
+    global_labels: Vec<(&'static str, SharedString)>,
+    pub(crate) names: MetricNames,
+    pending_count: Arc<AtomicU64>,
+    recording: RecordingSwitch,
+    normalize_trailing_slash: bool,
+    pub(crate) unlabeled_total: bool,
+    pub(crate) grouped_total: bool,
+    pub(crate) concurrency: bool,
+    pub(crate) request_header_size: bool,
+    duration_only_for_success: bool,
+    histogram_without_status: bool,
+    content_type_label: bool,
+    depth_label: bool,
+    query_key_label: bool,
+    lowercase_endpoints: bool,
+    pending_without_method: bool,
+    content_encoding_label: bool,
+    cache_header: Option<&'a str>,
+    retry_after_label: bool,
+    has_body_label: bool,
+    pub(crate) trailer_status: Option<HeaderName>,
+    pub(crate) phase_timing: bool,
+    duration_override: bool,
+    pub(crate) limiter_wait: bool,
+    root_label: Option<&'a str>,
+    label_sanitizer: Option<for<'s> fn(&'s str) -> Cow<'s, str>>,
+    pub(crate) inter_arrival: bool,
+    last_seen: Arc<LastSeen>,
+    ignore_status_range: Option<RangeInclusive<u16>>,
+    excluded_statuses: &'a [u16],
+    pub(crate) status_counters: Vec<(u16, String)>,
+    pub(crate) stream_duration: bool,
+    grouping_cache: Option<Arc<GroupingCache<'a>>>,
+    operation_names: Option<Arc<HashMap<&'a str, SharedString>>>,
+    fallback_endpoint_label: Option<&'a str>,
+    label_keys: LabelKeys,
+    on_request_complete: Option<OnRequestComplete>,
+    on_duration: Option<OnDuration>,
+    tls_label: Option<TlsLabel>,
+    error_code: Option<ErrorCodeSource>,
+    original_path: bool,
+    retry_label: bool,
+    client_ip_label: IpLabelMode,
+    client_ip_header: Option<&'a str>,
+    header_labels: &'a [(&'a str, &'static str)],
+    pub(crate) method_histogram: bool,
+    pub(crate) failure_reasons: bool,
+    pub(crate) pending_as_counters: bool,
+    warmup_until: Option<Instant>,
+    sampled: Option<fn(&http::request::Parts) -> bool>,
+    max_duration: Option<Duration>,
+    slos: Option<Arc<matchit::Router<Duration>>>,
+}
+
+/// A bounded cache of the group pattern (if any) each path resolves to.
+#[derive(Debug)]
+struct GroupingCache<'a> {
+    capacity: usize,
+    groups: std::sync::RwLock<HashMap<String, Option<&'a str>>>,
+    // Set once the cache is full, so the misses after that don't contend on the write lock.
+    full: AtomicBool,
+}
+
+impl<'a> GroupingCache<'a> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            groups: Default::default(),
+            full: AtomicBool::new(false),
+        }
+    }
+
+    fn get(&self, path: &str) -> Option<Option<&'a str>> {
+        self.groups
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(path)
+            .copied()
+    }
+
+    fn insert(&self, path: &str, group: Option<&'a str>) {
+        // Once full, new paths are resolved without caching, so unbounded (e.g. exact) paths can't grow the cache.
+        if self.full.load(Ordering::Relaxed) {
+            return;
+        }
+        let mut groups = self.groups.write().unwrap_or_else(|e| e.into_inner());
+        if groups.len() < self.capacity {
+            groups.insert(path.to_owned(), group);
+        } else {
+            self.full.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+/// The time of the last request to each endpoint (with the same method), for the inter-arrival histogram.
+///
+/// Every tracked request locks the map to swap its entry, so it's a point of contention under high concurrency. It
+/// holds at most [`LastSeen::CAPACITY`] endpoints: once full, new endpoints aren't tracked, so unbounded (e.g. exact)
+/// endpoint labels can't grow it.
+#[derive(Debug, Default)]
+struct LastSeen {
+    endpoints: Mutex<HashMap<(&'static str, SharedString), Instant>>,
+}
+
+impl LastSeen {
+    const CAPACITY: usize = 1024;
+
+    /// Record a request to `endpoint` at `now`, returning the time of the previous one, if it's still tracked.
+    fn swap(&self, method: &'static str, endpoint: SharedString, now: Instant) -> Option<Instant> {
+        let mut endpoints = self.endpoints.lock().unwrap_or_else(|e| e.into_inner());
+        let key = (method, endpoint);
+        if let Some(last) = endpoints.get_mut(&key) {
+            return Some(std::mem::replace(last, now));
+        }
+        if endpoints.len() < Self::CAPACITY {
+            endpoints.insert(key, now);
+        }
+        None
+    }
 }
 
 impl<'a> Traffic<'a> {
@@ -272,74 +978,612 @@ impl<'a> Traffic<'a> {
     }
 
     pub(crate) fn with_ignore_pattern(&mut self, ignore_pattern: &'a str) {
-        self.ignore_patterns
-            .insert(ignore_pattern, ())
+        self.try_with_ignore_pattern(ignore_pattern)
             .expect("good route specs");
     }
 
+    pub(crate) fn try_with_ignore_pattern(
+        &mut self,
+        ignore_pattern: &'a str,
+    ) -> Result<(), RouteSpecError> {
+        Arc::make_mut(&mut self.ignore_patterns)
+            .insert(ignore_pattern, ())
+            .map_err(|source| RouteSpecError::new(ignore_pattern, source))?;
+        self.has_ignore_patterns = true;
+        Ok(())
+    }
+
     pub(crate) fn with_ignore_patterns(&mut self, ignore_patterns: &'a [&'a str]) {
+        self.try_with_ignore_patterns(ignore_patterns)
+            .expect("good route specs");
+    }
+
+    pub(crate) fn try_with_ignore_patterns(
+        &mut self,
+        ignore_patterns: &'a [&'a str],
+    ) -> Result<(), RouteSpecError> {
         for pattern in ignore_patterns {
-            self.with_ignore_pattern(pattern);
+            self.try_with_ignore_pattern(pattern)?;
         }
+        Ok(())
     }
 
     pub(crate) fn with_group_patterns_as(&mut self, group_pattern: &'a str, patterns: &'a [&str]) {
-        self.group_patterns
+        self.try_with_group_patterns_as(group_pattern, patterns)
+            .expect("good route specs");
+    }
+
+    pub(crate) fn try_with_group_patterns_as(
+        &mut self,
+        group_pattern: &'a str,
+        patterns: &'a [&str],
+    ) -> Result<(), RouteSpecError> {
+        let router = Arc::make_mut(&mut self.group_patterns)
             .entry(group_pattern)
-            .and_modify(|router| {
-                for pattern in patterns {
-                    router.insert(*pattern, ()).expect("good route specs");
-                }
-            })
-            .or_insert_with(|| {
-                let mut inner_router = matchit::Router::new();
-                for pattern in patterns {
-                    inner_router.insert(*pattern, ()).expect("good route specs");
-                }
-                inner_router
-            });
+            .or_default();
+        for pattern in patterns {
+            router
+                .insert(*pattern, ())
+                .map_err(|source| RouteSpecError::new(pattern, source))?;
+        }
+        Ok(())
     }
 
     pub(crate) fn ignores(&self, path: &str) -> bool {
-        self.ignore_patterns.at(path).is_ok()
+        self.has_ignore_patterns && self.ignore_patterns.at(path).is_ok()
+    }
+
+    /// The group `path` is reported under, if any of the group patterns match it.
+    pub(crate) fn group_of(&self, path: &str) -> Option<&'a str> {
+        if self.group_patterns.is_empty() {
+            return None;
+        }
+        let Some(cache) = &self.grouping_cache else {
+            return self.find_group(path);
+        };
+        if let Some(group) = cache.get(path) {
+            return group;
+        }
+        let group = self.find_group(path);
+        cache.insert(path, group);
+        group
     }
 
-    pub(crate) fn apply_group_pattern(&self, path: &'a str) -> &'a str {
+    fn find_group(&self, path: &str) -> Option<&'a str> {
         self.group_patterns
             .iter()
             .find_map(|(&group, router)| router.at(path).ok().and(Some(group)))
-            .unwrap_or(path)
+    }
+
+    pub(crate) fn with_operation_names(&mut self, operation_names: HashMap<&'a str, &'a str>) {
+        let operation_names = operation_names
+            .into_iter()
+            .map(|(path, name)| (path, SharedString::from_shared(name.into())))
+            .collect();
+        self.operation_names = Some(Arc::new(operation_names));
+    }
+
+    pub(crate) fn with_fallback_endpoint_label(&mut self, label: &'a str) {
+        self.fallback_endpoint_label = Some(label);
+    }
+
+    pub(crate) fn with_label_keys(&mut self, label_keys: LabelKeys) {
+        self.label_keys = label_keys;
+    }
+
+    pub(crate) fn with_grouping_cache(&mut self, capacity: usize) {
+        self.grouping_cache = Some(Arc::new(GroupingCache::new(capacity)));
     }
 
     pub(crate) fn with_endpoint_label_type(&mut self, endpoint_label: EndpointLabel) {
         self.endpoint_label = endpoint_label;
     }
+
+    pub(crate) fn with_body_size(&mut self, enable: bool) {
+        self.body_size = enable;
+    }
+
+    pub(crate) fn with_body_size_success_only(&mut self, enable: bool) {
+        self.body_size_success_only = enable;
+    }
+
+    pub(crate) fn with_body_size_patterns(&mut self, patterns: &'a [&'a str]) {
+        self.try_with_body_size_patterns(patterns)
+            .expect("good route specs");
+    }
+
+    pub(crate) fn try_with_body_size_patterns(
+        &mut self,
+        patterns: &'a [&'a str],
+    ) -> Result<(), RouteSpecError> {
+        let router = Arc::make_mut(self.body_size_patterns.get_or_insert_with(Default::default));
+        for &pattern in patterns {
+            router
+                .insert(pattern, ())
+                .map_err(|source| RouteSpecError::new(pattern, source))?;
+        }
+        self.body_size = true;
+        Ok(())
+    }
+
+    pub(crate) fn with_slo(&mut self, pattern: &str, max: Duration) {
+        self.try_with_slo(pattern, max).expect("good route specs");
+    }
+
+    pub(crate) fn try_with_slo(
+        &mut self,
+        pattern: &str,
+        max: Duration,
+    ) -> Result<(), RouteSpecError> {
+        Arc::make_mut(self.slos.get_or_insert_with(Default::default))
+            .insert(pattern, max)
+            .map_err(|source| RouteSpecError::new(pattern, source))
+    }
+
+    pub(crate) fn has_slos(&self) -> bool {
+        self.slos.is_some()
+    }
+
+    /// Whether the response body size of a request to `path` should be recorded.
+    fn records_body_size(&self, path: &str) -> bool {
+        self.body_size
+            && self
+                .body_size_patterns
+                .as_ref()
+                .map_or(true, |patterns| patterns.at(path).is_ok())
+    }
+
+    pub(crate) fn with_response_chunks(&mut self, enable: bool) {
+        self.response_chunks = enable;
+    }
+
+    pub(crate) fn with_global_label(&mut self, key: &'static str, value: String) {
+        self.global_labels.retain(|(k, _)| *k != key);
+        self.global_labels
+            .push((key, SharedString::from_shared(value.into())));
+    }
+
+    pub(crate) fn with_namespace(&mut self, namespace: &str) {
+        self.names = MetricNames::namespaced(namespace);
+    }
+
+    pub(crate) fn with_normalize_trailing_slash(&mut self, normalize: bool) {
+        self.normalize_trailing_slash = normalize;
+    }
+
+    /// Strip a single trailing slash from `endpoint` if normalization is enabled. The root path is kept as is.
+    pub(crate) fn normalize<'e>(&self, endpoint: &'e str) -> &'e str {
+        if !self.normalize_trailing_slash || endpoint == "/" {
+            return endpoint;
+        }
+        endpoint.strip_suffix('/').unwrap_or(endpoint)
+    }
+
+    pub(crate) fn with_root_label(&mut self, root_label: &'a str) {
+        self.root_label = Some(root_label);
+    }
+
+    pub(crate) fn with_label_sanitizer(&mut self, sanitizer: for<'s> fn(&'s str) -> Cow<'s, str>) {
+        self.label_sanitizer = Some(sanitizer);
+    }
+
+    pub(crate) fn with_inter_arrival(&mut self, enable: bool) {
+        self.inter_arrival = enable;
+    }
+
+    pub(crate) fn with_ignore_status_range(&mut self, range: RangeInclusive<u16>) {
+        self.ignore_status_range = Some(range);
+    }
+
+    pub(crate) fn with_excluded_statuses(&mut self, statuses: &'a [u16]) {
+        self.excluded_statuses = statuses;
+    }
+
+    pub(crate) fn with_stream_duration(&mut self, enable: bool) {
+        self.stream_duration = enable;
+    }
+
+    pub(crate) fn with_unlabeled_total(&mut self, enable: bool) {
+        self.unlabeled_total = enable;
+    }
+
+    /// Register the standard metrics of `endpoint` and `method` with zero values, as if a successful request was about
+    /// to be recorded.
+    pub(crate) fn preregister(&self, endpoint: &str, method: &http::Method) {
+        let labels: Vec<_> = [
+            (
+                self.label_keys.method,
+                SharedString::const_str(utils::as_label(method)),
+            ),
+            (
+                self.label_keys.endpoint,
+                SharedString::from_owned(endpoint.to_owned()),
+            ),
+        ]
+        .into_iter()
+        .chain(self.global_labels.iter().cloned())
+        .collect();
+        let pending_labels = if self.pending_without_method {
+            &labels[1..]
+        } else {
+            &labels[..]
+        };
+        if self.pending_as_counters {
+            counter!(self.names.requests_started(), pending_labels).increment(0);
+            counter!(self.names.requests_finished(), pending_labels).increment(0);
+        } else {
+            gauge!(self.names.requests_pending(), pending_labels).increment(0.0);
+        }
+        let mut labels = labels;
+        labels.insert(1, (self.label_keys.status, SharedString::const_str("200")));
+        counter!(self.names.requests_total(), &labels).increment(0);
+        // Registering the histogram is enough for it to be exported.
+        let _ = histogram!(self.names.requests_duration(), &labels);
+    }
+
+    pub(crate) fn with_pending_as_counters(&mut self, enable: bool) {
+        self.pending_as_counters = enable;
+    }
+
+    pub(crate) fn with_failure_reasons(&mut self, enable: bool) {
+        self.failure_reasons = enable;
+    }
+
+    pub(crate) fn with_method_histogram(&mut self, enable: bool) {
+        self.method_histogram = enable;
+    }
+
+    pub(crate) fn with_header_labels(&mut self, headers: &'a [(&'a str, &'static str)]) {
+        self.header_labels = headers;
+    }
+
+    pub(crate) fn with_original_path_from_extension(&mut self) {
+        self.original_path = true;
+    }
+
+    pub(crate) fn with_error_code_label(&mut self, source: ErrorCodeSource) {
+        self.error_code = Some(source);
+    }
+
+    pub(crate) fn with_tls_label(&mut self, extract: TlsLabel) {
+        self.tls_label = Some(extract);
+    }
+
+    pub(crate) fn with_on_request_complete(&mut self, callback: OnRequestComplete) {
+        self.on_request_complete = Some(callback);
+    }
+
+    pub(crate) fn with_on_duration(&mut self, callback: OnDuration) {
+        self.on_duration = Some(callback);
+    }
+
+    pub(crate) fn with_request_header_size(&mut self, enable: bool) {
+        self.request_header_size = enable;
+    }
+
+    pub(crate) fn with_concurrency(&mut self, enable: bool) {
+        self.concurrency = enable;
+    }
+
+    pub(crate) fn with_grouped_total(&mut self, enable: bool) {
+        self.grouped_total = enable;
+    }
+
+    pub(crate) fn with_duration_only_for_success(&mut self, enable: bool) {
+        self.duration_only_for_success = enable;
+    }
+
+    pub(crate) fn with_histogram_without_status(&mut self, enable: bool) {
+        self.histogram_without_status = enable;
+    }
+
+    pub(crate) fn with_content_type_label(&mut self, enable: bool) {
+        self.content_type_label = enable;
+    }
+
+    pub(crate) fn with_trailer_status(&mut self, header_name: &str) {
+        self.trailer_status =
+            Some(HeaderName::from_bytes(header_name.as_bytes()).expect("a valid header name"));
+    }
+
+    pub(crate) fn with_content_encoding_label(&mut self, enable: bool) {
+        self.content_encoding_label = enable;
+    }
+
+    pub(crate) fn with_pending_without_method(&mut self, enable: bool) {
+        self.pending_without_method = enable;
+    }
+
+    pub(crate) fn with_lowercase_endpoints(&mut self, enable: bool) {
+        self.lowercase_endpoints = enable;
+    }
+
+    pub(crate) fn with_depth_label(&mut self, enable: bool) {
+        self.depth_label = enable;
+    }
+
+    pub(crate) fn with_query_key_label(&mut self, enable: bool) {
+        self.query_key_label = enable;
+    }
+
+    pub(crate) fn with_status_counter(&mut self, status: u16, metric_name: &str) {
+        self.status_counters.push((status, metric_name.to_owned()));
+    }
+
+    pub(crate) fn with_client_ip_label(&mut self, mode: IpLabelMode) {
+        self.client_ip_label = mode;
+    }
+
+    pub(crate) fn with_client_ip_header(&mut self, header: &'a str) {
+        self.client_ip_header = Some(header);
+    }
+
+    pub(crate) fn with_retry_label(&mut self) {
+        self.retry_label = true;
+    }
+
+    pub(crate) fn with_max_duration(&mut self, max: Duration) {
+        self.max_duration = Some(max);
+    }
+
+    pub(crate) fn with_warmup_until(&mut self, until: Instant) {
+        self.warmup_until = Some(until);
+    }
+
+    pub(crate) fn with_sampled(&mut self, sampled: fn(&http::request::Parts) -> bool) {
+        self.sampled = Some(sampled);
+    }
+
+    pub(crate) fn with_cache_label(&mut self, header_name: &'a str) {
+        self.cache_header = Some(header_name);
+    }
+
+    pub(crate) fn with_retry_after_label(&mut self, enable: bool) {
+        self.retry_after_label = enable;
+    }
+
+    pub(crate) fn with_has_body_label(&mut self, enable: bool) {
+        self.has_body_label = enable;
+    }
+
+    pub(crate) fn with_phase_timing(&mut self, enable: bool) {
+        self.phase_timing = enable;
+    }
+
+    pub(crate) fn with_duration_override(&mut self, enable: bool) {
+        self.duration_override = enable;
+    }
+
+    pub(crate) fn with_limiter_wait(&mut self, enable: bool) {
+        self.limiter_wait = enable;
+    }
+
+    pub(crate) fn recording_switch(&self) -> RecordingSwitch {
+        self.recording.clone()
+    }
+
+    pub(crate) fn pending_count(&self) -> u64 {
+        self.pending_count.load(Ordering::Relaxed)
+    }
+
+    /// Whether the response body needs to be observed chunk by chunk.
+    pub(crate) fn observes_body(&self) -> bool {
+        self.body_size
+            || self.response_chunks
+            || self.stream_duration
+            || self.trailer_status.is_some()
+    }
+}
+
+/// The classifier used by the metric layers by default, which classifies all `4xx` and `5xx` responses as failures.
+///
+/// Useful for composing a custom [`LifeCycleLayer`] with the same success/failure semantics as this crate.
+///
+/// [`LifeCycleLayer`]: lifecycle::layer::LifeCycleLayer
+pub fn default_classifier() -> SharedClassifier<StatusInRangeAsFailures> {
+    StatusInRangeAsFailures::new_for_client_and_server_errors().into_make_classifier()
 }
 
 /// Struct used for storing and calculating information about the current request.
+///
+/// Custom [`lifecycle::Callbacks`] implementations can construct it with [`MetricsData::new`]. Only the public fields
+/// are meant to be set by users, the rest is managed by this crate.
 #[derive(Debug, Clone)]
 pub struct MetricsData {
+    /// The endpoint label of the request.
     pub endpoint: String,
+    /// When the request arrived.
     pub start: Instant,
+    /// The HTTP method of the request.
     pub method: &'static str,
-    pub body_size: f64,
+    /// The size of the response body recorded so far.
+    pub body_size: u64,
     // FIXME: Unclear at the moment, maybe just a simple bool could suffice here?
     pub(crate) exact_body_size_called: Arc<AtomicBool>,
+    pub(crate) record_body_size: bool,
+    /// Whether the body size is left out of the body size histogram, because the response is classified as a
+    /// failure and only successful ones are recorded.
+    pub(crate) skip_body_size_histogram: bool,
+    pub(crate) record_chunks: bool,
+    pub(crate) chunks: u64,
+    /// The labels attached to every metric emitted for this request: method, endpoint, and any extra labels
+    /// (e.g. the version). Built once in `prepare`, so emitting metrics doesn't need to allocate the label values again.
+    pub(crate) labels: Arc<[(&'static str, SharedString)]>,
+    pub(crate) names: MetricNames,
+    pub(crate) phase_timer: Option<PhaseTimer>,
+    /// The duration reported by the handler, if it's allowed to override the measured one.
+    pub(crate) duration_override: Option<DurationOverride>,
+    /// The latency SLO of the endpoint, if any.
+    pub(crate) slo: Option<Duration>,
+    /// How long the request waited for the inner service to become ready, in seconds, if it's recorded.
+    pub(crate) limiter_wait: Option<f64>,
+    /// When the response body started streaming, if the stream duration is recorded.
+    pub(crate) body_start: Option<Instant>,
+    /// The trailer to read the status of the request from, if any.
+    pub(crate) trailer_status: Option<HeaderName>,
+    /// The normalized `Content-Encoding` of the response, if the body size is labeled with it.
+    pub(crate) encoding: Option<&'static str>,
+    /// The TLS label of the request, if the requests total is labeled with it.
+    pub(crate) tls: Option<SharedString>,
+    /// Whether the request is a retry, if the requests total is labeled with it.
+    pub(crate) retry: Option<bool>,
+    /// The subnet of the client IP address, if the requests total is labeled with it.
+    pub(crate) client_subnet: Option<SharedString>,
+    /// Whether the request has a body, if the requests duration is labeled with it.
+    pub(crate) has_body: Option<bool>,
+}
+
+impl MetricsData {
+    /// Create the data of a request to `endpoint` with the given `method`, that arrived just now.
+    ///
+    /// Metrics emitted for it are labeled with only the method and the endpoint, and the response body size and chunks
+    /// are not recorded.
+    pub fn new(endpoint: impl Into<String>, method: &'static str) -> Self {
+        let endpoint = endpoint.into();
+        let keys = LabelKeys::default();
+        let labels = Arc::new([
+            (keys.method, SharedString::const_str(method)),
+            (keys.endpoint, SharedString::from_owned(endpoint.clone())),
+        ]);
+        Self {
+            endpoint,
+            start: Instant::now(),
+            method,
+            body_size: 0,
+            exact_body_size_called: Arc::new(AtomicBool::new(false)),
+            record_body_size: false,
+            skip_body_size_histogram: false,
+            record_chunks: false,
+            chunks: 0,
+            labels,
+            names: MetricNames::default(),
+            phase_timer: None,
+            duration_override: None,
+            slo: None,
+            limiter_wait: None,
+            body_start: None,
+            trailer_status: None,
+            encoding: None,
+            tls: None,
+            retry: None,
+            client_subnet: None,
+            has_body: None,
+        }
+    }
+
+    /// Count this request by the status read from the trailers (or the headers of a trailers-only response).
+    fn record_trailer_status(&self, status: &HeaderValue) {
+        let status = status.to_str().unwrap_or("invalid").to_owned();
+        // The status goes right after the method.
+        let labels = self.labels_with(1, ("grpc_status", SharedString::from_owned(status)));
+        counter!(self.names.grpc_requests_total(), &labels).increment(1);
+    }
+
+    /// The labels of this request, with `label` inserted at `index`.
+    fn labels_with(
+        &self,
+        index: usize,
+        label: (&'static str, SharedString),
+    ) -> Vec<(&'static str, SharedString)> {
+        let mut labels = Vec::with_capacity(self.labels.len() + 1);
+        labels.extend_from_slice(&self.labels[..index]);
+        labels.push(label);
+        labels.extend_from_slice(&self.labels[index..]);
+        labels
+    }
+}
+
+/// The path of a request before it was rewritten, inserted into the request extensions by the middleware that
+/// rewrites it. See [`MetricLayerBuilder::with_original_path_from_extension`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OriginalPath(pub String);
+
+/// The number of times a request has been retried before this attempt, inserted into the request extensions by the
+/// retry middleware that replays it (`0` for the first attempt). See [`MetricLayerBuilder::with_retry_label`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryCount(pub u32);
+
+/// A summary of a request, passed to the callback registered with [`MetricLayerBuilder::on_request_complete`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct RequestSummary<'a> {
+    /// The HTTP method of the request.
+    pub method: &'a str,
+    /// The endpoint label the request is reported under.
+    pub endpoint: &'a str,
+    /// The status code of the response.
+    pub status: u16,
+    /// The time it took to produce the response.
+    pub duration: Duration,
+    /// The size of the response body, if it's known before the body is streamed (from `Content-Length`).
+    pub body_size: Option<u64>,
+}
+
+/// The callback registered with [`MetricLayerBuilder::on_request_complete`].
+pub(crate) type OnRequestComplete = Arc<dyn Fn(&RequestSummary<'_>) + Send + Sync>;
+
+/// The callback registered with [`MetricLayerBuilder::on_duration`].
+pub(crate) type OnDuration = Arc<dyn Fn(f64, &MetricsData, http::StatusCode) + Send + Sync>;
+
+/// Reads the TLS label registered with [`MetricLayerBuilder::with_tls_label`] from the request extensions.
+pub(crate) type TlsLabel = Arc<dyn Fn(&http::Extensions) -> Option<String> + Send + Sync>;
+
+/// A handle to turn recording on and off at runtime, without rebuilding the layer. See
+/// [`GenericMetricLayer::recording_switch`].
+///
+/// Recording is enabled by default.
+#[derive(Debug, Clone)]
+pub struct RecordingSwitch(Arc<AtomicBool>);
+
+impl RecordingSwitch {
+    /// Resume recording metrics.
+    pub fn enable(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Stop recording metrics. Requests that are already in-flight are still recorded.
+    pub fn disable(&self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+
+    /// Whether metrics are currently recorded.
+    pub fn is_enabled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+impl Default for RecordingSwitch {
+    fn default() -> Self {
+        Self(Arc::new(AtomicBool::new(true)))
+    }
 }
 
 #[doc(hidden)]
-pub struct Pending(Gauge);
+pub struct Pending(InFlight, Arc<AtomicU64>);
+
+// How a request is tracked while it's in-flight.
+enum InFlight {
+    // The pending requests gauge, to decrement once the request is done.
+    Pending(Gauge),
+    // The finished requests counter, to increment once the request is done.
+    Finished(Counter),
+}
 
 impl Drop for Pending {
     fn drop(&mut self) {
-        self.0.decrement(1);
+        match &self.0 {
+            InFlight::Pending(gauge) => gauge.decrement(1),
+            InFlight::Finished(counter) => counter.increment(1),
+        }
+        self.1.fetch_sub(1, Ordering::Relaxed);
     }
 }
 
 // The `Pending` struct is behind an Arc to make sure we only drop it once (since we're cloning this across the lifecycle).
 type DefaultCallbackData = Option<(MetricsData, Arc<Pending>)>;
 
-/// A marker struct that implements [`lifecycle::OnBodyChunk`], so it can be used to track response body sizes.
+/// A marker struct that implements [`lifecycle::OnBodyChunk`], so it can be used to track response body sizes
+/// and the number of data chunks in response bodies.
 #[derive(Clone)]
 pub struct BodySizeRecorder;
 
@@ -353,21 +1597,65 @@ where
     fn call(&mut self, body: &B, body_size: Option<u64>, data: &mut Self::Data) {
         let Some((metrics_data, _pending_guard)) = data else {
             return;
-        };
+        };
+        if metrics_data.record_chunks {
+            metrics_data.chunks += 1;
+        }
+        // Responses to `HEAD` requests never send their body, even if the inner service produced one (or reported
+        // its would-be size in `Content-Length`), so there's nothing to record.
+        if !metrics_data.record_body_size || metrics_data.method == "HEAD" {
+            return;
+        }
+        // The bytes of error responses are still counted, only their sizes are left out of the histogram.
+        let record_histogram = !metrics_data.skip_body_size_histogram;
         // If the exact body size is known ahead of time, we'll just call this whole thing once.
         if let Some(exact_size) = body_size {
             if !metrics_data
                 .exact_body_size_called
-                .swap(true, std::sync::atomic::Ordering::Relaxed)
+                .swap(true, Ordering::Relaxed)
             {
-                // If the body size is enormous, we lose some precision. It shouldn't matter really.
-                metrics_data.body_size = exact_size as f64;
-                body_size_histogram(metrics_data);
+                metrics_data.body_size = exact_size;
+                if record_histogram {
+                    body_size_histogram(metrics_data);
+                }
+                body_bytes_counter(metrics_data, exact_size);
             }
         } else {
             // Otherwise, sum all the chunks.
-            metrics_data.body_size += body.remaining() as f64;
-            body_size_histogram(metrics_data);
+            metrics_data.body_size = metrics_data
+                .body_size
+                .saturating_add(body.remaining() as u64);
+            if record_histogram {
+                body_size_histogram(metrics_data);
+            }
+            body_bytes_counter(metrics_data, body.remaining() as u64);
+        }
+    }
+
+    #[inline]
+    fn on_body_end(&mut self, data: &mut Self::Data) {
+        let Some((metrics_data, _pending_guard)) = data else {
+            return;
+        };
+        if metrics_data.record_chunks {
+            response_chunks_histogram(metrics_data);
+        }
+        if let Some(body_start) = metrics_data.body_start {
+            stream_duration_histogram(metrics_data, body_start);
+        }
+    }
+
+    #[inline]
+    fn on_trailers(&mut self, trailers: &http::HeaderMap, data: &mut Self::Data) {
+        let Some((metrics_data, _pending_guard)) = data else {
+            return;
+        };
+        if let Some(status) = metrics_data
+            .trailer_status
+            .as_ref()
+            .and_then(|name| trailers.get(name))
+        {
+            metrics_data.record_trailer_status(status);
         }
     }
 }
@@ -384,31 +1672,88 @@ where
             T::call(this, body, body_size, data);
         }
     }
+
+    fn on_body_end(&mut self, data: &mut Self::Data) {
+        if let Some(this) = self {
+            T::on_body_end(this, data);
+        }
+    }
+
+    fn on_body_drop(&mut self, data: &mut Self::Data) {
+        if let Some(this) = self {
+            T::on_body_drop(this, data);
+        }
+    }
+
+    fn on_trailers(&mut self, trailers: &http::HeaderMap, data: &mut Self::Data) {
+        if let Some(this) = self {
+            T::on_trailers(this, trailers, data);
+        }
+    }
 }
 
 fn body_size_histogram(metrics_data: &MetricsData) {
-    let labels = &[
-        ("method", metrics_data.method.to_owned()),
-        ("endpoint", metrics_data.endpoint.clone()),
-    ];
-    let response_body_size = PREFIXED_HTTP_RESPONSE_BODY_SIZE
-        .get()
-        .map_or(AXUM_HTTP_RESPONSE_BODY_SIZE, |s| s.as_str());
-    metrics::histogram!(response_body_size, labels).record(metrics_data.body_size);
+    let name = metrics_data.names.response_body_size();
+    let size = metrics_data.body_size as f64;
+    if let Some(encoding) = metrics_data.encoding {
+        // The encoding goes right after the method and endpoint.
+        let labels = metrics_data.labels_with(2, ("encoding", SharedString::const_str(encoding)));
+        metrics::histogram!(name, &labels).record(size);
+    } else {
+        metrics::histogram!(name, &*metrics_data.labels).record(size);
+    }
+}
+
+fn body_bytes_counter(metrics_data: &MetricsData, bytes: u64) {
+    // Only labeled with the endpoint, which is all a throughput dashboard needs.
+    let labels = [metrics_data.labels[1].clone()];
+    metrics::counter!(metrics_data.names.response_body_bytes_total(), &labels).increment(bytes);
+}
+
+fn response_chunks_histogram(metrics_data: &MetricsData) {
+    metrics::histogram!(metrics_data.names.response_chunks(), &*metrics_data.labels)
+        .record(metrics_data.chunks as f64);
+}
+
+fn stream_duration_histogram(metrics_data: &MetricsData, body_start: Instant) {
+    metrics::histogram!(metrics_data.names.stream_duration(), &*metrics_data.labels)
+        .record(body_start.elapsed().as_secs_f64());
 }
 
-impl<'a, FailureClass> Callbacks<FailureClass> for Traffic<'a> {
+impl<'a, FailureClass: 'static> Callbacks<FailureClass> for Traffic<'a> {
     type Data = DefaultCallbackData;
 
     fn prepare<B>(&mut self, request: &http::Request<B>) -> Self::Data {
         let now = std::time::Instant::now();
-        let exact_endpoint = request.uri().path();
-        if self.ignores(exact_endpoint) {
+        let original_path = request
+            .extensions()
+            .get::<OriginalPath>()
+            .filter(|_| self.original_path);
+        let exact_endpoint = match original_path {
+            Some(OriginalPath(path)) => path.as_str(),
+            None => request.uri().path(),
+        };
+        if !self.recording.is_enabled() || self.ignores(exact_endpoint) {
+            return None;
+        }
+        if self.warmup_until.is_some_and(|until| now < until) {
+            return None;
+        }
+        if self
+            .sampled
+            .is_some_and(|sampled| !sampled(&utils::request_parts(request)))
+        {
             return None;
         }
         let endpoint = match self.endpoint_label {
             EndpointLabel::Exact => Cow::from(exact_endpoint),
-            EndpointLabel::MatchedPath => Cow::from(
+            EndpointLabel::OriginalUri => Cow::from(
+                request
+                    .extensions()
+                    .get::<OriginalUri>()
+                    .map_or(exact_endpoint, |uri| uri.path()),
+            ),
+            EndpointLabel::MatchedPath | EndpointLabel::MatchedPathWithMethod => Cow::from(
                 request
                     .extensions()
                     .get::<MatchedPath>()
@@ -425,55 +1770,427 @@ impl<'a, FailureClass> Callbacks<FailureClass> for Traffic<'a> {
                     Cow::from(fallback_fn(exact_endpoint))
                 }
             }
+            EndpointLabel::TryDynamic(label_fn) => {
+                Cow::from(label_fn(&utils::request_parts(request))?)
+            }
         };
-        let endpoint = self.apply_group_pattern(&endpoint).to_owned();
+        let endpoint = self.normalize(&endpoint);
+        let group = self.group_of(endpoint);
+        if let (Some(group), true) = (group, self.grouped_total) {
+            let labels: Vec<_> =
+                std::iter::once(("group", SharedString::from_owned(group.to_owned())))
+                    .chain(self.global_labels.iter().cloned())
+                    .collect();
+            counter!(self.names.requests_grouped(), &labels).increment(1);
+        }
+        let endpoint = group.unwrap_or(endpoint);
+        // Looked up before the label is decorated (e.g. with the method or the root label), so the patterns can use the
+        // route syntax.
+        let slo = self
+            .slos
+            .as_ref()
+            .and_then(|slos| slos.at(endpoint).ok())
+            .map(|slo| *slo.value);
+        // The number of path segments, e.g. 2 for `/users/{id}`.
+        let depth = self.depth_label.then(|| {
+            let depth = endpoint.split('/').filter(|s| !s.is_empty()).count();
+            ("depth", SharedString::from_owned(depth.to_string()))
+        });
+        let mut endpoint = match (endpoint, self.root_label) {
+            ("/", Some(root_label)) => root_label,
+            (endpoint, _) => endpoint,
+        }
+        .to_owned();
+        if self.lowercase_endpoints {
+            endpoint.make_ascii_lowercase();
+        }
         let method = utils::as_label(request.method());
+        let mut endpoint = match (self.fallback_endpoint_label, &self.endpoint_label) {
+            (Some(label), _) if request.extensions().get::<MatchedPath>().is_none() => {
+                label.to_owned()
+            }
+            (_, EndpointLabel::MatchedPathWithMethod) => format!("{method} {endpoint}"),
+            _ => endpoint,
+        };
+        if let Some(sanitize) = self.label_sanitizer {
+            if let Cow::Owned(sanitized) = sanitize(&endpoint) {
+                endpoint = sanitized;
+            }
+        }
 
-        let pending = gauge!(
-            utils::requests_pending_name(),
-            &[
-                ("method", method.to_owned()),
-                ("endpoint", endpoint.clone()),
-            ]
-        );
-        pending.increment(1);
+        let endpoint_label = SharedString::from_shared(endpoint.as_str().into());
+        let operation = self.operation_names.as_ref().map(|names| {
+            let name = request
+                .extensions()
+                .get::<MatchedPath>()
+                .and_then(|path| names.get(path.as_str()));
+            ("operation", name.unwrap_or(&endpoint_label).clone())
+        });
+        let labels: Arc<[_]> = [
+            (self.label_keys.method, SharedString::const_str(method)),
+            (self.label_keys.endpoint, endpoint_label.clone()),
+        ]
+        .into_iter()
+        .chain(operation)
+        .chain(depth)
+        .chain(self.header_labels.iter().map(|(header, key)| {
+            let value = utils::header_label(request.headers().get(*header));
+            (*key, SharedString::from_owned(value))
+        }))
+        .chain(self.query_key_label.then(|| {
+            let keys = utils::query_keys_label(request.uri().query());
+            ("query_keys", SharedString::from_owned(keys))
+        }))
+        .chain(self.global_labels.iter().cloned())
+        .collect();
+        if self.inter_arrival {
+            if let Some(previous) = self.last_seen.swap(method, endpoint_label, now) {
+                histogram!(self.names.inter_arrival(), &*labels)
+                    .record(now.duration_since(previous).as_secs_f64());
+            }
+        }
+        if self.request_header_size {
+            let size: usize = request
+                .headers()
+                .iter()
+                .map(|(name, value)| name.as_str().len() + value.len())
+                .sum();
+            histogram!(self.names.request_headers(), &*labels).record(size as f64);
+        }
+        let client_subnet = match self.client_ip_label {
+            IpLabelMode::None => None,
+            IpLabelMode::Subnet(prefix_len) => {
+                let ip = self
+                    .client_ip_header
+                    .and_then(|header| utils::forwarded_ip(request.headers().get(header)?))
+                    .or_else(|| {
+                        let ConnectInfo(addr) =
+                            request.extensions().get::<ConnectInfo<SocketAddr>>()?;
+                        Some(addr.ip())
+                    });
+                let subnet = ip.map_or_else(
+                    || "unknown".to_owned(),
+                    |ip| utils::subnet_label(ip, prefix_len),
+                );
+                Some(SharedString::from_owned(subnet))
+            }
+        };
+        let tls = self.tls_label.as_ref().map(|extract| {
+            let tls = extract(request.extensions()).unwrap_or_else(|| "none".to_owned());
+            SharedString::from_owned(tls)
+        });
+        // The gauge is decremented (or the finished counter incremented) through the same handle once the request is
+        // done, so both sides always use the same labels.
+        let pending_labels = if self.pending_without_method {
+            &labels[1..]
+        } else {
+            &labels[..]
+        };
+        let pending = if self.pending_as_counters {
+            counter!(self.names.requests_started(), pending_labels).increment(1);
+            InFlight::Finished(counter!(self.names.requests_finished(), pending_labels))
+        } else {
+            let pending = gauge!(self.names.requests_pending(), pending_labels);
+            pending.increment(1);
+            InFlight::Pending(pending)
+        };
+        let in_flight = self.pending_count.fetch_add(1, Ordering::Relaxed) + 1;
+        if self.concurrency {
+            histogram!(self.names.requests_concurrency(), &self.global_labels)
+                .record(in_flight as f64);
+        }
 
         Some((
             MetricsData {
                 endpoint,
                 start: now,
                 method,
-                body_size: 0.0,
+                body_size: 0,
                 exact_body_size_called: Arc::new(AtomicBool::new(false)),
+                record_body_size: self.records_body_size(exact_endpoint),
+                skip_body_size_histogram: false,
+                record_chunks: self.response_chunks,
+                chunks: 0,
+                labels,
+                names: self.names.clone(),
+                phase_timer: self.phase_timing.then(|| PhaseTimer::new(now)),
+                duration_override: self.duration_override.then(DurationOverride::default),
+                slo,
+                limiter_wait: None,
+                body_start: None,
+                trailer_status: self.trailer_status.clone(),
+                encoding: None,
+                tls,
+                retry: self.retry_label.then(|| {
+                    request
+                        .extensions()
+                        .get::<RetryCount>()
+                        .is_some_and(|RetryCount(count)| *count > 0)
+                }),
+                client_subnet,
+                has_body: self
+                    .has_body_label
+                    .then(|| utils::has_body(request.headers())),
             },
-            Arc::new(Pending(pending)),
+            Arc::new(Pending(pending, Arc::clone(&self.pending_count))),
         ))
     }
 
+    fn on_ready_wait(&mut self, waited: Duration, data: &mut Self::Data) {
+        if let Some((data, _)) = data.as_mut().filter(|_| self.limiter_wait) {
+            data.limiter_wait = Some(waited.as_secs_f64());
+        }
+    }
+
+    fn on_request<B>(&mut self, request: &mut http::Request<B>, data: &mut Self::Data) {
+        if let Some(timer) = data.as_ref().and_then(|(data, _)| data.phase_timer.clone()) {
+            request.extensions_mut().insert(timer);
+        }
+        if let Some(duration_override) = data
+            .as_ref()
+            .and_then(|(data, _)| data.duration_override.clone())
+        {
+            request.extensions_mut().insert(duration_override);
+        }
+    }
+
+    fn on_cancel(&mut self, data: &mut Self::Data) {
+        if let Some((data, _pending_guard)) = data {
+            counter!(data.names.requests_cancelled(), &*data.labels).increment(1);
+        }
+    }
+
+    fn on_failure(
+        self,
+        _failed_at: lifecycle::FailedAt,
+        failure_classification: FailureClass,
+        data: &mut Self::Data,
+    ) {
+        if !self.failure_reasons {
+            return;
+        }
+        if let Some((data, _pending_guard)) = data {
+            let reason = utils::failure_reason(&failure_classification);
+            // The reason goes right after the method and endpoint.
+            let labels = data.labels_with(2, ("reason", SharedString::const_str(reason)));
+            counter!(data.names.requests_failed(), &labels).increment(1);
+        }
+    }
+
     fn on_response<B>(
         &mut self,
         res: &http::Response<B>,
-        _cls: ClassifiedResponse<FailureClass, ()>,
+        cls: ClassifiedResponse<FailureClass, ()>,
         data: &mut Self::Data,
     ) {
         if let Some((data, _pending_guard)) = data {
-            let duration_seconds = data.start.elapsed().as_secs_f64();
+            let status = res.status().as_u16();
+            if self
+                .ignore_status_range
+                .as_ref()
+                .is_some_and(|range| range.contains(&status))
+            {
+                // Don't record anything for the response body (or its trailers) either.
+                data.record_body_size = false;
+                data.record_chunks = false;
+                data.trailer_status = None;
+                return;
+            }
+            for (_, name) in self.status_counters.iter().filter(|(s, _)| *s == status) {
+                counter!(name.clone(), &*data.labels).increment(1);
+            }
+            let mut duration = data
+                .duration_override
+                .as_ref()
+                .and_then(DurationOverride::get)
+                .unwrap_or_else(|| data.start.elapsed());
+            if let Some(max) = self.max_duration {
+                duration = duration.min(max);
+            }
+            let duration_seconds = duration.as_secs_f64();
+            if let Some(slo) = data.slo {
+                if duration > slo {
+                    let labels = [data.labels[1].clone()];
+                    counter!(data.names.slo_violations(), &labels).increment(1);
+                }
+            }
+            if self.stream_duration {
+                data.body_start = Some(Instant::now());
+            }
+            if self.content_encoding_label {
+                let encoding = res
+                    .headers()
+                    .get(http::header::CONTENT_ENCODING)
+                    .and_then(|value| value.to_str().ok());
+                data.encoding = Some(utils::encoding_label(encoding));
+            }
+
+            // The status goes right after the method.
+            let mut labels = data.labels_with(
+                1,
+                (
+                    self.label_keys.status,
+                    SharedString::const_str(utils::status_label(status)),
+                ),
+            );
+            // The content type goes right after the status.
+            let content_type = self.content_type_label.then(|| {
+                let content_type = res
+                    .headers()
+                    .get(http::header::CONTENT_TYPE)
+                    .and_then(|ct| ct.to_str().ok());
+                (
+                    "content_type",
+                    SharedString::const_str(utils::content_type_label(content_type)),
+                )
+            });
+            if let Some(content_type) = content_type.clone() {
+                labels.insert(2, content_type);
+            }
+
+            // Streaming responses are classified at the end of the stream, so they're treated as successful here.
+            let is_failure = matches!(cls, ClassifiedResponse::Ready(Err(_)));
+            data.skip_body_size_histogram = self.body_size_success_only && is_failure;
+            let error_code = self
+                .error_code
+                .as_ref()
+                .filter(|_| is_failure)
+                .map(|source| {
+                    let code = (source.0)(res.headers(), res.extensions());
+                    SharedString::from_owned(code.unwrap_or_else(|| "none".to_owned()))
+                });
+            if !self.excluded_statuses.contains(&status) {
+                if self.cache_header.is_some()
+                    || data.tls.is_some()
+                    || error_code.is_some()
+                    || data.retry.is_some()
+                    || data.client_subnet.is_some()
+                    || self.retry_after_label
+                {
+                    // The cache, TLS, error code, retry, client subnet and retry-after labels are only attached to the
+                    // counter, after the status and the content type.
+                    let mut labels = labels.clone();
+                    let mut index = 2 + usize::from(content_type.is_some());
+                    if let Some(cache_header) = self.cache_header {
+                        let cache = res
+                            .headers()
+                            .get(cache_header)
+                            .and_then(|value| value.to_str().ok());
+                        labels.insert(
+                            index,
+                            ("cache", SharedString::const_str(utils::cache_label(cache))),
+                        );
+                        index += 1;
+                    }
+                    if let Some(tls) = &data.tls {
+                        labels.insert(index, ("tls", tls.clone()));
+                        index += 1;
+                    }
+                    if let Some(error_code) = error_code {
+                        labels.insert(index, ("error_code", error_code));
+                        index += 1;
+                    }
+                    if let Some(retry) = data.retry {
+                        let retry = if retry { "true" } else { "false" };
+                        labels.insert(index, ("retry", SharedString::const_str(retry)));
+                        index += 1;
+                    }
+                    if let Some(subnet) = &data.client_subnet {
+                        labels.insert(index, ("client_subnet", subnet.clone()));
+                        index += 1;
+                    }
+                    if self.retry_after_label {
+                        let retry_after = res.headers().contains_key(http::header::RETRY_AFTER);
+                        let retry_after = if retry_after { "true" } else { "false" };
+                        labels.insert(index, ("retry_after", SharedString::const_str(retry_after)));
+                    }
+                    counter!(data.names.requests_total(), &labels).increment(1);
+                } else {
+                    counter!(data.names.requests_total(), &labels).increment(1);
+                }
+            }
+            if !(self.duration_only_for_success && is_failure) {
+                // The body label is only attached to the duration, after the status and the content type.
+                let has_body = data.has_body.map(|has_body| {
+                    let has_body = if has_body { "true" } else { "false" };
+                    ("has_body", SharedString::const_str(has_body))
+                });
+                if !self.histogram_without_status {
+                    if let Some(has_body) = has_body {
+                        let mut labels = labels.clone();
+                        labels.insert(2 + usize::from(content_type.is_some()), has_body);
+                        histogram!(data.names.requests_duration(), &labels)
+                            .record(duration_seconds);
+                    } else {
+                        histogram!(data.names.requests_duration(), &labels)
+                            .record(duration_seconds);
+                    }
+                } else if let Some(content_type) = content_type {
+                    let mut labels = data.labels_with(1, content_type);
+                    if let Some(has_body) = has_body {
+                        labels.insert(2, has_body);
+                    }
+                    histogram!(data.names.requests_duration(), &labels).record(duration_seconds);
+                } else if let Some(has_body) = has_body {
+                    let labels = data.labels_with(1, has_body);
+                    histogram!(data.names.requests_duration(), &labels).record(duration_seconds);
+                } else {
+                    histogram!(data.names.requests_duration(), &*data.labels)
+                        .record(duration_seconds);
+                }
+                if self.method_histogram {
+                    let labels: Vec<_> = std::iter::once((
+                        self.label_keys.method,
+                        SharedString::const_str(data.method),
+                    ))
+                    .chain(self.global_labels.iter().cloned())
+                    .collect();
+                    histogram!(data.names.method_duration(), &labels).record(duration_seconds);
+                }
+                if let Some(callback) = &self.on_duration {
+                    callback(duration_seconds, data, res.status());
+                }
+            }
+
+            if self.unlabeled_total {
+                counter!(data.names.requests_total_unlabeled(), &self.global_labels).increment(1);
+            }
 
-            let labels = [
-                ("method", data.method.to_string()),
-                ("status", res.status().as_u16().to_string()),
-                ("endpoint", data.endpoint.to_string()),
-            ];
+            if let Some(timer) = &data.phase_timer {
+                for (phase, elapsed) in timer.take_phases() {
+                    // The phase goes right after the method and endpoint.
+                    let labels = data.labels_with(2, ("phase", phase.into()));
+                    histogram!(data.names.requests_phase(), &labels).record(elapsed);
+                }
+            }
+            if let Some(waited) = data.limiter_wait {
+                let labels =
+                    data.labels_with(2, ("phase", SharedString::const_str("limiter_wait")));
+                histogram!(data.names.requests_phase(), &labels).record(waited);
+            }
 
-            let requests_total = PREFIXED_HTTP_REQUESTS_TOTAL
-                .get()
-                .map_or(AXUM_HTTP_REQUESTS_TOTAL, |s| s.as_str());
-            counter!(requests_total, &labels).increment(1);
+            // Trailers-only responses (e.g. gRPC errors) carry the status in the headers instead.
+            if let Some(status) = data
+                .trailer_status
+                .as_ref()
+                .and_then(|name| res.headers().get(name))
+            {
+                data.record_trailer_status(status);
+            }
 
-            let requests_duration = PREFIXED_HTTP_REQUESTS_DURATION_SECONDS
-                .get()
-                .map_or(AXUM_HTTP_REQUESTS_DURATION_SECONDS, |s| s.as_str());
-            histogram!(requests_duration, &labels).record(duration_seconds);
+            if let Some(callback) = &self.on_request_complete {
+                callback(&RequestSummary {
+                    method: data.method,
+                    endpoint: &data.endpoint,
+                    status,
+                    duration,
+                    body_size: res
+                        .headers()
+                        .get(http::header::CONTENT_LENGTH)
+                        .and_then(|cl| cl.to_str().ok())
+                        .and_then(|cl| cl.parse().ok()),
+                });
+            }
         }
     }
 }
@@ -536,8 +2253,7 @@ impl<'a> BaseMetricLayer<'a> {
     /// }
     /// ```
     pub fn new() -> Self {
-        let make_classifier =
-            StatusInRangeAsFailures::new_for_client_and_server_errors().into_make_classifier();
+        let make_classifier = default_classifier();
         let inner_layer = LifeCycleLayer::new(make_classifier, Traffic::new(), None);
         Self { inner_layer }
     }
@@ -545,9 +2261,23 @@ impl<'a> BaseMetricLayer<'a> {
     /// Construct a new `BaseMetricLayer` with response body size tracking enabled.
     pub fn with_response_body_size() -> Self {
         let mut this = Self::new();
+        this.inner_layer.callbacks_mut().with_body_size(true);
         this.inner_layer.on_body_chunk(Some(BodySizeRecorder));
         this
     }
+
+    /// The number of requests currently in-flight through this layer (and all of its clones).
+    ///
+    /// Ignored requests are not counted. See [`GenericMetricLayer::pending_count`] for more details.
+    pub fn pending_count(&self) -> u64 {
+        self.inner_layer.callbacks().pending_count()
+    }
+
+    /// A handle to turn recording on and off at runtime for this layer (and all of its clones).
+    /// See [`GenericMetricLayer::recording_switch`] for more details.
+    pub fn recording_switch(&self) -> RecordingSwitch {
+        self.inner_layer.callbacks().recording_switch()
+    }
 }
 
 impl<'a> Default for BaseMetricLayer<'a> {
@@ -576,6 +2306,7 @@ pub struct GenericMetricLayer<'a, T, M> {
         Traffic<'a>,
         Option<BodySizeRecorder>,
     >,
+    handle: Option<Arc<T>>,
     _marker: PhantomData<(T, M)>,
 }
 
@@ -584,11 +2315,59 @@ impl<'a, T, M> std::clone::Clone for GenericMetricLayer<'a, T, M> {
     fn clone(&self) -> Self {
         GenericMetricLayer {
             inner_layer: self.inner_layer.clone(),
+            handle: self.handle.clone(),
             _marker: self._marker,
         }
     }
 }
 
+impl<'a, T, M> GenericMetricLayer<'a, T, M> {
+    /// The number of requests currently in-flight through this layer (and all of its clones).
+    ///
+    /// This is the same value reported by the pending requests gauge (summed over all labels), but it can be read
+    /// synchronously and cheaply, for example to implement custom load-shedding. Ignored requests are not counted.
+    ///
+    /// A request is considered in-flight until its response body has been dropped.
+    pub fn pending_count(&self) -> u64 {
+        self.inner_layer.callbacks().pending_count()
+    }
+
+    /// A handle to turn recording on and off at runtime, e.g. to stop emitting metrics during an incident (like a
+    /// cardinality explosion) without redeploying.
+    ///
+    /// The switch is shared by this layer and all of its clones, so disabling it stops recording for all of them.
+    /// While disabled, requests are treated as if they were ignored.
+    ///
+    /// ```rust
+    /// use axum_prometheus::PrometheusMetricLayerBuilder;
+    ///
+    /// let metric_layer = PrometheusMetricLayerBuilder::new().build();
+    /// let switch = metric_layer.recording_switch();
+    /// switch.disable();
+    /// assert!(!metric_layer.recording_switch().is_enabled());
+    /// ```
+    pub fn recording_switch(&self) -> RecordingSwitch {
+        self.inner_layer.callbacks().recording_switch()
+    }
+
+    /// The exporter handle this layer was created with, so it doesn't need to be passed around separately, e.g. to
+    /// mount the `/metrics` endpoint in a different place than where the layer is registered.
+    ///
+    /// This is only available for layers (and their clones) created along with their handle, i.e. with the `pair*`
+    /// constructors or [`MetricLayerBuilder::build_pair`], and for exporters that share their handle (see
+    /// [`MakeDefaultHandle::share_handle`]), like the default Prometheus exporter.
+    ///
+    /// ```rust,no_run
+    /// use axum_prometheus::PrometheusMetricLayer;
+    ///
+    /// let (metric_layer, _) = PrometheusMetricLayer::pair();
+    /// let metric_handle = metric_layer.handle().unwrap().clone();
+    /// ```
+    pub fn handle(&self) -> Option<&T> {
+        self.handle.as_deref()
+    }
+}
+
 impl<'a, T, M> GenericMetricLayer<'a, T, M>
 where
     M: MakeDefaultHandle<Out = T>,
@@ -648,31 +2427,46 @@ where
     /// }
     /// ```
     pub fn new() -> Self {
-        let make_classifier =
-            StatusInRangeAsFailures::new_for_client_and_server_errors().into_make_classifier();
+        let make_classifier = default_classifier();
         let inner_layer = LifeCycleLayer::new(make_classifier, Traffic::new(), None);
         Self {
             inner_layer,
+            handle: None,
             _marker: PhantomData,
         }
     }
 
-    pub(crate) fn from_builder(builder: MetricLayerBuilder<'a, T, M, LayerOnly>) -> Self {
-        let make_classifier =
-            StatusInRangeAsFailures::new_for_client_and_server_errors().into_make_classifier();
-        let inner_layer = if builder.enable_body_size {
+    pub(crate) fn from_builder<S: MetricBuilderState>(
+        mut builder: MetricLayerBuilder<'a, T, M, S>,
+    ) -> Self {
+        let make_classifier = match builder.failure_status_range {
+            Some(range) => StatusInRangeAsFailures::new(range),
+            None => StatusInRangeAsFailures::new_for_client_and_server_errors(),
+        }
+        .into_make_classifier();
+        for (endpoint, methods) in builder.preregistered_endpoints {
+            for method in *methods {
+                builder.traffic.preregister(endpoint, method);
+            }
+        }
+        if let Some(warmup) = builder.warmup {
+            builder.traffic.with_warmup_until(Instant::now() + warmup);
+        }
+        let inner_layer = if builder.traffic.observes_body() {
             LifeCycleLayer::new(make_classifier, builder.traffic, Some(BodySizeRecorder))
         } else {
             LifeCycleLayer::new(make_classifier, builder.traffic, None)
         };
         Self {
             inner_layer,
+            handle: None,
             _marker: PhantomData,
         }
     }
 
     /// Enable tracking response body sizes.
     pub fn enable_response_body_size(&mut self) {
+        self.inner_layer.callbacks_mut().with_body_size(true);
         self.inner_layer.on_body_chunk(Some(BodySizeRecorder));
     }
 
@@ -703,7 +2497,62 @@ where
     /// }
     /// ```
     pub fn pair_from(m: M) -> (Self, T) {
-        (Self::new(), M::make_default_handle(m))
+        let handle = M::make_default_handle(m);
+        (Self::new().with_handle(&handle), handle)
+    }
+
+    /// Crate a new tower middleware and a default exporter from the value returned by the passed in closure.
+    ///
+    /// This is the lazy counterpart of [`GenericMetricLayer::pair_from`], and the recommended alternative to
+    /// [`GenericMetricLayer::pair`] for exporters that have no sensible `Default` implementation (e.g. they need a host or port).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use axum_prometheus::{GenericMetricLayer, MakeDefaultHandle};
+    ///
+    /// struct Recorder { host: String, port: u16 }
+    ///
+    /// impl Recorder {
+    ///     fn new(host: &str, port: u16) -> Self {
+    ///         Self { host: host.to_string(), port }
+    ///     }
+    /// }
+    ///
+    /// impl MakeDefaultHandle for Recorder {
+    ///     type Out = ();
+    ///
+    ///     fn make_default_handle(self) -> Self::Out {
+    ///         // Perform the initialization. `self` is passed in by value.
+    ///         todo!();
+    ///     }
+    /// }
+    ///
+    /// fn main() {
+    ///     let (metric_layer, metric_handle) =
+    ///         GenericMetricLayer::pair_with(|| Recorder::new("0.0.0.0", 8125));
+    /// }
+    /// ```
+    pub fn pair_with(f: impl FnOnce() -> M) -> (Self, T) {
+        Self::pair_from(f())
+    }
+
+    pub(crate) fn pair_from_builder(
+        mut builder: MetricLayerBuilder<'a, T, M, Paired>,
+    ) -> (Self, T) {
+        let handle = builder
+            .metric_handle
+            .take()
+            .expect("the metric handle is always attached in the `Paired` state");
+        // The exporter is installed at this point, so the descriptions aren't lost.
+        builder.describe_metrics();
+        builder.validate();
+        (Self::from_builder(builder).with_handle(&handle), handle)
+    }
+
+    fn with_handle(mut self, handle: &T) -> Self {
+        self.handle = M::share_handle(handle).map(Arc::new);
+        self
     }
 }
 
@@ -711,30 +2560,11 @@ impl<'a, T, M> GenericMetricLayer<'a, T, M>
 where
     M: MakeDefaultHandle<Out = T> + Default,
 {
-    pub(crate) fn pair_from_builder(builder: MetricLayerBuilder<'a, T, M, Paired>) -> (Self, T) {
-        let make_classifier =
-            StatusInRangeAsFailures::new_for_client_and_server_errors().into_make_classifier();
-        let inner_layer = if builder.enable_body_size {
-            LifeCycleLayer::new(make_classifier, builder.traffic, Some(BodySizeRecorder))
-        } else {
-            LifeCycleLayer::new(make_classifier, builder.traffic, None)
-        };
-
-        (
-            Self {
-                inner_layer,
-                _marker: PhantomData,
-            },
-            builder
-                .metric_handle
-                .unwrap_or_else(|| M::make_default_handle(M::default())),
-        )
-    }
-
     /// Crate a new tower middleware and a default global Prometheus exporter with sensible defaults.
     ///
     /// If used with a custom exporter that's different from Prometheus, the exporter struct
-    /// must implement `MakeDefaultHandle + Default`.
+    /// must implement `MakeDefaultHandle + Default`. If there's no sensible default for your exporter,
+    /// use [`GenericMetricLayer::pair_from`] or [`GenericMetricLayer::pair_with`] instead.
     ///
     /// # Example
     /// ```
@@ -765,7 +2595,26 @@ where
     /// }
     /// ```
     pub fn pair() -> (Self, T) {
-        (Self::new(), M::make_default_handle(M::default()))
+        Self::pair_from(M::default())
+    }
+}
+
+impl<'a, T, M> GenericMetricLayer<'a, T, M>
+where
+    M: AsyncMakeDefaultHandle<Out = T>,
+{
+    /// Create a new tower middleware and a default exporter that's initialized asynchronously.
+    ///
+    /// This is the counterpart of [`GenericMetricLayer::pair_from`] for exporters implementing
+    /// [`AsyncMakeDefaultHandle`]. See its documentation for an example.
+    pub async fn pair_from_async(m: M) -> (Self, T) {
+        let handle = m.make_default_handle().await;
+        let layer = Self {
+            inner_layer: LifeCycleLayer::new(default_classifier(), Traffic::new(), None),
+            handle: M::share_handle(&handle).map(Arc::new),
+            _marker: PhantomData,
+        };
+        (layer, handle)
     }
 }
 
@@ -825,6 +2674,121 @@ pub trait MakeDefaultHandle {
     /// let (layer, handle) =  GenericMetricLayer::<'_, _, MyHandle>::pair();
     /// ```
     fn make_default_handle(self) -> Self::Out;
+
+    /// Initialize the metric exporter with the options set on the [`MetricLayerBuilder`], like
+    /// [`MetricLayerBuilder::with_exporter_prefix`]. This is what [`MetricLayerBuilder::with_default_metrics`] calls.
+    ///
+    /// Calls [`MakeDefaultHandle::make_default_handle`] by default, ignoring the options. Override it if the
+    /// exporter can make use of them:
+    ///
+    /// ```rust,no_run
+    /// use axum_prometheus::{ExporterOpts, MakeDefaultHandle};
+    ///
+    /// #[derive(Default)]
+    /// struct Recorder;
+    ///
+    /// impl MakeDefaultHandle for Recorder {
+    ///     type Out = ();
+    ///
+    ///     fn make_default_handle(self) -> Self::Out {
+    ///         self.make_default_handle_with_opts(&ExporterOpts::default())
+    ///     }
+    ///
+    ///     fn make_default_handle_with_opts(self, opts: &ExporterOpts) -> Self::Out {
+    ///         let prefix = opts.prefix().unwrap_or("my_app");
+    ///         // Build the exporter with `prefix`, and install it.
+    ///         todo!();
+    ///     }
+    /// }
+    /// ```
+    fn make_default_handle_with_opts(self, opts: &ExporterOpts) -> Self::Out
+    where
+        Self: Sized,
+    {
+        let _ = opts;
+        self.make_default_handle()
+    }
+
+    /// Create the default exporter, and initialize it with the options set on the [`MetricLayerBuilder`]. This is
+    /// what [`MetricLayerBuilder::with_default_metrics`] calls.
+    ///
+    /// Calls [`MakeDefaultHandle::make_default_handle_with_opts`] on `Self::default()` by default. Override it if
+    /// the `Default` implementation already initializes the exporter (like the one of [`Handle`] does), so it can be
+    /// initialized with the options instead.
+    fn default_handle_with_opts(opts: &ExporterOpts) -> Self::Out
+    where
+        Self: Default + Sized,
+    {
+        Self::default().make_default_handle_with_opts(opts)
+    }
+
+    /// A copy of `handle` to keep on the layers created with it, so it can be retrieved later with
+    /// [`GenericMetricLayer::handle`].
+    ///
+    /// Returns `None` by default, so the handle isn't kept. Override it if the handle is cheap to clone, like the
+    /// `PrometheusHandle`.
+    fn share_handle(handle: &Self::Out) -> Option<Self::Out> {
+        let _ = handle;
+        None
+    }
+}
+
+/// Exporter options set on the [`MetricLayerBuilder`], passed to [`MakeDefaultHandle::make_default_handle_with_opts`].
+#[derive(Debug, Clone, Default)]
+pub struct ExporterOpts {
+    pub(crate) prefix: Option<String>,
+    /// The metric names of the layer, so the default exporters can configure their buckets for them.
+    pub(crate) names: MetricNames,
+    /// The quantiles of the default Prometheus exporter, set with
+    /// [`MetricLayerBuilder::with_body_size_as_summary`].
+    pub(crate) quantiles: Option<Vec<f64>>,
+}
+
+impl ExporterOpts {
+    /// The prefix set with [`MetricLayerBuilder::with_exporter_prefix`], if any.
+    pub fn prefix(&self) -> Option<&str> {
+        self.prefix.as_deref()
+    }
+}
+
+/// The asynchronous counterpart of [`MakeDefaultHandle`], for exporters that need to await something during their
+/// initialization, like connecting to a collector. Use it with [`GenericMetricLayer::pair_from_async`].
+///
+/// Implementations can use an `async fn`:
+///
+/// ```rust,no_run
+/// use axum_prometheus::{AsyncMakeDefaultHandle, GenericMetricLayer};
+///
+/// struct Recorder { endpoint: String }
+///
+/// impl AsyncMakeDefaultHandle for Recorder {
+///     type Out = ();
+///
+///     async fn make_default_handle(self) -> Self::Out {
+///         // Connect to `self.endpoint`, then install the recorder.
+///         todo!();
+///     }
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let (metric_layer, metric_handle) = GenericMetricLayer::pair_from_async(
+///         Recorder { endpoint: "http://localhost:4317".to_string() }
+///     ).await;
+/// }
+/// ```
+pub trait AsyncMakeDefaultHandle {
+    /// The type of the metrics handle to return from [`GenericMetricLayer::pair_from_async`].
+    type Out;
+
+    /// Initialize the metric exporter, see [`MakeDefaultHandle::make_default_handle`].
+    fn make_default_handle(self) -> impl Future<Output = Self::Out>;
+
+    /// A copy of `handle` to keep on the layers created with it, see [`MakeDefaultHandle::share_handle`].
+    fn share_handle(handle: &Self::Out) -> Option<Self::Out> {
+        let _ = handle;
+        None
+    }
 }
 
 /// The default handle for the Prometheus exporter.
@@ -835,29 +2799,66 @@ pub struct Handle(pub PrometheusHandle);
 #[cfg(feature = "prometheus")]
 impl Default for Handle {
     fn default() -> Self {
-        let recorder = PrometheusBuilder::new()
-            .set_buckets_for_metric(
-                Matcher::Full(
-                    PREFIXED_HTTP_REQUESTS_DURATION_SECONDS
-                        .get()
-                        .map_or(AXUM_HTTP_REQUESTS_DURATION_SECONDS, |s| s.as_str())
-                        .to_string(),
-                ),
-                utils::SECONDS_DURATION_BUCKETS,
-            )
-            .unwrap()
-            .build_recorder();
-        let handle = recorder.handle();
-        let recorder_handle = handle.clone();
-        tokio::spawn(async move {
-            loop {
-                tokio::time::sleep(Duration::from_secs(5)).await;
-                recorder_handle.run_upkeep();
-            }
-        });
-        metrics::set_global_recorder(recorder).expect("Failed to set global recorder");
-        Self(handle)
+        Self(install_prometheus_recorder(default_prometheus_recorder(
+            &ExporterOpts::default(),
+        )))
+    }
+}
+
+/// Install `recorder` as the global recorder, and return its handle.
+#[cfg(feature = "prometheus")]
+pub(crate) fn install_prometheus_recorder(recorder: PrometheusRecorder) -> PrometheusHandle {
+    let handle = recorder.handle();
+    metrics::set_global_recorder(recorder).expect("Failed to set global recorder");
+    handle
+}
+
+/// Build the Prometheus recorder with the default configuration for the layer `opts` belong to, and spawn its upkeep
+/// task.
+///
+/// The recorder is __not__ installed.
+#[cfg(feature = "prometheus")]
+pub(crate) fn default_prometheus_recorder(opts: &ExporterOpts) -> PrometheusRecorder {
+    prometheus_recorder_with_buckets(opts, utils::SECONDS_DURATION_BUCKETS)
+}
+
+/// Same as [`default_prometheus_recorder`], but with `buckets` for the histograms measured in seconds.
+#[cfg(feature = "prometheus")]
+pub(crate) fn prometheus_recorder_with_buckets(
+    opts: &ExporterOpts,
+    buckets: &[f64],
+) -> PrometheusRecorder {
+    let names = &opts.names;
+    let mut builder = PrometheusBuilder::new();
+    if let Some(quantiles) = &opts.quantiles {
+        builder = builder
+            .set_quantiles(quantiles)
+            .expect("the quantiles are checked by the builder");
     }
+    let seconds_histograms = [
+        names.requests_duration(),
+        names.requests_phase(),
+        names.inter_arrival(),
+        names.stream_duration(),
+        names.method_duration(),
+        names.client_requests_duration(),
+    ];
+    let recorder = seconds_histograms
+        .into_iter()
+        .fold(builder, |builder, name| {
+            builder
+                .set_buckets_for_metric(Matcher::Full(name.into_owned()), buckets)
+                .unwrap()
+        })
+        .build_recorder();
+    let recorder_handle = recorder.handle();
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            recorder_handle.run_upkeep();
+        }
+    });
+    recorder
 }
 
 #[cfg(feature = "prometheus")]
@@ -867,6 +2868,15 @@ impl MakeDefaultHandle for Handle {
     fn make_default_handle(self) -> Self::Out {
         self.0
     }
+
+    fn default_handle_with_opts(opts: &ExporterOpts) -> Self::Out {
+        // `Handle::default` would configure the buckets by the global metric names, not the ones of the layer.
+        install_prometheus_recorder(default_prometheus_recorder(opts))
+    }
+
+    fn share_handle(handle: &Self::Out) -> Option<Self::Out> {
+        Some(handle.clone())
+    }
 }
 
 #[cfg(feature = "prometheus")]
@@ -19,7 +19,39 @@ pub struct ResponseBody<B, C, Callbacks, OnBodyChunk, CallbacksData> {
     pub(super) callbacks_data: CallbacksData,
     pub(super) on_body_chunk: OnBodyChunk,
     pub(super) content_length: Option<HeaderValue>,
+    pub(super) body_ended: bool,
+    // Stored as a function pointer, because `PinnedDrop` can't require `B: Body` nor
+    // `OnBodyChunk: OnBodyChunk<B::Data>`.
+    pub(super) on_drop: fn(&B, &mut OnBodyChunk, &mut CallbacksData),
 }
+
+impl<B, C, Callbacks, OnBodyChunk, CallbacksData> PinnedDrop
+    for ResponseBody<B, C, Callbacks, OnBodyChunk, CallbacksData>
+{
+    fn drop(this: Pin<&mut Self>) {
+        let this = this.project();
+        // `body_ended` is only set where `on_body_end` is called, so the two never both fire.
+        if !*this.body_ended {
+            *this.body_ended = true;
+            (this.on_drop)(&this.inner, this.on_body_chunk, this.callbacks_data);
+        }
+    }
+}
+}
+
+/// The `on_drop` function of a [`ResponseBody`].
+pub(super) fn on_drop<B, OnBodyChunkT>(
+    inner: &B,
+    on_body_chunk: &mut OnBodyChunkT,
+    callbacks_data: &mut OnBodyChunkT::Data,
+) where
+    B: Body,
+    OnBodyChunkT: OnBodyChunk<B::Data>,
+{
+    // Bodies that never had anything to send (e.g. empty ones) may be dropped without being polled at all.
+    if !inner.is_end_stream() {
+        on_body_chunk.on_body_drop(callbacks_data);
+    }
 }
 
 impl<B, C, CallbacksT, OnBodyChunkT, CallbacksData> Body
@@ -39,7 +71,7 @@ where
         self: Pin<&mut Self>,
         cx: &mut Context<'_>,
     ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
-        let this = self.project();
+        let mut this = self.project();
 
         let body_size = this.inner.size_hint().exact().or_else(|| {
             this.content_length
@@ -47,7 +79,7 @@ where
                 .and_then(|cl| cl.to_str().ok())
                 .and_then(|cl| cl.parse().ok())
         });
-        let result = ready!(this.inner.poll_frame(cx));
+        let result = ready!(this.inner.as_mut().poll_frame(cx));
 
         match result {
             Some(Ok(frame)) => {
@@ -62,6 +94,8 @@ where
 
                 let frame = match frame.into_trailers() {
                     Ok(trailers) => {
+                        this.on_body_chunk
+                            .on_trailers(&trailers, this.callbacks_data);
                         if let Some((classify_eos, callbacks)) = this.parts.take() {
                             let classification = classify_eos.classify_eos(Some(&trailers));
                             callbacks.on_eos(
@@ -75,6 +109,11 @@ where
                     Err(frame) => frame,
                 };
 
+                if this.inner.is_end_stream() && !*this.body_ended {
+                    *this.body_ended = true;
+                    this.on_body_chunk.on_body_end(this.callbacks_data);
+                }
+
                 Poll::Ready(Some(Ok(frame)))
             }
             Some(Err(err)) => {
@@ -86,6 +125,10 @@ where
                 Poll::Ready(Some(Err(err)))
             }
             None => {
+                if !*this.body_ended {
+                    *this.body_ended = true;
+                    this.on_body_chunk.on_body_end(this.callbacks_data);
+                }
                 if let Some((classify_eos, callbacks)) = this.parts.take() {
                     let classification = classify_eos.classify_eos(None);
                     callbacks.on_eos(None, classification, this.callbacks_data.clone());
@@ -9,7 +9,10 @@ use std::{
 };
 use tower_http::classify::{ClassifiedResponse, ClassifyResponse};
 
-use super::{body::ResponseBody, Callbacks, FailedAt, OnBodyChunk};
+use super::{
+    body::{self, ResponseBody},
+    Callbacks, FailedAt, OnBodyChunk,
+};
 
 pin_project! {
 pub struct ResponseFuture<F, C, Callbacks, OnBodyChunk, CallbackData> {
@@ -19,7 +22,25 @@ pub struct ResponseFuture<F, C, Callbacks, OnBodyChunk, CallbackData> {
     pub(super) callbacks: Option<Callbacks>,
     pub(super) on_body_chunk: Option<OnBodyChunk>,
     pub(super) callbacks_data: Option<CallbackData>,
-}}
+    // Stored as a function pointer, because `PinnedDrop` can't require `Callbacks: Callbacks<FailureClass>`.
+    pub(super) on_cancel: fn(&mut Callbacks, &mut CallbackData),
+}
+
+impl<F, C, Callbacks, OnBodyChunk, CallbackData> PinnedDrop
+    for ResponseFuture<F, C, Callbacks, OnBodyChunk, CallbackData>
+{
+    fn drop(this: Pin<&mut Self>) {
+        let this = this.project();
+        // The callbacks are only taken once the inner future completes, so if they're still here,
+        // the future is dropped before producing a response.
+        if let (Some(callbacks), Some(callbacks_data)) =
+            (this.callbacks.as_mut(), this.callbacks_data.as_mut())
+        {
+            (this.on_cancel)(callbacks, callbacks_data);
+        }
+    }
+}
+}
 
 impl<F, C, CallbacksData, ResBody, E, CallbacksT, OnBodyChunkT> Future
     for ResponseFuture<F, C, CallbacksT, OnBodyChunkT, CallbacksData>
@@ -75,6 +96,8 @@ where
                             on_body_chunk,
                             callbacks_data: callbacks_data.clone(),
                             content_length,
+                            body_ended: false,
+                            on_drop: body::on_drop::<ResBody, OnBodyChunkT>,
                         });
                         Poll::Ready(Ok(res))
                     }
@@ -90,6 +113,8 @@ where
                             on_body_chunk,
                             parts: Some((classify_eos, callbacks)),
                             content_length,
+                            body_ended: false,
+                            on_drop: body::on_drop::<ResBody, OnBodyChunkT>,
                         });
                         Poll::Ready(Ok(res))
                     }
@@ -27,6 +27,14 @@ impl<MC, Callbacks, OnBodyChunk> LifeCycleLayer<MC, Callbacks, OnBodyChunk> {
     pub(crate) fn on_body_chunk(&mut self, on_body_chunk: OnBodyChunk) {
         self.on_body_chunk = on_body_chunk;
     }
+
+    pub(crate) fn callbacks(&self) -> &Callbacks {
+        &self.callbacks
+    }
+
+    pub(crate) fn callbacks_mut(&mut self) -> &mut Callbacks {
+        &mut self.callbacks
+    }
 }
 
 impl<S, MC, Callbacks, OnBodyChunk> Layer<S> for LifeCycleLayer<MC, Callbacks, OnBodyChunk>
@@ -38,11 +46,11 @@ where
     type Service = LifeCycle<S, MC, Callbacks, OnBodyChunk>;
 
     fn layer(&self, inner: S) -> Self::Service {
-        LifeCycle {
+        LifeCycle::new(
             inner,
-            make_classifier: self.make_classifier.clone(),
-            callbacks: self.callbacks.clone(),
-            on_body_chunk: self.on_body_chunk.clone(),
-        }
+            self.make_classifier.clone(),
+            self.callbacks.clone(),
+            self.on_body_chunk.clone(),
+        )
     }
 }
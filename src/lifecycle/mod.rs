@@ -3,6 +3,8 @@
 //!
 //! `axum-prometheus` is built on top of lifecycle hooks. Using this module allows you to customize
 //! behavior even more.
+use std::time::Duration;
+
 use bytes::Buf;
 use http::{HeaderMap, Request, Response};
 use tower_http::classify::ClassifiedResponse;
@@ -28,6 +30,37 @@ pub trait Callbacks<FailureClass>: Sized {
     /// [`Service::call`]: tower::Service::call
     fn prepare<B>(&mut self, request: &Request<B>) -> Self::Data;
 
+    /// Perform some action on the request before it's passed to the inner service, for example inserting
+    /// extensions that handlers can extract.
+    ///
+    /// This method is called right after [`Callbacks::prepare`], with the data it returned.
+    ///
+    /// The default implementation does nothing and returns immediately.
+    #[inline]
+    fn on_request<B>(&mut self, _request: &mut Request<B>, _data: &mut Self::Data) {}
+
+    /// Perform some action with the time the request waited for the inner service to become ready, measured from the
+    /// first [`Service::poll_ready`] call until [`Service::call`]. Services like `tower::limit::ConcurrencyLimit` make
+    /// requests wait here until they're allowed through.
+    ///
+    /// This method is called right after [`Callbacks::prepare`] (before [`Callbacks::on_request`]), with the data it
+    /// returned. It's not called if
+    /// [`Service::call`] wasn't preceded by [`Service::poll_ready`].
+    ///
+    /// The default implementation does nothing and returns immediately.
+    ///
+    /// [`Service::poll_ready`]: tower::Service::poll_ready
+    /// [`Service::call`]: tower::Service::call
+    #[inline]
+    fn on_ready_wait(&mut self, _waited: Duration, _data: &mut Self::Data) {}
+
+    /// Perform some action when the response future is dropped before the inner service produced a response,
+    /// for example because a timeout layer above cancelled the request, or the client disconnected.
+    ///
+    /// The default implementation does nothing and returns immediately.
+    #[inline]
+    fn on_cancel(&mut self, _data: &mut Self::Data) {}
+
     /// Perform some action when a response has been generated.
     ///
     /// This method is called when the inner [`Service`]'s response future
@@ -126,6 +159,43 @@ pub trait OnBodyChunk<B: Buf> {
     /// [`Frame::into_data`]: http_body::Frame::into_data
     #[inline]
     fn call(&mut self, _body: &B, _exact_body_size: Option<u64>, _data: &mut Self::Data) {}
+
+    /// Perform some action when the response body has been fully streamed.
+    ///
+    /// This is called at most once per response body: either when [`Body::poll_frame`] returns `None`, or
+    /// when the inner body reports [`Body::is_end_stream`] right after producing a frame.
+    ///
+    /// The default implementation does nothing and returns immediately.
+    ///
+    /// [`Body::poll_frame`]: http_body::Body::poll_frame
+    /// [`Body::is_end_stream`]: http_body::Body::is_end_stream
+    #[inline]
+    fn on_body_end(&mut self, _data: &mut Self::Data) {}
+
+    /// Perform some action when the response body is dropped before it has been fully streamed, for example because
+    /// the client disconnected in the middle of a streaming response.
+    ///
+    /// This is called at most once per response body, and never if [`OnBodyChunk::on_body_end`] has been called.
+    /// Bodies that are already at their end (see [`Body::is_end_stream`]) when dropped are considered fully streamed,
+    /// even if they were never polled.
+    ///
+    /// The default implementation does nothing and returns immediately.
+    ///
+    /// [`Body::is_end_stream`]: http_body::Body::is_end_stream
+    #[inline]
+    fn on_body_drop(&mut self, _data: &mut Self::Data) {}
+
+    /// Perform some action when the response body produced trailers.
+    ///
+    /// This is called when [`Body::poll_frame`] returns `Some(Ok(frame))`, and [`Frame::into_trailers`] returns
+    /// with `Ok(trailers)`, regardless of how the response was classified.
+    ///
+    /// The default implementation does nothing and returns immediately.
+    ///
+    /// [`Body::poll_frame`]: http_body::Body::poll_frame
+    /// [`Frame::into_trailers`]: http_body::Frame::into_trailers
+    #[inline]
+    fn on_trailers(&mut self, _trailers: &HeaderMap, _data: &mut Self::Data) {}
 }
 
 /// Enum used to specify where an error was encountered.
@@ -1,4 +1,8 @@
-use std::task::{Context, Poll};
+use std::{
+    fmt,
+    task::{Context, Poll},
+    time::Instant,
+};
 
 use http::{Request, Response};
 use http_body::Body;
@@ -9,12 +13,40 @@ use super::{
     body::ResponseBody, future::ResponseFuture, layer::LifeCycleLayer, Callbacks, OnBodyChunk,
 };
 
-#[derive(Clone, Debug)]
 pub struct LifeCycle<S, MC, Callbacks, OnBodyChunk> {
     pub(super) inner: S,
     pub(super) make_classifier: MC,
     pub(super) callbacks: Callbacks,
     pub(super) on_body_chunk: OnBodyChunk,
+    // When the service was first polled for readiness since the last `call`.
+    pub(super) ready_wait_start: Option<Instant>,
+}
+
+impl<S: Clone, MC: Clone, Callbacks: Clone, OnBodyChunk: Clone> Clone
+    for LifeCycle<S, MC, Callbacks, OnBodyChunk>
+{
+    fn clone(&self) -> Self {
+        // A clone has to be polled for readiness on its own, so it doesn't inherit the pending wait.
+        Self::new(
+            self.inner.clone(),
+            self.make_classifier.clone(),
+            self.callbacks.clone(),
+            self.on_body_chunk.clone(),
+        )
+    }
+}
+
+impl<S: fmt::Debug, MC: fmt::Debug, Callbacks: fmt::Debug, OnBodyChunk: fmt::Debug> fmt::Debug
+    for LifeCycle<S, MC, Callbacks, OnBodyChunk>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LifeCycle")
+            .field("inner", &self.inner)
+            .field("make_classifier", &self.make_classifier)
+            .field("callbacks", &self.callbacks)
+            .field("on_body_chunk", &self.on_body_chunk)
+            .finish_non_exhaustive()
+    }
 }
 
 impl<S, MC, Callbacks, OnBodyChunk> LifeCycle<S, MC, Callbacks, OnBodyChunk> {
@@ -29,6 +61,7 @@ impl<S, MC, Callbacks, OnBodyChunk> LifeCycle<S, MC, Callbacks, OnBodyChunk> {
             make_classifier,
             callbacks,
             on_body_chunk,
+            ready_wait_start: None,
         }
     }
 
@@ -75,11 +108,17 @@ where
         ResponseFuture<S::Future, MC::Classifier, CallbacksT, OnBodyChunkT, CallbacksT::Data>;
 
     fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.ready_wait_start.get_or_insert_with(Instant::now);
         self.inner.poll_ready(cx)
     }
 
-    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
-        let callbacks_data = self.callbacks.prepare(&req);
+    fn call(&mut self, mut req: Request<ReqBody>) -> Self::Future {
+        let mut callbacks_data = self.callbacks.prepare(&req);
+        if let Some(start) = self.ready_wait_start.take() {
+            self.callbacks
+                .on_ready_wait(start.elapsed(), &mut callbacks_data);
+        }
+        self.callbacks.on_request(&mut req, &mut callbacks_data);
 
         let classifier = self.make_classifier.make_classifier(&req);
 
@@ -89,6 +128,7 @@ where
             callbacks: Some(self.callbacks.clone()),
             callbacks_data: Some(callbacks_data),
             on_body_chunk: Some(self.on_body_chunk.clone()),
+            on_cancel: <CallbacksT as Callbacks<MC::FailureClass>>::on_cancel,
         }
     }
 }
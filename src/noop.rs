@@ -0,0 +1,117 @@
+use std::marker::PhantomData;
+
+#[cfg(feature = "prometheus")]
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+use tower::Layer;
+
+use crate::{MakeDefaultHandle, RecordingSwitch};
+
+/// A layer with the same API as [`GenericMetricLayer`] that doesn't record anything, and passes requests through
+/// to the inner service untouched.
+///
+/// This is meant to compile the metrics out of some builds without changing the call sites, e.g. with a feature of
+/// your own crate:
+/// ```rust
+/// #[cfg(not(feature = "metrics"))]
+/// use axum_prometheus::NoopPrometheusMetricLayer as PrometheusMetricLayer;
+/// #[cfg(feature = "metrics")]
+/// use axum_prometheus::PrometheusMetricLayer;
+///
+/// let (metric_layer, metric_handle) = PrometheusMetricLayer::pair();
+/// let app = axum::Router::<()>::new().layer(metric_layer);
+/// ```
+/// The builder is not mirrored, so only the constructors of [`GenericMetricLayer`] can be swapped this way.
+///
+/// [`GenericMetricLayer`]: crate::GenericMetricLayer
+pub struct NoopMetricLayer<'a, T, M> {
+    _marker: PhantomData<(&'a (), T, M)>,
+}
+
+/// The no-op counterpart of [`PrometheusMetricLayer`](crate::PrometheusMetricLayer).
+#[cfg(feature = "prometheus")]
+pub type NoopPrometheusMetricLayer<'a> = NoopMetricLayer<'a, PrometheusHandle, crate::Handle>;
+
+impl<'a, T, M> Clone for NoopMetricLayer<'a, T, M> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T, M> Default for NoopMetricLayer<'a, T, M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T, M> NoopMetricLayer<'a, T, M> {
+    /// Create a new layer.
+    pub fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+
+    /// Does nothing.
+    pub fn enable_response_body_size(&mut self) {}
+
+    /// Always zero.
+    pub fn pending_count(&self) -> u64 {
+        0
+    }
+
+    /// Always `None`.
+    pub fn handle(&self) -> Option<&T> {
+        None
+    }
+
+    /// A switch that isn't connected to anything.
+    pub fn recording_switch(&self) -> RecordingSwitch {
+        RecordingSwitch::default()
+    }
+}
+
+impl<'a, T, M> NoopMetricLayer<'a, T, M>
+where
+    M: MakeDefaultHandle<Out = T>,
+{
+    /// Create a new layer and an exporter handle from `m`, like [`GenericMetricLayer::pair_from`].
+    ///
+    /// The exporter is still created (and possibly installed), since its handle is returned.
+    ///
+    /// [`GenericMetricLayer::pair_from`]: crate::GenericMetricLayer::pair_from
+    pub fn pair_from(m: M) -> (Self, T) {
+        (Self::new(), M::make_default_handle(m))
+    }
+
+    /// Create a new layer and an exporter handle from the value returned by `f`, like
+    /// [`GenericMetricLayer::pair_with`].
+    ///
+    /// The exporter is still created (and possibly installed), since its handle is returned.
+    ///
+    /// [`GenericMetricLayer::pair_with`]: crate::GenericMetricLayer::pair_with
+    pub fn pair_with(f: impl FnOnce() -> M) -> (Self, T) {
+        Self::pair_from(f())
+    }
+}
+
+#[cfg(feature = "prometheus")]
+impl<'a> NoopPrometheusMetricLayer<'a> {
+    /// Create a new layer and a Prometheus handle that renders nothing. Unlike [`PrometheusMetricLayer::pair`],
+    /// neither a global recorder nor an upkeep task is installed.
+    ///
+    /// [`PrometheusMetricLayer::pair`]: crate::GenericMetricLayer::pair
+    pub fn pair() -> (Self, PrometheusHandle) {
+        (
+            Self::new(),
+            PrometheusBuilder::new().build_recorder().handle(),
+        )
+    }
+}
+
+impl<'a, S, T, M> Layer<S> for NoopMetricLayer<'a, T, M> {
+    type Service = S;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        inner
+    }
+}
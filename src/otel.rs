@@ -0,0 +1,253 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, RwLock,
+    },
+};
+
+use metrics::{
+    Counter, CounterFn, Gauge, GaugeFn, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder,
+    SharedString, Unit,
+};
+use opentelemetry::{
+    metrics::{Counter as OtelCounter, Gauge as OtelGauge, Histogram as OtelHistogram, Meter},
+    KeyValue,
+};
+
+use crate::{utils, MakeDefaultHandle};
+
+/// A [`metrics::Recorder`] that forwards every metric to an OpenTelemetry [`Meter`], so the layer's metrics can be
+/// exported via OTLP (or any other OpenTelemetry exporter) instead of the Prometheus text format.
+///
+/// Counters, gauges and histograms are mapped to their OpenTelemetry counterparts, the labels are reported as
+/// attributes. Histograms measured in seconds use the same buckets as the Prometheus exporter
+/// ([`utils::SECONDS_DURATION_BUCKETS`]).
+///
+/// Installing it (via [`MakeDefaultHandle::make_default_handle`]) sets it as the global `metrics` recorder. Exporting
+/// is entirely up to the `MeterProvider` the [`Meter`] is created from, so there's no handle to render metrics from.
+///
+/// # Example
+/// ```rust,no_run
+/// use axum_prometheus::{GenericMetricLayer, OtelRecorder};
+///
+/// // Set up an SDK `MeterProvider` with an exporter, and register it with `opentelemetry::global::set_meter_provider`
+/// // before creating the recorder.
+/// let recorder = OtelRecorder::new(opentelemetry::global::meter("my-app"));
+/// let (metric_layer, _) = GenericMetricLayer::pair_from(recorder);
+/// ```
+pub struct OtelRecorder {
+    meter: Meter,
+    descriptions: RwLock<HashMap<KeyName, (Option<Unit>, SharedString)>>,
+    counters: RwLock<HashMap<KeyName, OtelCounter<u64>>>,
+    gauges: RwLock<HashMap<KeyName, OtelGauge<f64>>>,
+    histograms: RwLock<HashMap<KeyName, OtelHistogram<f64>>>,
+    gauge_values: Mutex<HashMap<Key, Arc<AtomicU64>>>,
+}
+
+impl OtelRecorder {
+    /// Create a recorder that reports into `meter`.
+    pub fn new(meter: Meter) -> Self {
+        Self {
+            meter,
+            descriptions: RwLock::default(),
+            counters: RwLock::default(),
+            gauges: RwLock::default(),
+            histograms: RwLock::default(),
+            gauge_values: Mutex::default(),
+        }
+    }
+
+    fn describe(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.descriptions
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key, (unit, description));
+    }
+
+    // OpenTelemetry instruments are meant to be created once and cloned afterwards, so they're cached by name.
+    fn instrument<I: Clone>(
+        &self,
+        cache: &RwLock<HashMap<KeyName, I>>,
+        name: &KeyName,
+        build: impl FnOnce(Option<&(Option<Unit>, SharedString)>) -> I,
+    ) -> I {
+        if let Some(instrument) = cache.read().unwrap_or_else(|e| e.into_inner()).get(name) {
+            return instrument.clone();
+        }
+        let descriptions = self.descriptions.read().unwrap_or_else(|e| e.into_inner());
+        cache
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(name.clone())
+            .or_insert_with(|| build(descriptions.get(name)))
+            .clone()
+    }
+}
+
+impl Default for OtelRecorder {
+    /// Create a recorder from the globally registered `MeterProvider`.
+    fn default() -> Self {
+        Self::new(opentelemetry::global::meter("axum-prometheus"))
+    }
+}
+
+impl MakeDefaultHandle for OtelRecorder {
+    type Out = ();
+
+    fn make_default_handle(self) -> Self::Out {
+        metrics::set_global_recorder(self).expect("Failed to set global recorder");
+    }
+}
+
+fn otel_unit(unit: Unit) -> &'static str {
+    match unit {
+        Unit::Seconds => "s",
+        Unit::Bytes => "By",
+        unit => unit.as_canonical_label(),
+    }
+}
+
+fn attributes(key: &Key) -> Vec<KeyValue> {
+    key.labels()
+        .map(|label| KeyValue::new(label.key().to_owned(), label.value().to_owned()))
+        .collect()
+}
+
+macro_rules! described {
+    ($builder:expr, $description:expr) => {{
+        let mut builder = $builder;
+        if let Some((unit, description)) = $description {
+            builder = builder.with_description(description.clone().into_owned());
+            if let Some(unit) = unit {
+                builder = builder.with_unit(otel_unit(*unit));
+            }
+        }
+        builder
+    }};
+}
+
+impl Recorder for OtelRecorder {
+    fn describe_counter(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.describe(key, unit, description);
+    }
+
+    fn describe_gauge(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.describe(key, unit, description);
+    }
+
+    fn describe_histogram(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.describe(key, unit, description);
+    }
+
+    fn register_counter(&self, key: &Key, _metadata: &Metadata<'_>) -> Counter {
+        let name = KeyName::from(key.name().to_owned());
+        let counter = self.instrument(&self.counters, &name, |description| {
+            described!(
+                self.meter.u64_counter(name.as_str().to_owned()),
+                description
+            )
+            .build()
+        });
+        Counter::from_arc(Arc::new(CounterHandle {
+            counter,
+            attributes: attributes(key),
+        }))
+    }
+
+    fn register_gauge(&self, key: &Key, _metadata: &Metadata<'_>) -> Gauge {
+        let name = KeyName::from(key.name().to_owned());
+        let gauge = self.instrument(&self.gauges, &name, |description| {
+            described!(self.meter.f64_gauge(name.as_str().to_owned()), description).build()
+        });
+        // OpenTelemetry gauges only record absolute values, so the current value is tracked here per label set.
+        let value = Arc::clone(
+            self.gauge_values
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .entry(key.clone())
+                .or_default(),
+        );
+        Gauge::from_arc(Arc::new(GaugeHandle {
+            gauge,
+            value,
+            attributes: attributes(key),
+        }))
+    }
+
+    fn register_histogram(&self, key: &Key, _metadata: &Metadata<'_>) -> Histogram {
+        let name = KeyName::from(key.name().to_owned());
+        let histogram = self.instrument(&self.histograms, &name, |description| {
+            let mut builder = described!(
+                self.meter.f64_histogram(name.as_str().to_owned()),
+                description
+            );
+            if let Some((Some(Unit::Seconds), _)) = description {
+                builder = builder.with_boundaries(utils::SECONDS_DURATION_BUCKETS.to_vec());
+            }
+            builder.build()
+        });
+        Histogram::from_arc(Arc::new(HistogramHandle {
+            histogram,
+            attributes: attributes(key),
+        }))
+    }
+}
+
+struct CounterHandle {
+    counter: OtelCounter<u64>,
+    attributes: Vec<KeyValue>,
+}
+
+impl CounterFn for CounterHandle {
+    fn increment(&self, value: u64) {
+        self.counter.add(value, &self.attributes);
+    }
+
+    // OpenTelemetry counters can't be set to an absolute value, and the layer never does so.
+    fn absolute(&self, _value: u64) {}
+}
+
+struct GaugeHandle {
+    gauge: OtelGauge<f64>,
+    value: Arc<AtomicU64>,
+    attributes: Vec<KeyValue>,
+}
+
+impl GaugeHandle {
+    fn update(&self, f: impl Fn(f64) -> f64) {
+        let previous = self
+            .value
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |bits| {
+                Some(f(f64::from_bits(bits)).to_bits())
+            })
+            .unwrap_or_else(|bits| bits);
+        self.gauge
+            .record(f(f64::from_bits(previous)), &self.attributes);
+    }
+}
+
+impl GaugeFn for GaugeHandle {
+    fn increment(&self, value: f64) {
+        self.update(|current| current + value);
+    }
+
+    fn decrement(&self, value: f64) {
+        self.update(|current| current - value);
+    }
+
+    fn set(&self, value: f64) {
+        self.update(|_| value);
+    }
+}
+
+struct HistogramHandle {
+    histogram: OtelHistogram<f64>,
+    attributes: Vec<KeyValue>,
+}
+
+impl HistogramFn for HistogramHandle {
+    fn record(&self, value: f64) {
+        self.histogram.record(value, &self.attributes);
+    }
+}
@@ -0,0 +1,88 @@
+use std::{
+    borrow::Cow,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+type Phases = Vec<(Cow<'static, str>, f64)>;
+
+/// A request extension that lets handlers record intermediate timings ("phases") of a request.
+///
+/// When phase timing is enabled with [`MetricLayerBuilder::enable_phase_timing`], the layer inserts a `PhaseTimer`
+/// into the extensions of every tracked request. Handlers can extract it, and mark when a phase of their work has been
+/// done. Once the response is generated, every recorded phase is reported in the
+/// `axum_http_requests_phase_seconds` histogram, with the phase name in the `phase` label, measured from the start of
+/// the request.
+///
+/// Phases recorded after the response has been generated are not reported.
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{routing::get, Extension, Router};
+/// use axum_prometheus::{PhaseTimer, PrometheusMetricLayerBuilder};
+///
+/// async fn handler(Extension(timer): Extension<PhaseTimer>) {
+///     // .. query the database
+///     timer.phase("db").record();
+///     // .. render the response
+///     timer.phase("render").record();
+/// }
+///
+/// let (metric_layer, metric_handle) = PrometheusMetricLayerBuilder::new()
+///     .enable_phase_timing()
+///     .with_default_metrics()
+///     .build_pair();
+/// let app = Router::<()>::new()
+///     .route("/", get(handler))
+///     .layer(metric_layer);
+/// ```
+///
+/// Since the extension is only present for tracked requests (e.g. not for ignored ones), prefer extracting
+/// `Option<Extension<PhaseTimer>>` if the handler may be called for an ignored route.
+///
+/// [`MetricLayerBuilder::enable_phase_timing`]: crate::MetricLayerBuilder::enable_phase_timing
+#[derive(Debug, Clone)]
+pub struct PhaseTimer {
+    start: Instant,
+    phases: Arc<Mutex<Phases>>,
+}
+
+impl PhaseTimer {
+    pub(crate) fn new(start: Instant) -> Self {
+        Self {
+            start,
+            phases: Arc::default(),
+        }
+    }
+
+    /// Start describing a phase named `name`. The phase is only recorded once [`Phase::record`] is called.
+    pub fn phase(&self, name: impl Into<Cow<'static, str>>) -> Phase<'_> {
+        Phase {
+            timer: self,
+            name: name.into(),
+        }
+    }
+
+    pub(crate) fn take_phases(&self) -> Phases {
+        std::mem::take(&mut *self.phases.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+/// A named phase of a request, created by [`PhaseTimer::phase`].
+#[derive(Debug)]
+pub struct Phase<'t> {
+    timer: &'t PhaseTimer,
+    name: Cow<'static, str>,
+}
+
+impl Phase<'_> {
+    /// Record that this phase is done now, measured from the start of the request.
+    pub fn record(self) {
+        let elapsed = self.timer.start.elapsed().as_secs_f64();
+        self.timer
+            .phases
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push((self.name, elapsed));
+    }
+}
@@ -0,0 +1,66 @@
+use std::{error::Error, fmt};
+
+/// A metrics handle that can render the recorded metrics, e.g. to serve them on a `/metrics` endpoint.
+///
+/// This generalizes `PrometheusHandle::render` to exporters whose rendering may fail. Implement it for the
+/// [`MakeDefaultHandle::Out`] type of a custom exporter to render its metrics the same way as Prometheus.
+///
+/// ```rust
+/// use axum_prometheus::{RenderError, Renderable};
+///
+/// struct MyHandle(Option<String>);
+///
+/// impl Renderable for MyHandle {
+///     fn try_render(&self) -> Result<String, RenderError> {
+///         self.0.clone().ok_or_else(|| RenderError::new("the exporter is shut down"))
+///     }
+/// }
+///
+/// assert_eq!(MyHandle(Some("metrics".into())).render(), "metrics");
+/// assert!(MyHandle(None).try_render().is_err());
+/// ```
+///
+/// [`MakeDefaultHandle::Out`]: crate::MakeDefaultHandle::Out
+pub trait Renderable {
+    /// Render the recorded metrics.
+    fn try_render(&self) -> Result<String, RenderError>;
+
+    /// Render the recorded metrics.
+    ///
+    /// # Panics
+    ///
+    /// Panics if rendering fails. This never happens for exporters that render infallibly, like Prometheus.
+    fn render(&self) -> String {
+        self.try_render().expect("rendering metrics failed")
+    }
+}
+
+#[cfg(feature = "prometheus")]
+impl Renderable for metrics_exporter_prometheus::PrometheusHandle {
+    fn try_render(&self) -> Result<String, RenderError> {
+        Ok(self.render())
+    }
+}
+
+/// The error returned by [`Renderable::try_render`].
+#[derive(Debug)]
+pub struct RenderError(Box<dyn Error + Send + Sync>);
+
+impl RenderError {
+    /// Create an error from the underlying cause of the failure.
+    pub fn new(source: impl Into<Box<dyn Error + Send + Sync>>) -> Self {
+        Self(source.into())
+    }
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to render metrics: {}", self.0)
+    }
+}
+
+impl Error for RenderError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.0)
+    }
+}
@@ -0,0 +1,290 @@
+//! An in-memory recorder that keeps the most recent samples of every metric, for embedding the metrics into the
+//! application itself (like a CLI or TUI dashboard) instead of exporting them.
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex, MutexGuard, RwLock},
+    time::Instant,
+};
+
+use metrics::{
+    Counter, CounterFn, Gauge, GaugeFn, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder,
+    SharedString, Unit,
+};
+
+use crate::MakeDefaultHandle;
+
+/// A [`MakeDefaultHandle`] implementation that records every metric into a bounded in-memory ring buffer, which can
+/// be read back at runtime with [`RingBufferHandle::snapshot`].
+///
+/// Each series (a metric name together with its labels) keeps its last `capacity` samples, and at most `max_series`
+/// series are tracked. Samples of series created once the limit is reached are discarded, so the memory used is
+/// bounded no matter how many distinct label values are emitted. Descriptions and units are ignored.
+///
+/// # Example
+/// ```rust
+/// use axum_prometheus::{ring_buffer::RingBufferRecorder, GenericMetricLayer};
+///
+/// let recorder = RingBufferRecorder::new(64).max_series(256);
+/// let (metric_layer, metric_handle) = GenericMetricLayer::pair_from(recorder);
+/// // Later, e.g. when redrawing a dashboard:
+/// for series in metric_handle.snapshot() {
+///     println!("{} {:?}: {:?}", series.name, series.labels, series.samples.last());
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RingBufferRecorder {
+    capacity: usize,
+    max_series: usize,
+}
+
+impl RingBufferRecorder {
+    /// Create a recorder that keeps the last `capacity` samples of each series.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "the ring buffer capacity must not be zero");
+        Self {
+            capacity,
+            max_series: 1024,
+        }
+    }
+
+    /// Set the maximum number of series to keep samples of. Defaults to 1024.
+    pub fn max_series(mut self, max_series: usize) -> Self {
+        self.max_series = max_series;
+        self
+    }
+}
+
+impl Default for RingBufferRecorder {
+    /// Create a recorder that keeps the last 128 samples of each series.
+    fn default() -> Self {
+        Self::new(128)
+    }
+}
+
+impl MakeDefaultHandle for RingBufferRecorder {
+    type Out = RingBufferHandle;
+
+    fn make_default_handle(self) -> Self::Out {
+        let handle = RingBufferHandle {
+            registry: Arc::new(Registry {
+                capacity: self.capacity,
+                max_series: self.max_series,
+                series: RwLock::default(),
+            }),
+        };
+        metrics::set_global_recorder(handle.clone()).expect("Failed to set global recorder");
+        handle
+    }
+
+    fn share_handle(handle: &Self::Out) -> Option<Self::Out> {
+        Some(handle.clone())
+    }
+}
+
+/// The handle of a [`RingBufferRecorder`], to read the recorded samples from.
+#[derive(Debug, Clone)]
+pub struct RingBufferHandle {
+    registry: Arc<Registry>,
+}
+
+impl RingBufferHandle {
+    /// The samples currently in the ring buffer of every series, oldest first. The series are in no particular
+    /// order.
+    pub fn snapshot(&self) -> Vec<RecentSamples> {
+        let series = self
+            .registry
+            .series
+            .read()
+            .unwrap_or_else(|e| e.into_inner());
+        series
+            .iter()
+            .map(|((kind, key), buffer)| RecentSamples {
+                kind: *kind,
+                name: key.name().to_owned(),
+                labels: key
+                    .labels()
+                    .map(|label| (label.key().to_owned(), label.value().to_owned()))
+                    .collect(),
+                samples: buffer.lock().samples.iter().copied().collect(),
+            })
+            .collect()
+    }
+}
+
+/// The kind of metric a series of [`RecentSamples`] belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SeriesKind {
+    /// A counter. Its samples are the value of the counter after each update.
+    Counter,
+    /// A gauge. Its samples are the value of the gauge after each update.
+    Gauge,
+    /// A histogram. Its samples are the recorded observations.
+    Histogram,
+}
+
+/// The recent samples of a single series, returned by [`RingBufferHandle::snapshot`].
+#[derive(Debug, Clone)]
+pub struct RecentSamples {
+    /// The kind of the metric.
+    pub kind: SeriesKind,
+    /// The name of the metric.
+    pub name: String,
+    /// The labels of the series.
+    pub labels: Vec<(String, String)>,
+    /// The samples of the series, oldest first.
+    pub samples: Vec<Sample>,
+}
+
+/// A single value recorded into a [`RingBufferRecorder`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sample {
+    /// The value of the sample.
+    pub value: f64,
+    /// When the sample was recorded.
+    pub at: Instant,
+}
+
+#[derive(Debug)]
+struct Registry {
+    capacity: usize,
+    max_series: usize,
+    series: RwLock<HashMap<(SeriesKind, Key), Arc<Buffer>>>,
+}
+
+impl Registry {
+    /// The buffer of the series, or `None` if it doesn't exist yet and there's no room for more series.
+    fn buffer(&self, kind: SeriesKind, key: &Key) -> Option<Arc<Buffer>> {
+        let id = (kind, key.clone());
+        if let Some(buffer) = self
+            .series
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&id)
+        {
+            return Some(Arc::clone(buffer));
+        }
+        let mut series = self.series.write().unwrap_or_else(|e| e.into_inner());
+        // Another thread may have created it in the meantime.
+        if let Some(buffer) = series.get(&id) {
+            return Some(Arc::clone(buffer));
+        }
+        if series.len() >= self.max_series {
+            return None;
+        }
+        let buffer = Arc::new(Buffer::new(self.capacity));
+        series.insert(id, Arc::clone(&buffer));
+        Some(buffer)
+    }
+}
+
+#[derive(Debug)]
+struct Buffer {
+    capacity: usize,
+    state: Mutex<BufferState>,
+}
+
+#[derive(Debug)]
+struct BufferState {
+    /// The current value of a counter or a gauge.
+    current: f64,
+    samples: VecDeque<Sample>,
+}
+
+impl BufferState {
+    fn push(&mut self, value: f64, capacity: usize) {
+        if self.samples.len() == capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(Sample {
+            value,
+            at: Instant::now(),
+        });
+    }
+}
+
+impl Buffer {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(BufferState {
+                current: 0.0,
+                samples: VecDeque::with_capacity(capacity),
+            }),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BufferState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn push(&self, value: f64) {
+        self.lock().push(value, self.capacity);
+    }
+
+    fn update(&self, f: impl FnOnce(f64) -> f64) {
+        let mut state = self.lock();
+        state.current = f(state.current);
+        let value = state.current;
+        state.push(value, self.capacity);
+    }
+}
+
+impl CounterFn for Buffer {
+    fn increment(&self, value: u64) {
+        self.update(|current| current + value as f64);
+    }
+
+    fn absolute(&self, value: u64) {
+        self.update(|_| value as f64);
+    }
+}
+
+impl GaugeFn for Buffer {
+    fn increment(&self, value: f64) {
+        self.update(|current| current + value);
+    }
+
+    fn decrement(&self, value: f64) {
+        self.update(|current| current - value);
+    }
+
+    fn set(&self, value: f64) {
+        self.update(|_| value);
+    }
+}
+
+impl HistogramFn for Buffer {
+    fn record(&self, value: f64) {
+        self.push(value);
+    }
+}
+
+impl Recorder for RingBufferHandle {
+    fn describe_counter(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+
+    fn describe_gauge(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+
+    fn describe_histogram(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+
+    fn register_counter(&self, key: &Key, _metadata: &Metadata<'_>) -> Counter {
+        self.registry
+            .buffer(SeriesKind::Counter, key)
+            .map_or_else(Counter::noop, Counter::from_arc)
+    }
+
+    fn register_gauge(&self, key: &Key, _metadata: &Metadata<'_>) -> Gauge {
+        self.registry
+            .buffer(SeriesKind::Gauge, key)
+            .map_or_else(Gauge::noop, Gauge::from_arc)
+    }
+
+    fn register_histogram(&self, key: &Key, _metadata: &Metadata<'_>) -> Histogram {
+        self.registry
+            .buffer(SeriesKind::Histogram, key)
+            .map_or_else(Histogram::noop, Histogram::from_arc)
+    }
+}
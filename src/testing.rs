@@ -0,0 +1,196 @@
+//! Helpers for testing how a metric layer records requests, without installing a global recorder.
+use std::{
+    convert::Infallible,
+    fmt,
+    future::{poll_fn, Future},
+    pin::{pin, Pin},
+    task::{Context, Poll},
+};
+
+use http::{Request, Response};
+use http_body::Body;
+use metrics_util::{
+    debugging::{DebugValue, DebuggingRecorder},
+    CompositeKey, MetricKind,
+};
+use pin_project_lite::pin_project;
+use tower::{Layer, Service, ServiceExt};
+
+/// Run `request` through `layer` wrapping an echo service (which responds with the request body), drain the response
+/// body so the body callbacks fire, and return every metric recorded along the way.
+///
+/// The metrics are captured by a [`DebuggingRecorder`] that's only active while driving the request, so the layer
+/// should be built without installing a recorder (e.g. with [`MetricLayerBuilder::build`]).
+///
+/// ```rust
+/// use axum_prometheus::{testing::drive_once, PrometheusMetricLayerBuilder};
+/// use http::Request;
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let layer = PrometheusMetricLayerBuilder::new().build();
+/// let request = Request::builder().uri("/foo").body(String::new()).unwrap();
+/// let metrics = drive_once(layer, request).await;
+///
+/// let labels = [("method", "GET"), ("status", "200"), ("endpoint", "/foo")];
+/// assert_eq!(metrics.counter("axum_http_requests_total", &labels), Some(1));
+/// # }
+/// ```
+///
+/// [`MetricLayerBuilder::build`]: crate::MetricLayerBuilder::build
+pub async fn drive_once<L, B, ResBody>(layer: L, request: Request<B>) -> RecordedMetrics
+where
+    L: Layer<Echo>,
+    L::Service: Service<Request<B>, Response = Response<ResBody>>,
+    <L::Service as Service<Request<B>>>::Error: fmt::Debug,
+    ResBody: Body,
+    ResBody::Error: fmt::Debug,
+{
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    WithRecorder {
+        recorder: &recorder,
+        inner: async move {
+            let response = layer
+                .layer(Echo)
+                .oneshot(request)
+                .await
+                .expect("the service failed");
+            let mut body = pin!(response.into_body());
+            while let Some(frame) = poll_fn(|cx| body.as_mut().poll_frame(cx)).await {
+                frame.expect("the response body failed");
+            }
+        },
+    }
+    .await;
+    RecordedMetrics(
+        snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .map(|(key, _, _, value)| (key, value))
+            .collect(),
+    )
+}
+
+/// Run the idle metric cleanup of the Prometheus recorder behind `handle` right away, instead of waiting for the next
+/// upkeep or render.
+///
+/// This doesn't remove metrics that were just updated: the recorder doesn't timestamp updates, and only notices that a
+/// metric changed when a sweep (or a render) sees it. So a metric is removed by the first sweep that runs longer than
+/// the timeout configured with `PrometheusBuilder::idle_timeout` after the sweep which first saw its current value,
+/// measured with a real clock that can't be advanced. To test expiry deterministically, configure a short timeout,
+/// sweep once to start the idle timer, wait the timeout out, then sweep again:
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use axum_prometheus::testing::force_idle_sweep;
+/// use metrics_exporter_prometheus::PrometheusBuilder;
+/// use metrics_util::MetricKindMask;
+///
+/// let recorder = PrometheusBuilder::new()
+///     .idle_timeout(MetricKindMask::ALL, Some(Duration::from_millis(10)))
+///     .build_recorder();
+/// let handle = recorder.handle();
+/// metrics::with_local_recorder(&recorder, || metrics::counter!("requests").increment(1));
+///
+/// // Starts the idle timer of `requests`.
+/// force_idle_sweep(&handle);
+/// assert!(handle.render().contains("requests 1"));
+///
+/// std::thread::sleep(Duration::from_millis(20));
+/// force_idle_sweep(&handle);
+/// assert!(!handle.render().contains("requests"));
+/// ```
+///
+/// Note that the default recorder of [`PrometheusMetricLayer::pair`] has no idle timeout, so nothing is ever removed
+/// from it.
+///
+/// [`PrometheusMetricLayer::pair`]: crate::GenericMetricLayer::pair
+#[cfg(feature = "prometheus")]
+pub fn force_idle_sweep(handle: &metrics_exporter_prometheus::PrometheusHandle) {
+    handle.run_upkeep();
+    // Idle metrics are only removed from the registry while taking a snapshot, which rendering does.
+    let _ = handle.render();
+}
+
+/// The service wrapped by the layer in [`drive_once`], which responds with the request body.
+#[derive(Debug, Clone, Copy)]
+pub struct Echo;
+
+impl<B> Service<Request<B>> for Echo {
+    type Response = Response<B>;
+    type Error = Infallible;
+    type Future = std::future::Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        std::future::ready(Ok(Response::new(req.into_body())))
+    }
+}
+
+/// The metrics recorded by [`drive_once`].
+#[derive(Debug)]
+pub struct RecordedMetrics(Vec<(CompositeKey, DebugValue)>);
+
+impl RecordedMetrics {
+    /// The value of the counter `name` with exactly the given `labels` (in any order), if it was recorded.
+    pub fn counter(&self, name: &str, labels: &[(&str, &str)]) -> Option<u64> {
+        match self.find(MetricKind::Counter, name, labels)? {
+            DebugValue::Counter(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// The value of the gauge `name` with exactly the given `labels` (in any order), if it was recorded.
+    pub fn gauge(&self, name: &str, labels: &[(&str, &str)]) -> Option<f64> {
+        match self.find(MetricKind::Gauge, name, labels)? {
+            DebugValue::Gauge(value) => Some(value.0),
+            _ => None,
+        }
+    }
+
+    /// The values recorded in the histogram `name` with exactly the given `labels` (in any order), if any.
+    pub fn histogram(&self, name: &str, labels: &[(&str, &str)]) -> Option<Vec<f64>> {
+        match self.find(MetricKind::Histogram, name, labels)? {
+            DebugValue::Histogram(values) => Some(values.iter().map(|value| value.0).collect()),
+            _ => None,
+        }
+    }
+
+    fn find(&self, kind: MetricKind, name: &str, labels: &[(&str, &str)]) -> Option<&DebugValue> {
+        self.0.iter().find_map(|(key, value)| {
+            let matches = key.kind() == kind
+                && key.key().name() == name
+                && key.key().labels().len() == labels.len()
+                && key
+                    .key()
+                    .labels()
+                    .all(|label| labels.contains(&(label.key(), label.value())));
+            matches.then_some(value)
+        })
+    }
+}
+
+pin_project! {
+    // Makes `recorder` the local recorder whenever `inner` is polled, so it captures the metrics even when the
+    // future moves between threads.
+    struct WithRecorder<'r, F> {
+        recorder: &'r DebuggingRecorder,
+        #[pin]
+        inner: F,
+    }
+}
+
+impl<F: Future> Future for WithRecorder<'_, F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        metrics::with_local_recorder(*this.recorder, || this.inner.poll(cx))
+    }
+}
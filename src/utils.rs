@@ -1,10 +1,36 @@
 //! Utilities for getting metric names at runtime, and other helpers.
-use http::Method;
+use std::{
+    any::Any,
+    borrow::Cow,
+    collections::BTreeSet,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+};
+
+use http::{header, request::Parts, HeaderMap, HeaderValue, Method, Request};
+use tower_http::classify::{ServerErrorsFailureClass, StatusInRangeFailureClass};
 
 use crate::{
-    AXUM_HTTP_REQUESTS_DURATION_SECONDS, AXUM_HTTP_REQUESTS_PENDING, AXUM_HTTP_REQUESTS_TOTAL,
-    AXUM_HTTP_RESPONSE_BODY_SIZE, PREFIXED_HTTP_REQUESTS_DURATION_SECONDS,
-    PREFIXED_HTTP_REQUESTS_PENDING, PREFIXED_HTTP_REQUESTS_TOTAL, PREFIXED_HTTP_RESPONSE_BODY_SIZE,
+    AXUM_GRPC_REQUESTS_TOTAL, AXUM_HTTP_CLIENT_REQUESTS_DURATION_SECONDS,
+    AXUM_HTTP_CLIENT_REQUESTS_TOTAL, AXUM_HTTP_INTER_ARRIVAL_SECONDS,
+    AXUM_HTTP_METHOD_DURATION_SECONDS, AXUM_HTTP_REQUESTS_CANCELLED_TOTAL,
+    AXUM_HTTP_REQUESTS_CONCURRENCY, AXUM_HTTP_REQUESTS_DURATION_SECONDS,
+    AXUM_HTTP_REQUESTS_FAILED_TOTAL, AXUM_HTTP_REQUESTS_FINISHED_TOTAL,
+    AXUM_HTTP_REQUESTS_GROUPED_TOTAL, AXUM_HTTP_REQUESTS_PENDING, AXUM_HTTP_REQUESTS_PHASE_SECONDS,
+    AXUM_HTTP_REQUESTS_STARTED_TOTAL, AXUM_HTTP_REQUESTS_TOTAL, AXUM_HTTP_REQUESTS_TOTAL_UNLABELED,
+    AXUM_HTTP_REQUEST_HEADERS_BYTES, AXUM_HTTP_RESPONSE_BODY_BYTES_TOTAL,
+    AXUM_HTTP_RESPONSE_BODY_SIZE, AXUM_HTTP_RESPONSE_CHUNKS, AXUM_HTTP_SLO_VIOLATIONS_TOTAL,
+    AXUM_HTTP_STREAM_DURATION_SECONDS, PREFIXED_GRPC_REQUESTS_TOTAL,
+    PREFIXED_HTTP_CLIENT_REQUESTS_DURATION_SECONDS, PREFIXED_HTTP_CLIENT_REQUESTS_TOTAL,
+    PREFIXED_HTTP_INTER_ARRIVAL_SECONDS, PREFIXED_HTTP_METHOD_DURATION_SECONDS,
+    PREFIXED_HTTP_REQUESTS_CANCELLED_TOTAL, PREFIXED_HTTP_REQUESTS_CONCURRENCY,
+    PREFIXED_HTTP_REQUESTS_DURATION_SECONDS, PREFIXED_HTTP_REQUESTS_FAILED_TOTAL,
+    PREFIXED_HTTP_REQUESTS_FINISHED_TOTAL, PREFIXED_HTTP_REQUESTS_GROUPED_TOTAL,
+    PREFIXED_HTTP_REQUESTS_PENDING, PREFIXED_HTTP_REQUESTS_PHASE_SECONDS,
+    PREFIXED_HTTP_REQUESTS_STARTED_TOTAL, PREFIXED_HTTP_REQUESTS_TOTAL,
+    PREFIXED_HTTP_REQUESTS_TOTAL_UNLABELED, PREFIXED_HTTP_REQUEST_HEADERS_BYTES,
+    PREFIXED_HTTP_RESPONSE_BODY_BYTES_TOTAL, PREFIXED_HTTP_RESPONSE_BODY_SIZE,
+    PREFIXED_HTTP_RESPONSE_CHUNKS, PREFIXED_HTTP_SLO_VIOLATIONS_TOTAL,
+    PREFIXED_HTTP_STREAM_DURATION_SECONDS,
 };
 
 /// Standard HTTP request duration buckets measured in seconds. The default buckets are tailored to broadly
@@ -14,6 +40,51 @@ pub const SECONDS_DURATION_BUCKETS: &[f64; 11] = &[
     0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
 ];
 
+/// Request duration buckets measured in seconds, for latency-sensitive services that respond well within a second.
+pub const FAST_API_BUCKETS: &[f64; 11] = &[
+    0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0,
+];
+
+/// Request duration buckets measured in seconds, for long-running requests (like batch jobs or report generation)
+/// taking up to ten minutes.
+pub const BATCH_BUCKETS: &[f64; 11] = &[
+    0.1, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0, 600.0,
+];
+
+/// Body size buckets measured in bytes, growing by a factor of four from 64 bytes to 16 MiB.
+///
+/// These are meant for the response body size metric, which isn't configured by [`BucketPreset`], e.g.
+/// `.set_buckets_for_metric(Matcher::Full(response_body_size_name().to_string()), BYTES_SIZE_BUCKETS)`.
+pub const BYTES_SIZE_BUCKETS: &[f64; 10] = &[
+    64.0, 256.0, 1024.0, 4096.0, 16384.0, 65536.0, 262144.0, 1048576.0, 4194304.0, 16777216.0,
+];
+
+/// Named sets of buckets for the histograms measured in seconds, used by
+/// [`PrometheusMetricLayerBuilder::with_bucket_preset`].
+///
+/// [`PrometheusMetricLayerBuilder::with_bucket_preset`]: crate::MetricLayerBuilder::with_bucket_preset
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BucketPreset {
+    /// [`SECONDS_DURATION_BUCKETS`], the buckets used by default.
+    #[default]
+    Standard,
+    /// [`FAST_API_BUCKETS`].
+    FastApi,
+    /// [`BATCH_BUCKETS`].
+    Batch,
+}
+
+impl BucketPreset {
+    /// The bucket boundaries of this preset, in seconds.
+    pub const fn buckets(self) -> &'static [f64] {
+        match self {
+            BucketPreset::Standard => SECONDS_DURATION_BUCKETS,
+            BucketPreset::FastApi => FAST_API_BUCKETS,
+            BucketPreset::Batch => BATCH_BUCKETS,
+        }
+    }
+}
+
 pub(super) const fn as_label(method: &Method) -> &'static str {
     match *method {
         Method::OPTIONS => "OPTIONS",
@@ -29,6 +100,250 @@ pub(super) const fn as_label(method: &Method) -> &'static str {
     }
 }
 
+// The digits of every valid status code (100..=999), so status labels don't need to be allocated.
+static STATUS_DIGITS: [u8; 2700] = {
+    let mut digits = [0; 2700];
+    let mut i = 0;
+    while i < 900 {
+        let code = i + 100;
+        digits[i * 3] = b'0' + (code / 100) as u8;
+        digits[i * 3 + 1] = b'0' + (code / 10 % 10) as u8;
+        digits[i * 3 + 2] = b'0' + (code % 10) as u8;
+        i += 1;
+    }
+    digits
+};
+
+/// The label of a status code, e.g. `"200"`. `status` must be in `100..=999`, like every [`http::StatusCode`].
+pub(crate) fn status_label(status: u16) -> &'static str {
+    let start = (status as usize - 100) * 3;
+    std::str::from_utf8(&STATUS_DIGITS[start..start + 3]).expect("status digits are ASCII")
+}
+
+/// Replace the characters in `label` that need escaping in the Prometheus text format (`\`, `"`, and control
+/// characters such as newlines) with `_`. Returns the label unchanged (and without allocating) if there's nothing
+/// to replace.
+///
+/// This is the default sanitizer that can be passed to [`with_label_sanitizer`].
+///
+/// ```rust
+/// use axum_prometheus::utils::sanitize_label;
+///
+/// assert_eq!(sanitize_label("/foo/{bar}"), "/foo/{bar}");
+/// assert_eq!(sanitize_label("/foo\"bar\n"), "/foo_bar_");
+/// ```
+///
+/// [`with_label_sanitizer`]: crate::MetricLayerBuilder::with_label_sanitizer
+pub fn sanitize_label(label: &str) -> Cow<'_, str> {
+    let needs_replacing = |c: char| c == '\\' || c == '"' || c.is_control();
+    if label.contains(needs_replacing) {
+        Cow::Owned(label.replace(needs_replacing, "_"))
+    } else {
+        Cow::Borrowed(label)
+    }
+}
+
+/// The `reason` label of a request that failed with an error, detected from the error message in the failure class:
+/// `reset` for HTTP/2 stream resets, `timeout` for timeouts, `connection` for other connection errors, and `other` for
+/// everything else, including failure classes other than the ones of `tower_http`'s classifiers.
+pub(crate) fn failure_reason(failure_class: &dyn Any) -> &'static str {
+    let error = if let Some(class) = failure_class.downcast_ref::<StatusInRangeFailureClass>() {
+        match class {
+            StatusInRangeFailureClass::Error(error) => error,
+            StatusInRangeFailureClass::StatusCode(_) => return "other",
+        }
+    } else if let Some(class) = failure_class.downcast_ref::<ServerErrorsFailureClass>() {
+        match class {
+            ServerErrorsFailureClass::Error(error) => error,
+            ServerErrorsFailureClass::StatusCode(_) => return "other",
+        }
+    } else {
+        return "other";
+    };
+    let mentions = |needles: &[&str]| {
+        needles.iter().any(|needle| {
+            error
+                .as_bytes()
+                .windows(needle.len())
+                .any(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
+        })
+    };
+    if mentions(&["stream error", "reset", "rst_stream"]) {
+        "reset"
+    } else if mentions(&["timed out", "timeout", "elapsed"]) {
+        "timeout"
+    } else if mentions(&["connection", "broken pipe"]) {
+        "connection"
+    } else {
+        "other"
+    }
+}
+
+/// The first IP address in a forwarded header value, like `x-forwarded-for` (which lists the client first) or
+/// `x-real-ip`.
+pub(crate) fn forwarded_ip(value: &HeaderValue) -> Option<IpAddr> {
+    value.to_str().ok()?.split(',').next()?.trim().parse().ok()
+}
+
+/// The `client_subnet` label of `ip`, keeping only its first `prefix_len` bits, e.g. `203.0.113.0/24`. IPv4-mapped
+/// IPv6 addresses are treated as IPv4.
+pub(crate) fn subnet_label(ip: IpAddr, prefix_len: u8) -> String {
+    match ip.to_canonical() {
+        IpAddr::V4(ip) => {
+            let prefix_len = prefix_len.min(32);
+            let mask = u32::MAX
+                .checked_shl(32 - u32::from(prefix_len))
+                .unwrap_or(0);
+            format!("{}/{prefix_len}", Ipv4Addr::from(u32::from(ip) & mask))
+        }
+        IpAddr::V6(ip) => {
+            let prefix_len = prefix_len.min(128);
+            let mask = u128::MAX
+                .checked_shl(128 - u32::from(prefix_len))
+                .unwrap_or(0);
+            format!("{}/{prefix_len}", Ipv6Addr::from(u128::from(ip) & mask))
+        }
+    }
+}
+
+// The content type of the Prometheus text format.
+#[cfg(feature = "prometheus")]
+pub(crate) const TEXT_FORMAT: HeaderValue = HeaderValue::from_static("text/plain; version=0.0.4");
+
+// The longest header value reported as a label, longer values are truncated.
+const HEADER_LABEL_MAX_LEN: usize = 64;
+
+/// The label of a request header value for [`with_header_labels`]: sanitized with [`sanitize_label`], and truncated
+/// to 64 bytes. Missing headers are reported as `none`, and values that aren't visible ASCII as `invalid`.
+///
+/// [`with_header_labels`]: crate::MetricLayerBuilder::with_header_labels
+pub(crate) fn header_label(value: Option<&HeaderValue>) -> String {
+    let Some(value) = value else {
+        return "none".to_owned();
+    };
+    let Ok(value) = value.to_str() else {
+        return "invalid".to_owned();
+    };
+    // Visible ASCII only, so any byte index is a char boundary.
+    let value = &value[..value.len().min(HEADER_LABEL_MAX_LEN)];
+    sanitize_label(value).into_owned()
+}
+
+/// A copy of the parts of `request`, for the hooks that take them. This clones the headers and extensions.
+pub(crate) fn request_parts<B>(request: &Request<B>) -> Parts {
+    let (mut parts, ()) = Request::new(()).into_parts();
+    parts.method = request.method().clone();
+    parts.uri = request.uri().clone();
+    parts.version = request.version();
+    parts.headers = request.headers().clone();
+    parts.extensions = request.extensions().clone();
+    parts
+}
+
+/// Whether a request has a body, judging by its headers: a `Transfer-Encoding`, or a `Content-Length` other than `0`.
+pub(crate) fn has_body(headers: &HeaderMap) -> bool {
+    headers.contains_key(header::TRANSFER_ENCODING)
+        || headers
+            .get(header::CONTENT_LENGTH)
+            .is_some_and(|length| length.as_bytes() != b"0")
+}
+
+/// The `query_keys` label of a request for [`with_query_key_label`]: the keys of the query parameters, sorted and
+/// deduplicated so the label doesn't depend on their order, and joined with `,`. The values are never included.
+/// Requests without query parameters are reported as `none`.
+///
+/// ```rust
+/// use axum_prometheus::utils::query_keys_label;
+///
+/// assert_eq!(query_keys_label(Some("offset=10&limit=5&limit=6")), "limit,offset");
+/// assert_eq!(query_keys_label(Some("flag&q=")), "flag,q");
+/// assert_eq!(query_keys_label(None), "none");
+/// ```
+///
+/// [`with_query_key_label`]: crate::MetricLayerBuilder::with_query_key_label
+pub fn query_keys_label(query: Option<&str>) -> String {
+    let keys: BTreeSet<&str> = query
+        .unwrap_or_default()
+        .split('&')
+        .map(|pair| pair.split_once('=').map_or(pair, |(key, _)| key))
+        .filter(|key| !key.is_empty())
+        .collect();
+    if keys.is_empty() {
+        return "none".to_owned();
+    }
+    let keys = keys.into_iter().collect::<Vec<_>>().join(",");
+    sanitize_label(&keys).into_owned()
+}
+
+/// Normalize a response `Content-Type` to a short label, so the number of distinct label values stays small.
+/// Parameters (like `charset`) are ignored, common MIME types are mapped to short tokens (e.g. `application/json` to
+/// `json`), and everything else is reported as `other`. A missing `Content-Type` is reported as `none`.
+///
+/// This is used by [`with_content_type_label`].
+///
+/// ```rust
+/// use axum_prometheus::utils::content_type_label;
+///
+/// assert_eq!(content_type_label(Some("application/json; charset=utf-8")), "json");
+/// assert_eq!(content_type_label(Some("image/png")), "image");
+/// assert_eq!(content_type_label(Some("application/vnd.foo")), "other");
+/// assert_eq!(content_type_label(None), "none");
+/// ```
+///
+/// [`with_content_type_label`]: crate::MetricLayerBuilder::with_content_type_label
+pub fn content_type_label(content_type: Option<&str>) -> &'static str {
+    let Some(content_type) = content_type else {
+        return "none";
+    };
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    match mime.as_str() {
+        "application/json" => "json",
+        m if m.starts_with("application/") && m.ends_with("+json") => "json",
+        "application/xml" | "text/xml" => "xml",
+        "text/html" => "html",
+        "text/plain" => "text",
+        "text/event-stream" => "sse",
+        m if m.starts_with("application/grpc") => "grpc",
+        "application/protobuf" | "application/x-protobuf" => "protobuf",
+        "application/octet-stream" => "binary",
+        "application/x-www-form-urlencoded" => "form",
+        m if m.starts_with("multipart/") => "multipart",
+        m if m.starts_with("image/") => "image",
+        _ => "other",
+    }
+}
+
+/// Normalize a response `Content-Encoding` to one of the common encodings, `identity` if the header is missing, or
+/// `other`.
+pub(crate) fn encoding_label(value: Option<&str>) -> &'static str {
+    let Some(value) = value else {
+        return "identity";
+    };
+    match value.trim().to_ascii_lowercase().as_str() {
+        "gzip" | "x-gzip" => "gzip",
+        "br" => "br",
+        "deflate" => "deflate",
+        "zstd" => "zstd",
+        "identity" | "" => "identity",
+        _ => "other",
+    }
+}
+
+/// Normalize the value of a cache status header (like `X-Cache: HIT from cloudfront`) to `hit` or `miss`, or `none`
+/// if the header is missing.
+pub(crate) fn cache_label(value: Option<&str>) -> &'static str {
+    match value {
+        Some(value) if value.to_ascii_lowercase().contains("hit") => "hit",
+        Some(_) => "miss",
+        None => "none",
+    }
+}
+
 /// The name of the requests total metric. By default, it's the same as [`AXUM_HTTP_REQUESTS_TOTAL`], but
 /// can be changed via the [`with_prefix`] function.
 ///
@@ -68,3 +383,183 @@ pub fn response_body_size_name() -> &'static str {
         .get()
         .map_or(AXUM_HTTP_RESPONSE_BODY_SIZE, |s| s.as_str())
 }
+
+/// The name of the response chunks metric. By default, it's the same as [`AXUM_HTTP_RESPONSE_CHUNKS`], but
+/// can be changed via the [`with_prefix`] function.
+///
+/// [`with_prefix`]: crate::MetricLayerBuilder::with_prefix
+pub fn response_chunks_name() -> &'static str {
+    PREFIXED_HTTP_RESPONSE_CHUNKS
+        .get()
+        .map_or(AXUM_HTTP_RESPONSE_CHUNKS, |s| s.as_str())
+}
+
+/// The name of the unlabeled requests total metric. By default, it's the same as [`AXUM_HTTP_REQUESTS_TOTAL_UNLABELED`], but
+/// can be changed via the [`with_prefix`] function.
+///
+/// [`with_prefix`]: crate::MetricLayerBuilder::with_prefix
+pub fn requests_total_unlabeled_name() -> &'static str {
+    PREFIXED_HTTP_REQUESTS_TOTAL_UNLABELED
+        .get()
+        .map_or(AXUM_HTTP_REQUESTS_TOTAL_UNLABELED, |s| s.as_str())
+}
+
+/// The name of the request phase metric. By default, it's the same as [`AXUM_HTTP_REQUESTS_PHASE_SECONDS`], but
+/// can be changed via the [`with_prefix`] function.
+///
+/// [`with_prefix`]: crate::MetricLayerBuilder::with_prefix
+pub fn requests_phase_name() -> &'static str {
+    PREFIXED_HTTP_REQUESTS_PHASE_SECONDS
+        .get()
+        .map_or(AXUM_HTTP_REQUESTS_PHASE_SECONDS, |s| s.as_str())
+}
+
+/// The name of the cancelled requests total metric. By default, it's the same as [`AXUM_HTTP_REQUESTS_CANCELLED_TOTAL`], but
+/// can be changed via the [`with_prefix`] function.
+///
+/// [`with_prefix`]: crate::MetricLayerBuilder::with_prefix
+pub fn requests_cancelled_name() -> &'static str {
+    PREFIXED_HTTP_REQUESTS_CANCELLED_TOTAL
+        .get()
+        .map_or(AXUM_HTTP_REQUESTS_CANCELLED_TOTAL, |s| s.as_str())
+}
+
+/// The name of the inter-arrival time metric. By default, it's the same as [`AXUM_HTTP_INTER_ARRIVAL_SECONDS`], but
+/// can be changed via the [`with_prefix`] function.
+///
+/// [`with_prefix`]: crate::MetricLayerBuilder::with_prefix
+pub fn inter_arrival_name() -> &'static str {
+    PREFIXED_HTTP_INTER_ARRIVAL_SECONDS
+        .get()
+        .map_or(AXUM_HTTP_INTER_ARRIVAL_SECONDS, |s| s.as_str())
+}
+
+/// The name of the stream duration metric. By default, it's the same as [`AXUM_HTTP_STREAM_DURATION_SECONDS`], but
+/// can be changed via the [`with_prefix`] function.
+///
+/// [`with_prefix`]: crate::MetricLayerBuilder::with_prefix
+pub fn stream_duration_name() -> &'static str {
+    PREFIXED_HTTP_STREAM_DURATION_SECONDS
+        .get()
+        .map_or(AXUM_HTTP_STREAM_DURATION_SECONDS, |s| s.as_str())
+}
+
+/// The name of the grouped requests total metric. By default, it's the same as [`AXUM_HTTP_REQUESTS_GROUPED_TOTAL`], but
+/// can be changed via the [`with_prefix`] function.
+///
+/// [`with_prefix`]: crate::MetricLayerBuilder::with_prefix
+pub fn requests_grouped_name() -> &'static str {
+    PREFIXED_HTTP_REQUESTS_GROUPED_TOTAL
+        .get()
+        .map_or(AXUM_HTTP_REQUESTS_GROUPED_TOTAL, |s| s.as_str())
+}
+
+/// The name of the requests concurrency metric. By default, it's the same as [`AXUM_HTTP_REQUESTS_CONCURRENCY`], but
+/// can be changed via the [`with_prefix`] function.
+///
+/// [`with_prefix`]: crate::MetricLayerBuilder::with_prefix
+pub fn requests_concurrency_name() -> &'static str {
+    PREFIXED_HTTP_REQUESTS_CONCURRENCY
+        .get()
+        .map_or(AXUM_HTTP_REQUESTS_CONCURRENCY, |s| s.as_str())
+}
+
+/// The name of the gRPC requests total metric. By default, it's the same as [`AXUM_GRPC_REQUESTS_TOTAL`], but
+/// can be changed via the [`with_prefix`] function.
+///
+/// [`with_prefix`]: crate::MetricLayerBuilder::with_prefix
+pub fn grpc_requests_total_name() -> &'static str {
+    PREFIXED_GRPC_REQUESTS_TOTAL
+        .get()
+        .map_or(AXUM_GRPC_REQUESTS_TOTAL, |s| s.as_str())
+}
+
+/// The name of the request headers size metric. By default, it's the same as [`AXUM_HTTP_REQUEST_HEADERS_BYTES`], but
+/// can be changed via the [`with_prefix`] function.
+///
+/// [`with_prefix`]: crate::MetricLayerBuilder::with_prefix
+pub fn request_headers_name() -> &'static str {
+    PREFIXED_HTTP_REQUEST_HEADERS_BYTES
+        .get()
+        .map_or(AXUM_HTTP_REQUEST_HEADERS_BYTES, |s| s.as_str())
+}
+
+/// The name of the method duration metric. By default, it's the same as [`AXUM_HTTP_METHOD_DURATION_SECONDS`], but
+/// can be changed via the [`with_prefix`] function.
+///
+/// [`with_prefix`]: crate::MetricLayerBuilder::with_prefix
+pub fn method_duration_name() -> &'static str {
+    PREFIXED_HTTP_METHOD_DURATION_SECONDS
+        .get()
+        .map_or(AXUM_HTTP_METHOD_DURATION_SECONDS, |s| s.as_str())
+}
+
+/// The name of the failed requests metric. By default, it's the same as [`AXUM_HTTP_REQUESTS_FAILED_TOTAL`], but
+/// can be changed via the [`with_prefix`] function.
+///
+/// [`with_prefix`]: crate::MetricLayerBuilder::with_prefix
+pub fn requests_failed_name() -> &'static str {
+    PREFIXED_HTTP_REQUESTS_FAILED_TOTAL
+        .get()
+        .map_or(AXUM_HTTP_REQUESTS_FAILED_TOTAL, |s| s.as_str())
+}
+
+/// The name of the started requests metric. By default, it's the same as [`AXUM_HTTP_REQUESTS_STARTED_TOTAL`], but
+/// can be changed via the [`with_prefix`] function.
+///
+/// [`with_prefix`]: crate::MetricLayerBuilder::with_prefix
+pub fn requests_started_name() -> &'static str {
+    PREFIXED_HTTP_REQUESTS_STARTED_TOTAL
+        .get()
+        .map_or(AXUM_HTTP_REQUESTS_STARTED_TOTAL, |s| s.as_str())
+}
+
+/// The name of the finished requests metric. By default, it's the same as [`AXUM_HTTP_REQUESTS_FINISHED_TOTAL`], but
+/// can be changed via the [`with_prefix`] function.
+///
+/// [`with_prefix`]: crate::MetricLayerBuilder::with_prefix
+pub fn requests_finished_name() -> &'static str {
+    PREFIXED_HTTP_REQUESTS_FINISHED_TOTAL
+        .get()
+        .map_or(AXUM_HTTP_REQUESTS_FINISHED_TOTAL, |s| s.as_str())
+}
+
+/// The name of the client requests total metric. By default, it's the same as [`AXUM_HTTP_CLIENT_REQUESTS_TOTAL`], but
+/// can be changed via the [`with_prefix`] function.
+///
+/// [`with_prefix`]: crate::MetricLayerBuilder::with_prefix
+pub fn client_requests_total_name() -> &'static str {
+    PREFIXED_HTTP_CLIENT_REQUESTS_TOTAL
+        .get()
+        .map_or(AXUM_HTTP_CLIENT_REQUESTS_TOTAL, |s| s.as_str())
+}
+
+/// The name of the client requests duration metric. By default, it's the same as [`AXUM_HTTP_CLIENT_REQUESTS_DURATION_SECONDS`], but
+/// can be changed via the [`with_prefix`] function.
+///
+/// [`with_prefix`]: crate::MetricLayerBuilder::with_prefix
+pub fn client_requests_duration_name() -> &'static str {
+    PREFIXED_HTTP_CLIENT_REQUESTS_DURATION_SECONDS
+        .get()
+        .map_or(AXUM_HTTP_CLIENT_REQUESTS_DURATION_SECONDS, |s| s.as_str())
+}
+
+/// The name of the response body bytes total metric. By default, it's the same as [`AXUM_HTTP_RESPONSE_BODY_BYTES_TOTAL`], but
+/// can be changed via the [`with_prefix`] function.
+///
+/// [`with_prefix`]: crate::MetricLayerBuilder::with_prefix
+pub fn response_body_bytes_total_name() -> &'static str {
+    PREFIXED_HTTP_RESPONSE_BODY_BYTES_TOTAL
+        .get()
+        .map_or(AXUM_HTTP_RESPONSE_BODY_BYTES_TOTAL, |s| s.as_str())
+}
+
+/// The name of the SLO violations counter metric. By default, it's the same as [`AXUM_HTTP_SLO_VIOLATIONS_TOTAL`], but
+/// can be changed via the [`with_prefix`] function.
+///
+/// [`with_prefix`]: crate::MetricLayerBuilder::with_prefix
+pub fn slo_violations_name() -> &'static str {
+    PREFIXED_HTTP_SLO_VIOLATIONS_TOTAL
+        .get()
+        .map_or(AXUM_HTTP_SLO_VIOLATIONS_TOTAL, |s| s.as_str())
+}
@@ -0,0 +1,32 @@
+mod common;
+use common::{echo, BoxBody};
+
+use axum_prometheus::{AdaptiveBucketsRecorder, GenericMetricLayer};
+use http::Request;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn buckets_derived_after_warmup() {
+    let (layer, handle) = GenericMetricLayer::pair_from(AdaptiveBucketsRecorder::new(3).buckets(4));
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+    for _ in 0..3 {
+        assert!(handle.buckets().is_none());
+        let req = Request::builder().body(BoxBody::default()).unwrap();
+        let _res = service.ready().await.unwrap().call(req).await.unwrap();
+    }
+
+    let buckets = handle.buckets().unwrap().to_vec();
+    assert!(!buckets.is_empty() && buckets.len() <= 4);
+    assert!(buckets.windows(2).all(|pair| pair[0] < pair[1]));
+
+    // Requests after the warmup are recorded with the derived buckets.
+    let req = Request::builder().body(BoxBody::default()).unwrap();
+    let _res = service.ready().await.unwrap().call(req).await.unwrap();
+    let rendered = handle.render();
+    let le = format!("le=\"{}\"", buckets.last().unwrap());
+    assert!(rendered.lines().any(|line| line
+        .starts_with("axum_http_requests_duration_seconds_bucket")
+        && line.contains(&le)));
+    assert!(rendered
+        .contains("axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/\"} 1\n"));
+}
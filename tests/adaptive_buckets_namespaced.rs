@@ -0,0 +1,26 @@
+mod common;
+use common::{echo, BoxBody};
+
+use axum_prometheus::{AdaptiveBucketsHandle, AdaptiveBucketsRecorder, MetricLayerBuilder};
+use http::Request;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn buckets_derived_for_namespaced_layer() {
+    let (layer, handle): (_, AdaptiveBucketsHandle) = MetricLayerBuilder::new()
+        .with_namespace("api")
+        .with_metrics_from(AdaptiveBucketsRecorder::new(3))
+        .build_pair();
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+    for _ in 0..3 {
+        let req = Request::builder().body(BoxBody::default()).unwrap();
+        let _res = service.ready().await.unwrap().call(req).await.unwrap();
+    }
+    assert!(handle.buckets().is_some());
+
+    let req = Request::builder().body(BoxBody::default()).unwrap();
+    let _res = service.ready().await.unwrap().call(req).await.unwrap();
+    assert!(handle
+        .render()
+        .contains("api_http_requests_duration_seconds_bucket"));
+}
@@ -0,0 +1,26 @@
+use axum::{body::Body, routing::get, Router};
+use http::Request;
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn body_bytes_summed_across_requests() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .enable_response_body_size(true)
+        .with_default_metrics()
+        .build_pair();
+    let app = Router::new()
+        .route("/hello", get(|| async { "hello" }))
+        .route("/long", get(|| async { "a longer body" }))
+        .layer(layer);
+
+    for uri in ["/hello", "/hello", "/long"] {
+        let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let res = app.clone().oneshot(req).await.unwrap();
+        res.into_body().collect().await.unwrap();
+    }
+
+    let rendered = handle.render();
+    assert!(rendered.contains("axum_http_response_body_bytes_total{endpoint=\"/hello\"} 10\n"));
+    assert!(rendered.contains("axum_http_response_body_bytes_total{endpoint=\"/long\"} 13\n"));
+}
@@ -0,0 +1,90 @@
+mod common;
+use common::BoxBody;
+
+use std::sync::{Arc, Mutex};
+
+use axum_prometheus::lifecycle::{layer::LifeCycleLayer, Callbacks, OnBodyChunk};
+use http::{Request, Response};
+use http_body_util::{BodyExt, Full};
+use tower::{BoxError, Service, ServiceBuilder, ServiceExt};
+use tower_http::classify::StatusInRangeFailureClass;
+
+#[derive(Clone)]
+struct NoCallbacks;
+
+impl Callbacks<StatusInRangeFailureClass> for NoCallbacks {
+    type Data = ();
+
+    fn prepare<B>(&mut self, _request: &Request<B>) -> Self::Data {}
+}
+
+#[derive(Clone, Default)]
+struct RecordBodyEnd(Arc<Mutex<Vec<&'static str>>>);
+
+impl<B: bytes::Buf> OnBodyChunk<B> for RecordBodyEnd {
+    type Data = ();
+
+    fn on_body_end(&mut self, _data: &mut Self::Data) {
+        self.0.lock().unwrap().push("end");
+    }
+
+    fn on_body_drop(&mut self, _data: &mut Self::Data) {
+        self.0.lock().unwrap().push("drop");
+    }
+}
+
+#[tokio::test]
+async fn body_drop_fires_once_for_unfinished_bodies() {
+    let events = RecordBodyEnd::default();
+    let layer = LifeCycleLayer::new(
+        axum_prometheus::default_classifier(),
+        NoCallbacks,
+        events.clone(),
+    );
+    let mut service =
+        ServiceBuilder::new()
+            .layer(layer)
+            .service_fn(|req: Request<BoxBody>| async move {
+                let body = match req.uri().path() {
+                    "/empty" => BoxBody::default(),
+                    _ => Full::from("hello").map_err(Into::into).boxed_unsync(),
+                };
+                Ok::<_, BoxError>(Response::new(body))
+            });
+    let req = |uri| {
+        Request::builder()
+            .uri(uri)
+            .body(BoxBody::default())
+            .unwrap()
+    };
+
+    // Fully streamed.
+    service
+        .ready()
+        .await
+        .unwrap()
+        .call(req("/"))
+        .await
+        .unwrap()
+        .into_body()
+        .collect()
+        .await
+        .unwrap();
+    assert_eq!(*events.0.lock().unwrap(), ["end"]);
+
+    // Dropped before streaming anything.
+    drop(service.ready().await.unwrap().call(req("/")).await.unwrap());
+    assert_eq!(*events.0.lock().unwrap(), ["end", "drop"]);
+
+    // Nothing to stream, so it's not cut short.
+    drop(
+        service
+            .ready()
+            .await
+            .unwrap()
+            .call(req("/empty"))
+            .await
+            .unwrap(),
+    );
+    assert_eq!(*events.0.lock().unwrap(), ["end", "drop"]);
+}
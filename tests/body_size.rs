@@ -0,0 +1,93 @@
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Buf;
+use http::{Request, Response};
+use http_body::Frame;
+use http_body_util::BodyExt;
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder};
+use tower::{BoxError, Service, ServiceBuilder, ServiceExt};
+
+// 2^53, the point where `f64` can no longer represent every integer.
+const F64_EXACT_LIMIT: u64 = 1 << 53;
+
+/// A buffer that claims to hold `n` bytes without allocating them.
+struct Synthetic(u64);
+
+impl Buf for Synthetic {
+    fn remaining(&self) -> usize {
+        self.0 as usize
+    }
+
+    fn chunk(&self) -> &[u8] {
+        &[0; 1][..self.0.min(1) as usize]
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        self.0 -= cnt as u64;
+    }
+}
+
+struct Chunked(VecDeque<u64>);
+
+impl http_body::Body for Chunked {
+    type Data = Synthetic;
+    type Error = BoxError;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        Poll::Ready(
+            self.0
+                .pop_front()
+                .map(|size| Ok(Frame::data(Synthetic(size)))),
+        )
+    }
+}
+
+#[tokio::test]
+async fn large_body_size_accumulated_exactly() {
+    let handle = PrometheusBuilder::new()
+        .set_buckets_for_metric(
+            Matcher::Full("axum_http_response_body_size".to_string()),
+            &[F64_EXACT_LIMIT as f64],
+        )
+        .unwrap()
+        .install_recorder()
+        .unwrap();
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .enable_response_body_size(true)
+        .with_metrics_from_recorder(handle)
+        .build_pair();
+
+    let mut service = ServiceBuilder::new()
+        .layer(layer)
+        .service_fn(|_req: Request<()>| async {
+            Ok::<_, BoxError>(Response::new(Chunked(
+                [F64_EXACT_LIMIT, 1, 1].into_iter().collect(),
+            )))
+        });
+
+    let res = service
+        .ready()
+        .await
+        .unwrap()
+        .call(Request::new(()))
+        .await
+        .unwrap();
+    let mut body = res.into_body();
+    while body.frame().await.is_some() {}
+
+    // Accumulating in `f64` would get stuck at 2^53, so all three recorded sizes would fall into the bucket.
+    let rendered = handle.render();
+    assert!(rendered.contains(
+        "axum_http_response_body_size_bucket{method=\"GET\",endpoint=\"/\",le=\"9007199254740992\"} 2\n"
+    ));
+    assert!(rendered.contains(
+        "axum_http_response_body_size_bucket{method=\"GET\",endpoint=\"/\",le=\"+Inf\"} 3\n"
+    ));
+}
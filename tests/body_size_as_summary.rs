@@ -0,0 +1,40 @@
+use bytes::Bytes;
+use http::{Request, Response};
+use http_body_util::{BodyExt, Full};
+use tower::{BoxError, Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn body_size_summary_with_quantiles() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_body_size_as_summary(&[0.5, 0.99])
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new()
+        .layer(layer)
+        .service_fn(|_req: Request<()>| async {
+            Ok::<_, BoxError>(Response::new(Full::new(Bytes::from_static(b"hello"))))
+        });
+    let res = service
+        .ready()
+        .await
+        .unwrap()
+        .call(Request::new(()))
+        .await
+        .unwrap();
+    let mut body = res.into_body();
+    while body.frame().await.is_some() {}
+
+    let rendered = handle.render();
+    assert!(rendered.contains("# TYPE axum_http_response_body_size summary\n"));
+    // The quantiles are approximated, so only their presence is checked.
+    assert!(rendered
+        .contains("axum_http_response_body_size{method=\"GET\",endpoint=\"/\",quantile=\"0.5\"} "));
+    assert!(rendered.contains(
+        "axum_http_response_body_size{method=\"GET\",endpoint=\"/\",quantile=\"0.99\"} "
+    ));
+    // Only the configured quantiles are rendered.
+    assert!(!rendered.contains("quantile=\"0.9\""));
+    // The duration keeps its buckets.
+    assert!(rendered.contains("# TYPE axum_http_requests_duration_seconds histogram\n"));
+}
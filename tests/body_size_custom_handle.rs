@@ -0,0 +1,46 @@
+use std::{
+    io,
+    sync::{Arc, Mutex},
+};
+
+use metrics_exporter_prometheus::PrometheusBuilder;
+
+#[derive(Clone, Default)]
+struct Logs(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for Logs {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn warns_about_body_size_with_custom_handle() {
+    let logs = Logs::default();
+    let writer = logs.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_writer(move || writer.clone())
+        .finish();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let handle = PrometheusBuilder::new().build_recorder().handle();
+        let _ = axum_prometheus::PrometheusMetricLayerBuilder::new()
+            .with_metrics_from_recorder(handle)
+            .build_pair();
+        assert!(logs.0.lock().unwrap().is_empty());
+
+        let handle = PrometheusBuilder::new().build_recorder().handle();
+        let _ = axum_prometheus::PrometheusMetricLayerBuilder::new()
+            .enable_response_body_size(true)
+            .with_metrics_from_recorder(handle)
+            .build_pair();
+    });
+
+    let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+    assert!(logs.contains("WARN"));
+    assert!(logs.contains("axum_http_response_body_size"));
+}
@@ -0,0 +1,34 @@
+use bytes::Bytes;
+use http::{Request, Response};
+use http_body_util::{BodyExt, Full};
+use tower::{BoxError, Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn body_size_only_for_matching_paths() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_endpoint_label_type(axum_prometheus::EndpointLabel::Exact)
+        .body_size_for_patterns(&["/download/{file}"])
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new()
+        .layer(layer)
+        .service_fn(|_req: Request<()>| async {
+            Ok::<_, BoxError>(Response::new(Full::new(Bytes::from_static(b"hello"))))
+        });
+
+    for uri in ["/download/a.txt", "/other"] {
+        let req = Request::builder().uri(uri).body(()).unwrap();
+        let res = service.ready().await.unwrap().call(req).await.unwrap();
+        let mut body = res.into_body();
+        while body.frame().await.is_some() {}
+    }
+
+    let rendered = handle.render();
+    assert!(rendered.contains(
+        "axum_http_response_body_size_sum{method=\"GET\",endpoint=\"/download/a.txt\"} 5\n"
+    ));
+    assert!(
+        !rendered.contains("axum_http_response_body_size_sum{method=\"GET\",endpoint=\"/other\"}")
+    );
+}
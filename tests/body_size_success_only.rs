@@ -0,0 +1,34 @@
+use axum::{body::Body, routing::get, Router};
+use http::{Request, StatusCode};
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn error_bodies_left_out_of_body_size() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .enable_response_body_size(true)
+        .body_size_success_only(true)
+        .with_default_metrics()
+        .build_pair();
+    let app = Router::new()
+        .route("/ok", get(|| async { "payload" }))
+        .route(
+            "/error",
+            get(|| async { (StatusCode::NOT_FOUND, "not found") }),
+        )
+        .layer(layer);
+
+    for uri in ["/ok", "/error"] {
+        let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let res = app.clone().oneshot(req).await.unwrap();
+        res.into_body().collect().await.unwrap();
+    }
+
+    let rendered = handle.render();
+    assert!(rendered
+        .contains("axum_http_response_body_size_count{method=\"GET\",endpoint=\"/ok\"} 1\n"));
+    assert!(!rendered
+        .contains("axum_http_response_body_size_count{method=\"GET\",endpoint=\"/error\"}"));
+    // The bytes are still counted.
+    assert!(rendered.contains("axum_http_response_body_bytes_total{endpoint=\"/error\"} 9\n"));
+}
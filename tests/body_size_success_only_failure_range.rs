@@ -0,0 +1,37 @@
+use axum::{body::Body, routing::get, Router};
+use http::{Request, StatusCode};
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn body_size_follows_failure_status_range() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .enable_response_body_size(true)
+        .body_size_success_only(true)
+        .with_failure_status_range(500..=599)
+        .with_default_metrics()
+        .build_pair();
+    let app = Router::new()
+        .route(
+            "/missing",
+            get(|| async { (StatusCode::NOT_FOUND, "not found") }),
+        )
+        .route(
+            "/error",
+            get(|| async { (StatusCode::INTERNAL_SERVER_ERROR, "failed") }),
+        )
+        .layer(layer);
+
+    for uri in ["/missing", "/error"] {
+        let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let res = app.clone().oneshot(req).await.unwrap();
+        res.into_body().collect().await.unwrap();
+    }
+
+    let rendered = handle.render();
+    // `404`s aren't failures with this range, so their bodies are recorded.
+    assert!(rendered
+        .contains("axum_http_response_body_size_count{method=\"GET\",endpoint=\"/missing\"} 1\n"));
+    assert!(!rendered
+        .contains("axum_http_response_body_size_count{method=\"GET\",endpoint=\"/error\"}"));
+}
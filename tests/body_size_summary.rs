@@ -0,0 +1,45 @@
+use bytes::Bytes;
+use http::{Request, Response};
+use http_body_util::{BodyExt, Full};
+use metrics_exporter_prometheus::PrometheusBuilder;
+use tower::{BoxError, Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn body_size_rendered_as_summary() {
+    let handle = PrometheusBuilder::new()
+        .set_quantiles(&[0.5, 0.99])
+        .unwrap()
+        .install_recorder()
+        .unwrap();
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .enable_response_body_size(true)
+        .with_metrics_from_recorder(handle)
+        .build_pair();
+
+    let mut service = ServiceBuilder::new()
+        .layer(layer)
+        .service_fn(|_req: Request<()>| async {
+            Ok::<_, BoxError>(Response::new(Full::new(Bytes::from_static(b"hello"))))
+        });
+    let res = service
+        .ready()
+        .await
+        .unwrap()
+        .call(Request::new(()))
+        .await
+        .unwrap();
+    let mut body = res.into_body();
+    while body.frame().await.is_some() {}
+
+    let rendered = handle.render();
+    assert!(rendered.contains("# TYPE axum_http_response_body_size summary\n"));
+    // The quantiles are approximated, so only their presence is checked.
+    assert!(rendered
+        .contains("axum_http_response_body_size{method=\"GET\",endpoint=\"/\",quantile=\"0.5\"} "));
+    assert!(rendered.contains(
+        "axum_http_response_body_size{method=\"GET\",endpoint=\"/\",quantile=\"0.99\"} "
+    ));
+    assert!(
+        rendered.contains("axum_http_response_body_size_sum{method=\"GET\",endpoint=\"/\"} 5\n")
+    );
+}
@@ -0,0 +1,41 @@
+mod common;
+use common::{echo, BoxBody};
+
+use axum_prometheus::utils::{
+    BucketPreset, BATCH_BUCKETS, BYTES_SIZE_BUCKETS, FAST_API_BUCKETS, SECONDS_DURATION_BUCKETS,
+};
+use http::Request;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[test]
+fn presets_are_monotonic() {
+    for buckets in [
+        SECONDS_DURATION_BUCKETS.as_slice(),
+        FAST_API_BUCKETS,
+        BATCH_BUCKETS,
+        BYTES_SIZE_BUCKETS,
+    ] {
+        assert!(buckets.windows(2).all(|w| w[0] < w[1]), "{buckets:?}");
+    }
+    assert_eq!(BucketPreset::default().buckets(), SECONDS_DURATION_BUCKETS);
+    assert_eq!(BucketPreset::FastApi.buckets(), FAST_API_BUCKETS);
+    assert_eq!(BucketPreset::Batch.buckets(), BATCH_BUCKETS);
+}
+
+#[tokio::test]
+async fn bucket_preset_applied() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_bucket_preset(BucketPreset::FastApi)
+        .build_pair();
+
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+    let req = Request::builder()
+        .uri("/")
+        .body(BoxBody::default())
+        .unwrap();
+    let _res = service.ready().await.unwrap().call(req).await.unwrap();
+
+    let rendered = handle.render();
+    assert!(rendered.contains("axum_http_requests_duration_seconds_bucket{method=\"GET\",status=\"200\",endpoint=\"/\",le=\"0.0005\"}"));
+    assert!(!rendered.contains("le=\"10\""));
+}
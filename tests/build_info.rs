@@ -0,0 +1,16 @@
+use axum_prometheus::PrometheusMetricLayerBuilder;
+use metrics_exporter_prometheus::PrometheusBuilder;
+
+#[test]
+fn build_info_emitted_when_exporter_attached() {
+    let recorder = PrometheusBuilder::new().build_recorder();
+    let handle = recorder.handle();
+    metrics::set_global_recorder(recorder).unwrap();
+
+    // Not finalized with `build_pair`.
+    let _builder = PrometheusMetricLayerBuilder::new().with_metrics_from_recorder(handle.clone());
+
+    assert!(handle.render().contains(
+        "axum_prometheus_build_info{body_size=\"false\",pending=\"true\",prefix=\"\",exporter=\"Handle\"} 1\n"
+    ));
+}
@@ -0,0 +1,28 @@
+use axum::{body::Body, routing::get, Router};
+use http::{header, Request};
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn router_serves_metrics() {
+    let app = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_default_metrics()
+        .build_router_at(Router::new().route("/", get(|| async {})), "/stats");
+
+    let req = Request::builder().uri("/").body(Body::empty()).unwrap();
+    let _res = app.clone().oneshot(req).await.unwrap();
+
+    let req = Request::builder()
+        .uri("/stats")
+        .body(Body::empty())
+        .unwrap();
+    let res = app.oneshot(req).await.unwrap();
+    assert_eq!(
+        res.headers()[header::CONTENT_TYPE],
+        "text/plain; version=0.0.4"
+    );
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    let rendered = String::from_utf8(body.to_vec()).unwrap();
+    assert!(rendered
+        .contains("axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/\"} 1\n"));
+}
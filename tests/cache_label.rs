@@ -0,0 +1,41 @@
+use bytes::Bytes;
+use http::{Request, Response};
+use http_body_util::Empty;
+use tower::{BoxError, Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn cache_label_normalized() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_cache_label("x-cache")
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service =
+        ServiceBuilder::new()
+            .layer(layer)
+            .service_fn(|req: Request<()>| async move {
+                let mut res = Response::builder();
+                match req.uri().path() {
+                    "/hit" => res = res.header("x-cache", "Hit from cloudfront"),
+                    "/miss" => res = res.header("x-cache", "EXPIRED"),
+                    _ => {}
+                }
+                Ok::<_, BoxError>(res.body(Empty::<Bytes>::new()).unwrap())
+            });
+
+    for uri in ["/hit", "/miss", "/none"] {
+        let req = Request::builder().uri(uri).body(()).unwrap();
+        let _res = service.ready().await.unwrap().call(req).await.unwrap();
+    }
+
+    let rendered = handle.render();
+    for (uri, cache) in [("/hit", "hit"), ("/miss", "miss"), ("/none", "none")] {
+        assert!(rendered.contains(&format!(
+            "axum_http_requests_total{{method=\"GET\",status=\"200\",cache=\"{cache}\",endpoint=\"{uri}\"}} 1\n"
+        )));
+    }
+    // The cache label is only attached to the counter.
+    assert!(!rendered.contains(
+        "axum_http_requests_duration_seconds_count{method=\"GET\",status=\"200\",cache="
+    ));
+}
@@ -0,0 +1,35 @@
+mod common;
+use common::BoxBody;
+
+use std::time::Duration;
+
+use http::{Request, Response};
+use tower::{BoxError, Service, ServiceBuilder, ServiceExt};
+
+async fn never_responds(_req: Request<BoxBody>) -> Result<Response<BoxBody>, BoxError> {
+    std::future::pending().await
+}
+
+#[tokio::test]
+async fn dropped_response_future_counts_as_cancelled() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new()
+        .layer(layer)
+        .service_fn(never_responds);
+
+    let req = Request::builder().body(BoxBody::default()).unwrap();
+    let fut = service.ready().await.unwrap().call(req);
+    assert!(tokio::time::timeout(Duration::from_millis(10), fut)
+        .await
+        .is_err());
+
+    let rendered = handle.render();
+    assert!(
+        rendered.contains("axum_http_requests_cancelled_total{method=\"GET\",endpoint=\"/\"} 1\n")
+    );
+    assert!(rendered.contains("axum_http_requests_pending{method=\"GET\",endpoint=\"/\"} 0\n"));
+    assert!(!rendered.contains("axum_http_requests_total{"));
+}
@@ -0,0 +1,35 @@
+use std::time::Duration;
+
+use axum::{body::Body, routing::get, Router};
+use http::Request;
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn duration_is_clamped() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .clamp_duration(Duration::from_millis(10))
+        .with_default_metrics()
+        .build_pair();
+    let app = Router::new()
+        .route(
+            "/slow",
+            get(|| tokio::time::sleep(Duration::from_millis(50))),
+        )
+        .layer(layer);
+
+    let req = Request::builder().uri("/slow").body(Body::empty()).unwrap();
+    let _res = app.oneshot(req).await.unwrap();
+
+    let rendered = handle.render();
+    let sum: f64 = rendered
+        .lines()
+        .find_map(|line| {
+            line.strip_prefix(
+                "axum_http_requests_duration_seconds_sum{method=\"GET\",status=\"200\",endpoint=\"/slow\"} ",
+            )
+        })
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert_eq!(sum, 0.01);
+}
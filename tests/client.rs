@@ -0,0 +1,47 @@
+use axum_prometheus::{ClientMetricLayer, PrometheusMetricLayer};
+use bytes::Bytes;
+use http::{Request, Response, StatusCode};
+use http_body_util::Empty;
+use tower::{BoxError, Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn client_requests_recorded() {
+    // Only used to install the exporter.
+    let (_layer, handle) = PrometheusMetricLayer::pair();
+
+    let mut client = ServiceBuilder::new()
+        .layer(ClientMetricLayer::new())
+        .service_fn(|req: Request<()>| async move {
+            match req.uri().path() {
+                "/down" => Err(BoxError::from("connection refused")),
+                "/missing" => Ok(Response::builder()
+                    .status(StatusCode::NOT_FOUND)
+                    .body(Empty::<Bytes>::new())
+                    .unwrap()),
+                _ => Ok(Response::new(Empty::<Bytes>::new())),
+            }
+        });
+
+    for uri in [
+        "http://example.com/",
+        "http://example.com/missing",
+        "http://example.org/down",
+    ] {
+        let req = Request::builder().uri(uri).body(()).unwrap();
+        let _res = client.ready().await.unwrap().call(req).await;
+    }
+
+    let rendered = handle.render();
+    for (host, status) in [
+        ("example.com", "200"),
+        ("example.com", "404"),
+        ("example.org", "error"),
+    ] {
+        assert!(rendered.contains(&format!(
+            "axum_http_client_requests_total{{method=\"GET\",host=\"{host}\",status=\"{status}\"}} 1\n"
+        )));
+        assert!(rendered.contains(&format!(
+            "axum_http_client_requests_duration_seconds_count{{method=\"GET\",host=\"{host}\",status=\"{status}\"}} 1\n"
+        )));
+    }
+}
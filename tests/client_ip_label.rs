@@ -0,0 +1,49 @@
+mod common;
+use common::{echo, BoxBody};
+
+use std::net::SocketAddr;
+
+use axum::extract::ConnectInfo;
+use axum_prometheus::IpLabelMode;
+use http::Request;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn client_subnet_labeled_on_counter() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_client_ip_label(IpLabelMode::Subnet(24))
+        .with_client_ip_header("x-forwarded-for")
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+
+    let requests = [
+        (Some("203.0.113.7, 10.0.0.1"), None),
+        (None, Some("203.0.113.200:4000")),
+        (None, Some("[2001:db8:abcd::1]:4000")),
+        (None, None),
+    ];
+    for (forwarded_for, peer) in requests {
+        let mut req = Request::builder().uri("/foo");
+        if let Some(forwarded_for) = forwarded_for {
+            req = req.header("x-forwarded-for", forwarded_for);
+        }
+        let mut req = req.body(BoxBody::default()).unwrap();
+        if let Some(peer) = peer {
+            let peer: SocketAddr = peer.parse().unwrap();
+            req.extensions_mut().insert(ConnectInfo(peer));
+        }
+        let _res = service.ready().await.unwrap().call(req).await.unwrap();
+    }
+
+    let rendered = handle.render();
+    for (subnet, count) in [("203.0.113.0/24", 2), ("2001:d00::/24", 1), ("unknown", 1)] {
+        assert!(
+            rendered.contains(&format!(
+                "axum_http_requests_total{{method=\"GET\",status=\"200\",client_subnet=\"{subnet}\",endpoint=\"/foo\"}} {count}\n"
+            )),
+            "{rendered}"
+        );
+    }
+}
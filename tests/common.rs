@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use bytes::Bytes;
 use http::{Request, Response};
 use http_body_util::BodyExt;
@@ -0,0 +1,39 @@
+use bytes::Bytes;
+use http::{Request, Response};
+use http_body_util::Empty;
+use tower::{BoxError, Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn concurrency_recorded_on_arrival() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .enable_concurrency(true)
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new()
+        .layer(layer)
+        .service_fn(|_req: Request<()>| async {
+            Ok::<_, BoxError>(Response::new(Empty::<Bytes>::new()))
+        });
+
+    // Keep the first response (and therefore its request) in flight while the second one arrives.
+    let first = service
+        .ready()
+        .await
+        .unwrap()
+        .call(Request::new(()))
+        .await
+        .unwrap();
+    let _second = service
+        .ready()
+        .await
+        .unwrap()
+        .call(Request::new(()))
+        .await
+        .unwrap();
+    drop(first);
+
+    let rendered = handle.render();
+    assert!(rendered.contains("axum_http_requests_concurrency_count 2\n"));
+    assert!(rendered.contains("axum_http_requests_concurrency_sum 3\n"));
+}
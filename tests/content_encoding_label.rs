@@ -0,0 +1,40 @@
+use bytes::Bytes;
+use http::{header, Request, Response};
+use http_body_util::{BodyExt, Full};
+use tower::{BoxError, Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn body_size_labeled_with_encoding() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_endpoint_label_type(axum_prometheus::EndpointLabel::Exact)
+        .enable_response_body_size(true)
+        .with_content_encoding_label(true)
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service =
+        ServiceBuilder::new()
+            .layer(layer)
+            .service_fn(|req: Request<()>| async move {
+                let mut res = Response::builder();
+                if req.uri().path() == "/gzip" {
+                    res = res.header(header::CONTENT_ENCODING, "gzip");
+                }
+                Ok::<_, BoxError>(res.body(Full::new(Bytes::from_static(b"hello"))).unwrap())
+            });
+
+    for uri in ["/gzip", "/plain"] {
+        let req = Request::builder().uri(uri).body(()).unwrap();
+        let res = service.ready().await.unwrap().call(req).await.unwrap();
+        let mut body = res.into_body();
+        while body.frame().await.is_some() {}
+    }
+
+    let rendered = handle.render();
+    assert!(rendered.contains(
+        "axum_http_response_body_size_count{method=\"GET\",endpoint=\"/gzip\",encoding=\"gzip\"} 1\n"
+    ));
+    assert!(rendered.contains(
+        "axum_http_response_body_size_count{method=\"GET\",endpoint=\"/plain\",encoding=\"identity\"} 1\n"
+    ));
+}
@@ -0,0 +1,39 @@
+use bytes::Bytes;
+use http::{header, Request, Response};
+use http_body_util::Empty;
+use tower::{BoxError, Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn content_type_label_normalized() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_content_type_label(true)
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service =
+        ServiceBuilder::new()
+            .layer(layer)
+            .service_fn(|req: Request<()>| async move {
+                let mut res = Response::builder();
+                if req.uri().path() == "/json" {
+                    res = res.header(header::CONTENT_TYPE, "application/json; charset=utf-8");
+                }
+                Ok::<_, BoxError>(res.body(Empty::<Bytes>::new()).unwrap())
+            });
+
+    for uri in ["/json", "/none"] {
+        let req = Request::builder().uri(uri).body(()).unwrap();
+        let _res = service.ready().await.unwrap().call(req).await.unwrap();
+    }
+
+    let rendered = handle.render();
+    assert!(rendered.contains(
+        "axum_http_requests_total{method=\"GET\",status=\"200\",content_type=\"json\",endpoint=\"/json\"} 1\n"
+    ));
+    assert!(rendered.contains(
+        "axum_http_requests_total{method=\"GET\",status=\"200\",content_type=\"none\",endpoint=\"/none\"} 1\n"
+    ));
+    assert!(rendered.contains(
+        "axum_http_requests_duration_seconds_count{method=\"GET\",status=\"200\",content_type=\"json\",endpoint=\"/json\"} 1\n"
+    ));
+}
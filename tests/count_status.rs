@@ -0,0 +1,26 @@
+use axum::{body::Body, routing::get, Router};
+use http::{Request, StatusCode};
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn status_counted_in_dedicated_counter() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .count_status(429, "rate_limited_total")
+        .count_status(503, "unavailable_total")
+        .with_default_metrics()
+        .build_pair();
+    let app = Router::new()
+        .route("/", get(|| async {}))
+        .route("/limited", get(|| async { StatusCode::TOO_MANY_REQUESTS }))
+        .layer(layer);
+
+    for uri in ["/", "/limited", "/limited"] {
+        let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let _res = app.clone().oneshot(req).await.unwrap();
+    }
+
+    let rendered = handle.render();
+    assert!(rendered.contains("rate_limited_total{method=\"GET\",endpoint=\"/limited\"} 2\n"));
+    assert!(!rendered.contains("rate_limited_total{method=\"GET\",endpoint=\"/\"}"));
+    assert!(!rendered.contains("unavailable_total"));
+}
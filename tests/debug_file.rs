@@ -0,0 +1,27 @@
+#![cfg(feature = "debug-file")]
+
+mod common;
+use common::{echo, BoxBody};
+
+use std::time::Duration;
+
+use axum_prometheus::{DebugFileRecorder, GenericMetricLayer};
+use http::Request;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn metrics_written_to_file() {
+    let path = std::env::temp_dir().join(format!("axum-prometheus-{}.txt", std::process::id()));
+    let recorder = DebugFileRecorder::file(&path).interval(Duration::from_millis(10));
+    let (layer, _handle) = GenericMetricLayer::pair_from(recorder);
+
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+    let req = Request::builder().body(BoxBody::default()).unwrap();
+    let _res = service.ready().await.unwrap().call(req).await.unwrap();
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let written = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(written
+        .contains("axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/\"} 1\n"));
+}
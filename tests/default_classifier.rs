@@ -0,0 +1,67 @@
+mod common;
+use common::BoxBody;
+
+use std::sync::{Arc, Mutex};
+
+use axum_prometheus::lifecycle::{layer::LifeCycleLayer, Callbacks, OnBodyChunk};
+use http::{Request, Response, StatusCode};
+use tower::{BoxError, Service, ServiceBuilder, ServiceExt};
+use tower_http::classify::{ClassifiedResponse, StatusInRangeFailureClass};
+
+#[derive(Clone)]
+struct RecordFailures(Arc<Mutex<Vec<bool>>>);
+
+impl Callbacks<StatusInRangeFailureClass> for RecordFailures {
+    type Data = ();
+
+    fn prepare<B>(&mut self, _request: &Request<B>) -> Self::Data {}
+
+    fn on_response<B>(
+        &mut self,
+        _response: &Response<B>,
+        classification: ClassifiedResponse<StatusInRangeFailureClass, ()>,
+        _data: &mut Self::Data,
+    ) {
+        let is_failure = matches!(classification, ClassifiedResponse::Ready(Err(_)));
+        self.0.lock().unwrap().push(is_failure);
+    }
+}
+
+#[derive(Clone)]
+struct NoBody;
+
+impl<B: bytes::Buf> OnBodyChunk<B> for NoBody {
+    type Data = ();
+
+    fn call(&mut self, _body: &B, _body_size: Option<u64>, _data: &mut Self::Data) {}
+}
+
+#[tokio::test]
+async fn custom_layer_classifies_like_the_crate() {
+    let failures = Arc::new(Mutex::new(Vec::new()));
+    let layer = LifeCycleLayer::new(
+        axum_prometheus::default_classifier(),
+        RecordFailures(Arc::clone(&failures)),
+        NoBody,
+    );
+    let mut service =
+        ServiceBuilder::new()
+            .layer(layer)
+            .service_fn(|req: Request<BoxBody>| async move {
+                let mut res = Response::new(BoxBody::default());
+                if req.uri().path() == "/missing" {
+                    *res.status_mut() = StatusCode::NOT_FOUND;
+                }
+                Ok::<_, BoxError>(res)
+            });
+
+    for uri in ["/", "/missing"] {
+        let req = Request::builder()
+            .uri(uri)
+            .body(BoxBody::default())
+            .unwrap();
+        let _res = service.ready().await.unwrap().call(req).await.unwrap();
+    }
+
+    assert_eq!(*failures.lock().unwrap(), [false, true]);
+}
@@ -0,0 +1,28 @@
+use axum::{body::Body, routing::get, Router};
+use http::Request;
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn depth_of_matched_path() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_depth_label(true)
+        .with_default_metrics()
+        .build_pair();
+    let app = Router::new()
+        .route("/", get(|| async {}))
+        .route("/users/{id}/posts", get(|| async {}))
+        .layer(layer);
+
+    for uri in ["/", "/users/42/posts"] {
+        let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let _res = app.clone().oneshot(req).await.unwrap();
+    }
+
+    let rendered = handle.render();
+    assert!(rendered.contains(
+        "axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/\",depth=\"0\"} 1\n"
+    ));
+    assert!(rendered.contains(
+        "axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/users/{id}/posts\",depth=\"3\"} 1\n"
+    ));
+}
@@ -0,0 +1,31 @@
+mod common;
+use common::{echo, BoxBody};
+
+use axum_prometheus::Descriptions;
+use http::Request;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn custom_descriptions_without_initialization() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .no_initialize_metrics()
+        .with_descriptions(Descriptions {
+            total: "Requests served by the test.",
+            ..Default::default()
+        })
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+    let req = Request::builder()
+        .uri("/foo")
+        .body(BoxBody::default())
+        .unwrap();
+    let _res = service.ready().await.unwrap().call(req).await.unwrap();
+
+    let rendered = handle.render();
+    assert!(rendered.contains("# HELP axum_http_requests_total Requests served by the test.\n"));
+    assert!(rendered.contains(
+        "# HELP axum_http_requests_duration_seconds The distribution of HTTP response times.\n"
+    ));
+}
@@ -0,0 +1,32 @@
+use std::time::Duration;
+
+use axum::{body::Body, routing::get, Extension, Router};
+use axum_prometheus::DurationOverride;
+use http::Request;
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn handler_overrides_duration() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .enable_duration_override()
+        .with_default_metrics()
+        .build_pair();
+    let app = Router::new()
+        .route(
+            "/",
+            get(
+                |Extension(duration): Extension<DurationOverride>| async move {
+                    duration.set_duration_override(Duration::from_secs(42));
+                },
+            ),
+        )
+        .layer(layer);
+
+    let req = Request::builder().uri("/").body(Body::empty()).unwrap();
+    let _res = app.oneshot(req).await.unwrap();
+
+    let rendered = handle.render();
+    assert!(rendered.contains(
+        "axum_http_requests_duration_seconds_sum{method=\"GET\",status=\"200\",endpoint=\"/\"} 42\n"
+    ));
+}
@@ -0,0 +1,27 @@
+mod common;
+use common::{echo, BoxBody};
+
+use axum_prometheus::EndpointLabel;
+use http::Request;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn endpoint_label_includes_method() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_endpoint_label_type(EndpointLabel::MatchedPathWithMethod)
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+
+    let req = Request::builder()
+        .method("POST")
+        .uri("/foo")
+        .body(BoxBody::default())
+        .unwrap();
+    let _res = service.ready().await.unwrap().call(req).await.unwrap();
+
+    assert!(handle.render().contains(
+        "axum_http_requests_total{method=\"POST\",status=\"200\",endpoint=\"POST /foo\"} 1\n"
+    ));
+}
@@ -0,0 +1,46 @@
+use bytes::Bytes;
+use http::{Request, Response, StatusCode};
+use http_body_util::Empty;
+use tower::{BoxError, Service, ServiceBuilder, ServiceExt};
+
+use axum_prometheus::ErrorCodeSource;
+
+#[tokio::test]
+async fn error_code_only_on_failures() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_error_code_label(ErrorCodeSource::header("x-error-code"))
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service =
+        ServiceBuilder::new()
+            .layer(layer)
+            .service_fn(|req: Request<()>| async move {
+                let status = match req.uri().path() {
+                    "/fail" => StatusCode::INTERNAL_SERVER_ERROR,
+                    _ => StatusCode::OK,
+                };
+                Ok::<_, BoxError>(
+                    Response::builder()
+                        .status(status)
+                        .header("x-error-code", "db_timeout")
+                        .body(Empty::<Bytes>::new())
+                        .unwrap(),
+                )
+            });
+
+    for uri in ["/ok", "/fail"] {
+        let req = Request::builder().uri(uri).body(()).unwrap();
+        let _res = service.ready().await.unwrap().call(req).await.unwrap();
+    }
+
+    let rendered = handle.render();
+    assert!(rendered.contains(
+        "axum_http_requests_total{method=\"GET\",status=\"500\",error_code=\"db_timeout\",endpoint=\"/fail\"} 1\n"
+    ));
+    assert!(rendered
+        .contains("axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/ok\"} 1\n"));
+    assert!(!rendered.contains(
+        "axum_http_requests_duration_seconds_count{method=\"GET\",status=\"500\",error_code"
+    ));
+}
@@ -0,0 +1,41 @@
+mod common;
+use common::BoxBody;
+
+use http::{Request, Response, StatusCode};
+use tower::{BoxError, Service, ServiceBuilder, ServiceExt};
+
+async fn status_from_path(req: Request<BoxBody>) -> Result<Response<BoxBody>, BoxError> {
+    let status = req.uri().path().trim_start_matches('/').parse::<u16>()?;
+    let mut res = Response::new(BoxBody::default());
+    *res.status_mut() = StatusCode::from_u16(status)?;
+    Ok(res)
+}
+
+#[tokio::test]
+async fn excluded_statuses_not_counted() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_endpoint_label_type(axum_prometheus::EndpointLabel::Exact)
+        .exclude_statuses(&[101])
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new()
+        .layer(layer)
+        .service_fn(status_from_path);
+
+    for uri in ["/200", "/101"] {
+        let req = Request::builder()
+            .uri(uri)
+            .body(BoxBody::default())
+            .unwrap();
+        let _res = service.ready().await.unwrap().call(req).await.unwrap();
+    }
+
+    let rendered = handle.render();
+    assert!(rendered
+        .contains("axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/200\"} 1\n"));
+    assert!(!rendered.contains("axum_http_requests_total{method=\"GET\",status=\"101\""));
+    assert!(rendered.contains(
+        "axum_http_requests_duration_seconds_count{method=\"GET\",status=\"101\",endpoint=\"/101\"} 1\n"
+    ));
+}
@@ -0,0 +1,30 @@
+use axum_prometheus::{ExporterOpts, MakeDefaultHandle, MetricLayerBuilder};
+
+#[derive(Default)]
+struct PrefixRecorder;
+
+impl MakeDefaultHandle for PrefixRecorder {
+    type Out = Option<String>;
+
+    fn make_default_handle(self) -> Self::Out {
+        self.make_default_handle_with_opts(&ExporterOpts::default())
+    }
+
+    fn make_default_handle_with_opts(self, opts: &ExporterOpts) -> Self::Out {
+        opts.prefix().map(str::to_owned)
+    }
+}
+
+#[test]
+fn exporter_prefix_passed_to_exporter() {
+    let (_layer, handle) = MetricLayerBuilder::<'_, _, PrefixRecorder, _>::new()
+        .with_exporter_prefix("my_app")
+        .with_default_metrics()
+        .build_pair();
+    assert_eq!(handle.as_deref(), Some("my_app"));
+
+    let (_layer, handle) = MetricLayerBuilder::<'_, _, PrefixRecorder, _>::new()
+        .with_default_metrics()
+        .build_pair();
+    assert_eq!(handle, None);
+}
@@ -0,0 +1,39 @@
+use bytes::Bytes;
+use http::{Request, Response};
+use http_body_util::Empty;
+use tower::{BoxError, Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn failures_counted_by_reason() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .enable_failure_reasons(true)
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service =
+        ServiceBuilder::new()
+            .layer(layer)
+            .service_fn(|req: Request<()>| async move {
+                match req.uri().path() {
+                    "/reset" => Err(BoxError::from(
+                        "stream error received: stream no longer needed",
+                    )),
+                    "/timeout" => Err(BoxError::from("request timed out")),
+                    _ => Ok(Response::new(Empty::<Bytes>::new())),
+                }
+            });
+
+    for uri in ["/reset", "/reset", "/timeout", "/ok"] {
+        let req = Request::builder().uri(uri).body(()).unwrap();
+        let _res = service.ready().await.unwrap().call(req).await;
+    }
+
+    let rendered = handle.render();
+    assert!(rendered.contains(
+        "axum_http_requests_failed_total{method=\"GET\",endpoint=\"/reset\",reason=\"reset\"} 2\n"
+    ));
+    assert!(rendered.contains(
+        "axum_http_requests_failed_total{method=\"GET\",endpoint=\"/timeout\",reason=\"timeout\"} 1\n"
+    ));
+    assert!(!rendered.contains("axum_http_requests_failed_total{method=\"GET\",endpoint=\"/ok\""));
+}
@@ -0,0 +1,28 @@
+use axum::{body::Body, routing::get, Router};
+use http::Request;
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn unmatched_requests_use_fallback_label() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .fallback_endpoint_label("unmatched")
+        .with_default_metrics()
+        .build_pair();
+
+    let app = Router::new()
+        .route("/users/{id}", get(|| async {}))
+        .layer(layer);
+
+    for uri in ["/users/1", "/wp-admin", "/.env"] {
+        let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let _res = app.clone().oneshot(req).await.unwrap();
+    }
+
+    let rendered = handle.render();
+    assert!(rendered.contains(
+        "axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/users/{id}\"} 1\n"
+    ));
+    assert!(rendered.contains(
+        "axum_http_requests_total{method=\"GET\",status=\"404\",endpoint=\"unmatched\"} 2\n"
+    ));
+}
@@ -0,0 +1,28 @@
+#![cfg(all(feature = "testing", feature = "prometheus"))]
+
+use std::time::Duration;
+
+use axum_prometheus::testing::force_idle_sweep;
+use metrics_exporter_prometheus::PrometheusBuilder;
+use metrics_util::MetricKindMask;
+
+#[test]
+fn idle_metrics_removed() {
+    let recorder = PrometheusBuilder::new()
+        .idle_timeout(MetricKindMask::COUNTER, Some(Duration::from_millis(50)))
+        .build_recorder();
+    let handle = recorder.handle();
+    metrics::with_local_recorder(&recorder, || {
+        metrics::counter!("idle_total").increment(1);
+        metrics::gauge!("kept").set(1.0);
+    });
+
+    force_idle_sweep(&handle);
+    assert!(handle.render().contains("idle_total 1\n"));
+
+    std::thread::sleep(Duration::from_millis(100));
+    force_idle_sweep(&handle);
+    let rendered = handle.render();
+    assert!(!rendered.contains("idle_total"));
+    assert!(rendered.contains("kept 1\n"));
+}
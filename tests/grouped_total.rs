@@ -0,0 +1,28 @@
+mod common;
+use common::{echo, BoxBody};
+
+use http::Request;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn grouped_requests_counted() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_group_patterns_as("/users", &["/users/{id}"])
+        .enable_grouped_total(true)
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+
+    for uri in ["/users/1", "/users/2", "/other"] {
+        let req = Request::builder()
+            .uri(uri)
+            .body(BoxBody::default())
+            .unwrap();
+        let _res = service.ready().await.unwrap().call(req).await.unwrap();
+    }
+
+    let rendered = handle.render();
+    assert!(rendered.contains("axum_http_requests_grouped_total{group=\"/users\"} 2\n"));
+    assert!(!rendered.contains("group=\"/other\""));
+}
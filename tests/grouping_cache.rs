@@ -0,0 +1,32 @@
+mod common;
+use common::{echo, BoxBody};
+
+use axum_prometheus::EndpointLabel;
+use http::Request;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn grouping_cache_keeps_grouping_beyond_capacity() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_endpoint_label_type(EndpointLabel::Exact)
+        .with_group_patterns_as("/foo", &["/foo/{bar}"])
+        .with_grouping_cache(1)
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+
+    for uri in ["/foo/1", "/foo/1", "/foo/2", "/baz"] {
+        let req = Request::builder()
+            .uri(uri)
+            .body(BoxBody::default())
+            .unwrap();
+        let _res = service.ready().await.unwrap().call(req).await.unwrap();
+    }
+
+    let rendered = handle.render();
+    assert!(rendered
+        .contains("axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/foo\"} 3\n"));
+    assert!(rendered
+        .contains("axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/baz\"} 1\n"));
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "gzip")]
+
+use std::io::Read;
+
+use axum::{body::Body, routing::get, Router};
+use flate2::read::GzDecoder;
+use http::{header, Request};
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn metrics_served_gzipped_when_accepted() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayer::pair();
+    let app = Router::new()
+        .route("/", get(|| async {}))
+        .merge(axum_prometheus::gzip::router("/metrics", handle))
+        .layer(layer);
+
+    let req = Request::builder().uri("/").body(Body::empty()).unwrap();
+    let _res = app.clone().oneshot(req).await.unwrap();
+
+    let req = Request::builder()
+        .uri("/metrics")
+        .header(header::ACCEPT_ENCODING, "deflate, gzip;q=0.8")
+        .body(Body::empty())
+        .unwrap();
+    let res = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(res.headers()[header::CONTENT_ENCODING], "gzip");
+    let compressed = res.into_body().collect().await.unwrap().to_bytes();
+    let mut rendered = String::new();
+    GzDecoder::new(&compressed[..])
+        .read_to_string(&mut rendered)
+        .unwrap();
+    assert!(rendered
+        .contains("axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/\"} 1\n"));
+
+    let req = Request::builder()
+        .uri("/metrics")
+        .header(header::ACCEPT_ENCODING, "gzip;q=0")
+        .body(Body::empty())
+        .unwrap();
+    let res = app.oneshot(req).await.unwrap();
+    assert!(res.headers().get(header::CONTENT_ENCODING).is_none());
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    assert!(std::str::from_utf8(&body)
+        .unwrap()
+        .contains("axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/\"} 1\n"));
+}
@@ -0,0 +1,41 @@
+use axum::{
+    body::Body,
+    routing::{get, post},
+    Router,
+};
+use http::{header, Request};
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn duration_labeled_with_request_body_presence() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .label_has_body(true)
+        .with_default_metrics()
+        .build_pair();
+    let app = Router::new()
+        .route("/", get(|| async {}))
+        .route("/upload", post(|body: String| async move { body }))
+        .layer(layer);
+
+    let req = Request::builder().uri("/").body(Body::empty()).unwrap();
+    let _res = app.clone().oneshot(req).await.unwrap();
+    let req = Request::builder()
+        .method("POST")
+        .uri("/upload")
+        .header(header::CONTENT_LENGTH, "5")
+        .body(Body::from("hello"))
+        .unwrap();
+    let _res = app.oneshot(req).await.unwrap();
+
+    let rendered = handle.render();
+    assert!(rendered.contains(
+        "axum_http_requests_duration_seconds_count{method=\"GET\",status=\"200\",has_body=\"false\",endpoint=\"/\"} 1\n"
+    ));
+    assert!(rendered.contains(
+        "axum_http_requests_duration_seconds_count{method=\"POST\",status=\"200\",has_body=\"true\",endpoint=\"/upload\"} 1\n"
+    ));
+    // The counter isn't split by it.
+    assert!(rendered.contains(
+        "axum_http_requests_total{method=\"POST\",status=\"200\",endpoint=\"/upload\"} 1\n"
+    ));
+}
@@ -0,0 +1,37 @@
+use bytes::Bytes;
+use http::{header, Method, Request, Response};
+use http_body_util::{BodyExt, Full};
+use tower::{BoxError, Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn head_responses_skip_body_size() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .enable_response_body_size(true)
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new()
+        .layer(layer)
+        .service_fn(|_req: Request<()>| async {
+            let res = Response::builder()
+                .header(header::CONTENT_LENGTH, "5")
+                .body(Full::new(Bytes::from_static(b"hello")))
+                .unwrap();
+            Ok::<_, BoxError>(res)
+        });
+
+    for method in [Method::GET, Method::HEAD] {
+        let req = Request::builder().method(method).body(()).unwrap();
+        let res = service.ready().await.unwrap().call(req).await.unwrap();
+        let mut body = res.into_body();
+        while body.frame().await.is_some() {}
+    }
+
+    let rendered = handle.render();
+    assert!(
+        rendered.contains("axum_http_response_body_size_count{method=\"GET\",endpoint=\"/\"} 1\n")
+    );
+    assert!(!rendered.contains("axum_http_response_body_size_count{method=\"HEAD\""));
+    assert!(rendered
+        .contains("axum_http_requests_total{method=\"HEAD\",status=\"200\",endpoint=\"/\"} 1\n"));
+}
@@ -0,0 +1,37 @@
+mod common;
+use common::{echo, BoxBody};
+
+use http::Request;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn header_labels_sanitized_and_truncated() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_header_labels(&[("x-client", "client"), ("x-region", "region")])
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+
+    let req = Request::builder()
+        .uri("/")
+        .header("x-client", "web\"app")
+        .header("x-region", "e".repeat(100))
+        .body(BoxBody::default())
+        .unwrap();
+    let _res = service.ready().await.unwrap().call(req).await.unwrap();
+    let req = Request::builder()
+        .uri("/")
+        .body(BoxBody::default())
+        .unwrap();
+    let _res = service.ready().await.unwrap().call(req).await.unwrap();
+
+    let rendered = handle.render();
+    let region = "e".repeat(64);
+    assert!(rendered.contains(&format!(
+        "axum_http_requests_total{{method=\"GET\",status=\"200\",endpoint=\"/\",client=\"web_app\",region=\"{region}\"}} 1\n"
+    )));
+    assert!(rendered.contains(
+        "axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/\",client=\"none\",region=\"none\"} 1\n"
+    ));
+}
@@ -0,0 +1,30 @@
+mod common;
+use common::{echo, BoxBody};
+
+use http::Request;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn duration_histogram_rendered_without_status() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .histogram_without_status(true)
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+
+    let req = Request::builder().body(BoxBody::default()).unwrap();
+    let _res = service.ready().await.unwrap().call(req).await.unwrap();
+
+    let rendered = handle.render();
+    assert!(rendered
+        .contains("axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/\"} 1\n"));
+    assert!(rendered.contains(
+        "axum_http_requests_duration_seconds_bucket{method=\"GET\",endpoint=\"/\",le=\"+Inf\"} 1\n"
+    ));
+    assert!(rendered
+        .contains("axum_http_requests_duration_seconds_count{method=\"GET\",endpoint=\"/\"} 1\n"));
+    assert!(!rendered
+        .lines()
+        .any(|l| l.starts_with("axum_http_requests_duration_seconds") && l.contains("status")));
+}
@@ -0,0 +1,41 @@
+mod common;
+use common::BoxBody;
+
+use http::{Request, Response, StatusCode};
+use tower::{BoxError, Service, ServiceBuilder, ServiceExt};
+
+async fn status_from_path(req: Request<BoxBody>) -> Result<Response<BoxBody>, BoxError> {
+    let status = req.uri().path().trim_start_matches('/').parse::<u16>()?;
+    let mut res = Response::new(BoxBody::default());
+    *res.status_mut() = StatusCode::from_u16(status)?;
+    Ok(res)
+}
+
+#[tokio::test]
+async fn ignored_statuses_not_recorded() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_endpoint_label_type(axum_prometheus::EndpointLabel::Exact)
+        .ignore_status_range(300..=399)
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new()
+        .layer(layer)
+        .service_fn(status_from_path);
+
+    for uri in ["/200", "/301", "/404"] {
+        let req = Request::builder()
+            .uri(uri)
+            .body(BoxBody::default())
+            .unwrap();
+        let _res = service.ready().await.unwrap().call(req).await.unwrap();
+    }
+
+    let rendered = handle.render();
+    assert!(rendered
+        .contains("axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/200\"} 1\n"));
+    assert!(rendered
+        .contains("axum_http_requests_total{method=\"GET\",status=\"404\",endpoint=\"/404\"} 1\n"));
+    assert!(!rendered.contains("status=\"301\""));
+    assert!(rendered.contains("axum_http_requests_pending{method=\"GET\",endpoint=\"/301\"} 0\n"));
+}
@@ -0,0 +1,50 @@
+mod common;
+use common::BoxBody;
+
+use http::{Request, Response, StatusCode};
+use tower::{BoxError, Service, ServiceBuilder, ServiceExt};
+
+async fn redirect(_req: Request<BoxBody>) -> Result<Response<BoxBody>, BoxError> {
+    let mut res = Response::new(BoxBody::default());
+    *res.status_mut() = StatusCode::MOVED_PERMANENTLY;
+    Ok(res)
+}
+
+#[tokio::test]
+async fn ignored_statuses_still_in_request_metrics() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .ignore_status_range(300..=399)
+        .pending_as_counters(true)
+        .enable_concurrency(true)
+        .enable_inter_arrival(true)
+        .enable_request_header_size(true)
+        .with_group_patterns_as("/legacy", &["/old"])
+        .enable_grouped_total(true)
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(redirect);
+
+    for _ in 0..2 {
+        let req = Request::builder()
+            .uri("/old")
+            .body(BoxBody::default())
+            .unwrap();
+        let _res = service.ready().await.unwrap().call(req).await.unwrap();
+    }
+
+    let rendered = handle.render();
+    assert!(!rendered.contains("axum_http_requests_total{"));
+    assert!(!rendered.contains("axum_http_requests_duration_seconds"));
+    // Recorded before the status is known, so the ignored responses are still included.
+    for line in [
+        "axum_http_requests_started_total{method=\"GET\",endpoint=\"/legacy\"} 2\n",
+        "axum_http_requests_finished_total{method=\"GET\",endpoint=\"/legacy\"} 2\n",
+        "axum_http_requests_concurrency_count 2\n",
+        "axum_http_inter_arrival_seconds_count{method=\"GET\",endpoint=\"/legacy\"} 1\n",
+        "axum_http_request_headers_bytes_count{method=\"GET\",endpoint=\"/legacy\"} 2\n",
+        "axum_http_requests_grouped_total{group=\"/legacy\"} 2\n",
+    ] {
+        assert!(rendered.contains(line), "missing {line}");
+    }
+}
@@ -0,0 +1,29 @@
+mod common;
+use common::{echo, BoxBody};
+
+use http::Request;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn inter_arrival_recorded_from_second_request() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .enable_inter_arrival(true)
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+
+    for uri in ["/foo", "/foo", "/foo", "/bar"] {
+        let req = Request::builder()
+            .uri(uri)
+            .body(BoxBody::default())
+            .unwrap();
+        let _res = service.ready().await.unwrap().call(req).await.unwrap();
+    }
+
+    let rendered = handle.render();
+    assert!(rendered
+        .contains("axum_http_inter_arrival_seconds_count{method=\"GET\",endpoint=\"/foo\"} 2\n"));
+    assert!(!rendered
+        .contains("axum_http_inter_arrival_seconds_count{method=\"GET\",endpoint=\"/bar\"}"));
+}
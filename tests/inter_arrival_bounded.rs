@@ -0,0 +1,35 @@
+mod common;
+use common::{echo, BoxBody};
+
+use axum_prometheus::EndpointLabel;
+use http::Request;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn inter_arrival_ignores_endpoints_once_full() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .enable_inter_arrival(true)
+        .with_endpoint_label_type(EndpointLabel::Exact)
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+
+    // One more endpoint than the map holds, so the last one isn't tracked.
+    let uris = (0..=1024)
+        .map(|i| format!("/{i}"))
+        .chain(["/0".to_owned(), "/1024".to_owned()]);
+    for uri in uris {
+        let req = Request::builder()
+            .uri(uri)
+            .body(BoxBody::default())
+            .unwrap();
+        let _res = service.ready().await.unwrap().call(req).await.unwrap();
+    }
+
+    let rendered = handle.render();
+    assert!(rendered
+        .contains("axum_http_inter_arrival_seconds_count{method=\"GET\",endpoint=\"/0\"} 1\n"));
+    assert!(!rendered
+        .contains("axum_http_inter_arrival_seconds_count{method=\"GET\",endpoint=\"/1024\"}"));
+}
@@ -0,0 +1,36 @@
+#![cfg(feature = "json")]
+use axum_prometheus::json::parse_exposition;
+use serde_json::json;
+
+#[test]
+fn exposition_parsed_into_metric_families() {
+    let exposition =
+        "# HELP axum_http_requests_total The number of times a HTTP request was processed.\n\
+        # TYPE axum_http_requests_total counter\n\
+        axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/a\\\"b\"} 3\n\
+        \n\
+        # TYPE axum_http_requests_duration_seconds histogram\n\
+        axum_http_requests_duration_seconds_bucket{method=\"GET\",le=\"+Inf\"} 1\n\
+        axum_http_requests_duration_seconds_sum{method=\"GET\"} 0.5\n\
+        axum_http_requests_duration_seconds_count{method=\"GET\"} 1\n";
+
+    let parsed = parse_exposition(exposition);
+
+    assert_eq!(
+        parsed["axum_http_requests_total"],
+        json!({
+            "help": "The number of times a HTTP request was processed.",
+            "type": "counter",
+            "samples": [{
+                "name": "axum_http_requests_total",
+                "labels": { "method": "GET", "status": "200", "endpoint": "/a\"b" },
+                "value": 3.0
+            }]
+        })
+    );
+    let histogram = &parsed["axum_http_requests_duration_seconds"];
+    assert_eq!(histogram["type"], "histogram");
+    assert_eq!(histogram["samples"].as_array().unwrap().len(), 3);
+    assert_eq!(histogram["samples"][0]["labels"]["le"], "+Inf");
+    assert_eq!(histogram["samples"][1]["value"], 0.5);
+}
@@ -0,0 +1,31 @@
+mod common;
+use common::{echo, BoxBody};
+
+use axum_prometheus::LabelKeys;
+use http::Request;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn custom_label_keys() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_label_keys(LabelKeys {
+            method: "http_method",
+            endpoint: "path",
+            status: "code",
+        })
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+
+    let req = Request::builder()
+        .uri("/foo")
+        .body(BoxBody::default())
+        .unwrap();
+    let _res = service.ready().await.unwrap().call(req).await.unwrap();
+
+    let rendered = handle.render();
+    assert!(rendered
+        .contains("axum_http_requests_total{http_method=\"GET\",code=\"200\",path=\"/foo\"} 1\n"));
+    assert!(!rendered.contains("endpoint="));
+}
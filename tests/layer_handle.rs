@@ -0,0 +1,28 @@
+mod common;
+use common::{echo, BoxBody};
+
+use http::Request;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn handle_retrieved_from_layer_clone() {
+    let (layer, _handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_default_metrics()
+        .build_pair();
+    let metric_handle = layer.clone().handle().unwrap().clone();
+
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+    let req = Request::builder()
+        .uri("/foo")
+        .body(BoxBody::default())
+        .unwrap();
+    let _res = service.ready().await.unwrap().call(req).await.unwrap();
+
+    assert!(metric_handle
+        .render()
+        .contains("axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/foo\"} 1\n"));
+
+    // Layers built without a handle don't have one.
+    let layer = axum_prometheus::PrometheusMetricLayerBuilder::new().build();
+    assert!(layer.handle().is_none());
+}
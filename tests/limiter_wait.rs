@@ -0,0 +1,51 @@
+mod common;
+use common::{echo, BoxBody};
+
+use std::time::Duration;
+
+use http::Request;
+use tower::{limit::ConcurrencyLimitLayer, Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn limiter_wait_is_recorded() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .enable_limiter_wait()
+        .with_default_metrics()
+        .build_pair();
+
+    let service = ServiceBuilder::new()
+        .layer(layer)
+        .layer(ConcurrencyLimitLayer::new(1))
+        .service_fn(echo);
+    let mut first = service.clone();
+    let mut second = service;
+
+    // The first request holds the only permit until its response future completes.
+    let req = Request::builder().body(BoxBody::default()).unwrap();
+    let first_response = first.ready().await.unwrap().call(req);
+
+    let req = Request::builder().body(BoxBody::default()).unwrap();
+    let (_first, _second) = tokio::join!(
+        async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            first_response.await.unwrap()
+        },
+        async { second.ready().await.unwrap().call(req).await.unwrap() }
+    );
+
+    let rendered = handle.render();
+    assert!(rendered.contains(
+        "axum_http_requests_phase_seconds_count{method=\"GET\",endpoint=\"/\",phase=\"limiter_wait\"} 2\n"
+    ));
+    let sum: f64 = rendered
+        .lines()
+        .find_map(|line| {
+            line.strip_prefix(
+                "axum_http_requests_phase_seconds_sum{method=\"GET\",endpoint=\"/\",phase=\"limiter_wait\"} ",
+            )
+        })
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(sum >= 0.05, "{sum}");
+}
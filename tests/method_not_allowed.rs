@@ -0,0 +1,29 @@
+use axum::{body::Body, routing::get, Router};
+use http::{Method, Request, StatusCode};
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn method_not_allowed_labeled_with_matched_path() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_default_metrics()
+        .build_pair();
+    let app = Router::new()
+        .route("/users/{id}", get(|| async {}))
+        .layer(layer);
+
+    let req = Request::builder()
+        .method(Method::DELETE)
+        .uri("/users/42")
+        .body(Body::empty())
+        .unwrap();
+    let res = app.oneshot(req).await.unwrap();
+    assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+
+    let rendered = handle.render();
+    assert!(
+        rendered.contains(
+            "axum_http_requests_total{method=\"DELETE\",status=\"405\",endpoint=\"/users/{id}\"} 1\n"
+        ),
+        "{rendered}"
+    );
+}
@@ -0,0 +1,9 @@
+use axum_prometheus::MetricsData;
+
+#[test]
+fn metrics_data_constructed_outside_the_crate() {
+    let data = MetricsData::new("/foo", "GET");
+    assert_eq!(data.endpoint, "/foo");
+    assert_eq!(data.method, "GET");
+    assert_eq!(data.body_size, 0);
+}
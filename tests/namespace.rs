@@ -0,0 +1,36 @@
+mod common;
+use common::{echo, BoxBody};
+
+use http::Request;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn namespaced_layers_report_independently() {
+    let (public_layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_namespace("public")
+        .with_default_metrics()
+        .build_pair();
+    let internal_layer = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_namespace("internal")
+        .build();
+
+    let mut public = ServiceBuilder::new().layer(public_layer).service_fn(echo);
+    let mut internal = ServiceBuilder::new().layer(internal_layer).service_fn(echo);
+
+    for _ in 0..2 {
+        let req = Request::builder().body(BoxBody::default()).unwrap();
+        let _res = public.ready().await.unwrap().call(req).await.unwrap();
+    }
+    let req = Request::builder().body(BoxBody::default()).unwrap();
+    let _res = internal.ready().await.unwrap().call(req).await.unwrap();
+
+    let rendered = handle.render();
+    assert!(rendered
+        .contains("public_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/\"} 2\n"));
+    assert!(rendered.contains(
+        "internal_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/\"} 1\n"
+    ));
+    assert!(!rendered.contains("axum_http_requests_total"));
+    // The default exporter configures the buckets for the namespaced duration.
+    assert!(rendered.contains("public_http_requests_duration_seconds_bucket"));
+}
@@ -0,0 +1,23 @@
+mod common;
+use common::{echo, BoxBody};
+
+use axum_prometheus::NoopPrometheusMetricLayer;
+use http::Request;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn noop_layer_records_nothing() {
+    let (mut layer, handle) = NoopPrometheusMetricLayer::pair();
+    layer.enable_response_body_size();
+
+    let mut service = ServiceBuilder::new().layer(layer.clone()).service_fn(echo);
+    let req = Request::builder()
+        .uri("/foo")
+        .body(BoxBody::default())
+        .unwrap();
+    let res = service.ready().await.unwrap().call(req).await.unwrap();
+
+    assert_eq!(res.status(), 200);
+    assert_eq!(layer.pending_count(), 0);
+    assert_eq!(handle.render(), "");
+}
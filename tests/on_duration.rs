@@ -0,0 +1,32 @@
+use std::sync::{Arc, Mutex};
+
+use axum::{body::Body, routing::get, Router};
+use http::{Request, StatusCode};
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn on_duration_called_with_recorded_duration() {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&calls);
+    let layer = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .on_duration(Arc::new(move |seconds, data, status| {
+            recorded
+                .lock()
+                .unwrap()
+                .push((seconds, data.endpoint.clone(), status));
+        }))
+        .build();
+    let app = Router::new()
+        .route("/", get(|| async { StatusCode::ACCEPTED }))
+        .layer(layer);
+
+    let req = Request::builder().uri("/").body(Body::empty()).unwrap();
+    let _res = app.oneshot(req).await.unwrap();
+
+    let calls = calls.lock().unwrap();
+    assert_eq!(calls.len(), 1);
+    let (seconds, endpoint, status) = &calls[0];
+    assert!(*seconds >= 0.0);
+    assert_eq!(endpoint, "/");
+    assert_eq!(*status, StatusCode::ACCEPTED);
+}
@@ -0,0 +1,39 @@
+mod common;
+use common::{echo, BoxBody};
+
+use std::sync::{Arc, Mutex};
+
+use http::Request;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn callback_called_with_summary() {
+    let summaries = Arc::new(Mutex::new(Vec::new()));
+    let (layer, _handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .on_request_complete(Arc::new({
+            let summaries = Arc::clone(&summaries);
+            move |summary| {
+                summaries.lock().unwrap().push((
+                    summary.method.to_owned(),
+                    summary.endpoint.to_owned(),
+                    summary.status,
+                ))
+            }
+        }))
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+
+    let req = Request::builder()
+        .method("POST")
+        .uri("/foo")
+        .body(BoxBody::default())
+        .unwrap();
+    let _res = service.ready().await.unwrap().call(req).await.unwrap();
+
+    assert_eq!(
+        *summaries.lock().unwrap(),
+        [("POST".to_owned(), "/foo".to_owned(), 200)]
+    );
+}
@@ -0,0 +1,31 @@
+use std::collections::HashMap;
+
+use axum::{body::Body, routing::get, Router};
+use http::Request;
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn operation_label_from_matched_path() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_operation_names(HashMap::from([("/users/{id}", "get_user")]))
+        .with_default_metrics()
+        .build_pair();
+
+    let app = Router::new()
+        .route("/users/{id}", get(|| async {}))
+        .route("/health", get(|| async {}))
+        .layer(layer);
+
+    for uri in ["/users/1", "/health"] {
+        let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let _res = app.clone().oneshot(req).await.unwrap();
+    }
+
+    let rendered = handle.render();
+    assert!(rendered.contains(
+        "axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/users/{id}\",operation=\"get_user\"} 1\n"
+    ));
+    assert!(rendered.contains(
+        "axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/health\",operation=\"/health\"} 1\n"
+    ));
+}
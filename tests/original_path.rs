@@ -0,0 +1,38 @@
+mod common;
+use common::{echo, BoxBody};
+
+use axum_prometheus::{EndpointLabel, OriginalPath};
+use http::Request;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn original_path_used_as_endpoint() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_endpoint_label_type(EndpointLabel::Exact)
+        .with_original_path_from_extension()
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+
+    let mut req = Request::builder()
+        .uri("/foo")
+        .body(BoxBody::default())
+        .unwrap();
+    req.extensions_mut()
+        .insert(OriginalPath("/v1/foo/".to_owned()));
+    let _res = service.ready().await.unwrap().call(req).await.unwrap();
+    let req = Request::builder()
+        .uri("/bar")
+        .body(BoxBody::default())
+        .unwrap();
+    let _res = service.ready().await.unwrap().call(req).await.unwrap();
+
+    let rendered = handle.render();
+    assert!(rendered.contains(
+        "axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/v1/foo/\"} 1\n"
+    ));
+    assert!(rendered
+        .contains("axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/bar\"} 1\n"));
+    assert!(!rendered.contains("endpoint=\"/foo\""));
+}
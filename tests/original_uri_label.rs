@@ -0,0 +1,27 @@
+use axum::{body::Body, routing::get, Router};
+use axum_prometheus::EndpointLabel;
+use http::Request;
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn nested_router_labeled_with_full_path() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_endpoint_label_type(EndpointLabel::OriginalUri)
+        .with_default_metrics()
+        .build_pair();
+    let api = Router::new()
+        .route("/users/{id}", get(|| async {}))
+        .layer(layer);
+    let app = Router::new().nest("/api", api);
+
+    let req = Request::builder()
+        .uri("/api/users/1")
+        .body(Body::empty())
+        .unwrap();
+    let _res = app.oneshot(req).await.unwrap();
+
+    let rendered = handle.render();
+    assert!(rendered.contains(
+        "axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/api/users/1\"} 1\n"
+    ));
+}
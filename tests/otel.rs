@@ -0,0 +1,43 @@
+#![cfg(feature = "otel")]
+mod common;
+use common::{echo, BoxBody};
+
+use axum_prometheus::{GenericMetricLayer, OtelRecorder};
+use http::Request;
+use opentelemetry::metrics::MeterProvider;
+use opentelemetry_sdk::metrics::{InMemoryMetricExporter, SdkMeterProvider};
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn metrics_forwarded_to_meter() {
+    let exporter = InMemoryMetricExporter::default();
+    let provider = SdkMeterProvider::builder()
+        .with_periodic_exporter(exporter.clone())
+        .build();
+
+    let (layer, ()) = GenericMetricLayer::pair_from(OtelRecorder::new(provider.meter("test")));
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+
+    let req = Request::builder().body(BoxBody::default()).unwrap();
+    let _res = service.ready().await.unwrap().call(req).await.unwrap();
+
+    provider.force_flush().unwrap();
+    let mut names = exporter
+        .get_finished_metrics()
+        .unwrap()
+        .iter()
+        .flat_map(|rm| rm.scope_metrics())
+        .flat_map(|sm| sm.metrics())
+        .map(|m| m.name().to_owned())
+        .collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+    assert_eq!(
+        names,
+        [
+            "axum_http_requests_duration_seconds",
+            "axum_http_requests_pending",
+            "axum_http_requests_total"
+        ]
+    );
+}
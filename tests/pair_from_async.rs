@@ -0,0 +1,41 @@
+mod common;
+use common::{echo, BoxBody};
+
+use axum_prometheus::{AsyncMakeDefaultHandle, GenericMetricLayer};
+use http::Request;
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+struct AsyncRecorder;
+
+impl AsyncMakeDefaultHandle for AsyncRecorder {
+    type Out = PrometheusHandle;
+
+    async fn make_default_handle(self) -> Self::Out {
+        // Stands in for connecting to a collector.
+        tokio::task::yield_now().await;
+        PrometheusBuilder::new().install_recorder().unwrap()
+    }
+
+    fn share_handle(handle: &Self::Out) -> Option<Self::Out> {
+        Some(handle.clone())
+    }
+}
+
+#[tokio::test]
+async fn pair_from_async_exporter() {
+    let (layer, handle) =
+        GenericMetricLayer::<'_, _, AsyncRecorder>::pair_from_async(AsyncRecorder).await;
+    assert!(layer.handle().is_some());
+
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+    let req = Request::builder()
+        .uri("/foo")
+        .body(BoxBody::default())
+        .unwrap();
+    let _res = service.ready().await.unwrap().call(req).await.unwrap();
+
+    assert!(handle
+        .render()
+        .contains("axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/foo\"} 1\n"));
+}
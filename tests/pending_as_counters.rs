@@ -0,0 +1,39 @@
+mod common;
+use common::{echo, BoxBody};
+
+use http::Request;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn pending_counted_with_counters() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .pending_as_counters(true)
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+
+    let req = Request::builder()
+        .uri("/foo")
+        .body(BoxBody::default())
+        .unwrap();
+    let in_flight = service.ready().await.unwrap().call(req).await.unwrap();
+    let req = Request::builder()
+        .uri("/foo")
+        .body(BoxBody::default())
+        .unwrap();
+    drop(service.ready().await.unwrap().call(req).await.unwrap());
+
+    let rendered = handle.render();
+    assert!(
+        rendered.contains("axum_http_requests_started_total{method=\"GET\",endpoint=\"/foo\"} 2\n")
+    );
+    assert!(rendered
+        .contains("axum_http_requests_finished_total{method=\"GET\",endpoint=\"/foo\"} 1\n"));
+    assert!(!rendered.contains("axum_http_requests_pending"));
+
+    drop(in_flight);
+    assert!(handle
+        .render()
+        .contains("axum_http_requests_finished_total{method=\"GET\",endpoint=\"/foo\"} 2\n"));
+}
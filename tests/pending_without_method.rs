@@ -0,0 +1,32 @@
+use bytes::Bytes;
+use http::{Request, Response};
+use http_body_util::Empty;
+use tower::{BoxError, Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn pending_gauge_without_method() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .pending_without_method(true)
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new()
+        .layer(layer)
+        .service_fn(|_req: Request<()>| async {
+            Ok::<_, BoxError>(Response::new(Empty::<Bytes>::new()))
+        });
+
+    for method in ["GET", "POST"] {
+        let req = Request::builder().method(method).body(()).unwrap();
+        let res = service.ready().await.unwrap().call(req).await.unwrap();
+        assert!(handle
+            .render()
+            .contains("axum_http_requests_pending{endpoint=\"/\"} 1\n"));
+        drop(res);
+    }
+
+    // The decrement uses the same labels as the increment, so the gauge doesn't drift.
+    let rendered = handle.render();
+    assert!(rendered.contains("axum_http_requests_pending{endpoint=\"/\"} 0\n"));
+    assert!(!rendered.contains("axum_http_requests_pending{method="));
+}
@@ -0,0 +1,29 @@
+mod common;
+use common::BoxBody;
+
+use axum_prometheus::PhaseTimer;
+use http::{Request, Response};
+use tower::{BoxError, Service, ServiceBuilder, ServiceExt};
+
+async fn handler(req: Request<BoxBody>) -> Result<Response<BoxBody>, BoxError> {
+    let timer = req.extensions().get::<PhaseTimer>().unwrap();
+    timer.phase("db").record();
+    Ok(Response::new(BoxBody::default()))
+}
+
+#[tokio::test]
+async fn phases_are_recorded() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .enable_phase_timing()
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(handler);
+
+    let req = Request::builder().body(BoxBody::default()).unwrap();
+    let _res = service.ready().await.unwrap().call(req).await.unwrap();
+
+    assert!(handle.render().contains(
+        "axum_http_requests_phase_seconds_count{method=\"GET\",endpoint=\"/\",phase=\"db\"} 1\n"
+    ));
+}
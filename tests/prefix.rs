@@ -15,13 +15,21 @@ async fn metric_handle_rendered_correctly_with_prefix() {
 
     let req = Request::builder().body(BoxBody::default()).unwrap();
     let _res = service.ready().await.unwrap().call(req).await.unwrap();
+    assert!(handle.render().contains(
+        "axum_prometheus_build_info{body_size=\"false\",pending=\"true\",prefix=\"pref\",exporter=\"Handle\"} 1\n"
+    ));
     insta::with_settings!({
             filters =>
             vec![
                 (
                     r"\b[-+]?[0-9]*\.?[0-9]+\b\\npref_http_requests_duration_seconds_count",
                     "",
-                )
+                ),
+                // The order of the gauges isn't stable, and the build info is checked separately.
+                (
+                    r"# TYPE axum_prometheus_build_info gauge\\naxum_prometheus_build_info\{[^}]*\} 1\\n\\n",
+                    "",
+                ),
             ]
         },
     {
@@ -0,0 +1,28 @@
+mod common;
+use common::{echo, BoxBody};
+
+use axum_prometheus::{utils, MetricKind};
+use http::Request;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn prefix_applied_to_selected_metrics() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_prefix_for(
+            &[MetricKind::RequestsPending, MetricKind::RequestsDuration],
+            "pref",
+        )
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+    let req = Request::builder().body(BoxBody::default()).unwrap();
+    let _res = service.ready().await.unwrap().call(req).await.unwrap();
+
+    assert_eq!(utils::requests_total_name(), "axum_http_requests_total");
+    assert_eq!(utils::requests_pending_name(), "pref_http_requests_pending");
+    let rendered = handle.render();
+    assert!(rendered.contains("\naxum_http_requests_total{"));
+    assert!(rendered.contains("\npref_http_requests_pending{"));
+    assert!(rendered.contains("\npref_http_requests_duration_seconds_count{"));
+}
@@ -0,0 +1,29 @@
+use http::Method;
+
+#[tokio::test]
+async fn endpoints_registered_at_startup() {
+    let (_layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .preregister_endpoints(&[
+            ("/users", &[Method::GET, Method::POST]),
+            ("/users/{id}", &[Method::GET]),
+        ])
+        .with_default_metrics()
+        .build_pair();
+
+    let rendered = handle.render();
+    for (method, endpoint) in [
+        ("GET", "/users"),
+        ("POST", "/users"),
+        ("GET", "/users/{id}"),
+    ] {
+        assert!(rendered.contains(&format!(
+            "axum_http_requests_total{{method=\"{method}\",status=\"200\",endpoint=\"{endpoint}\"}} 0\n"
+        )));
+        assert!(rendered.contains(&format!(
+            "axum_http_requests_pending{{method=\"{method}\",endpoint=\"{endpoint}\"}} 0\n"
+        )));
+        assert!(rendered.contains(&format!(
+            "axum_http_requests_duration_seconds_count{{method=\"{method}\",status=\"200\",endpoint=\"{endpoint}\"}} 0\n"
+        )));
+    }
+}
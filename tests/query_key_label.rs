@@ -0,0 +1,29 @@
+use axum::{body::Body, routing::get, Router};
+use http::Request;
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn query_keys_are_sorted_without_values() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_query_key_label(true)
+        .with_default_metrics()
+        .build_pair();
+    let app = Router::new().route("/items", get(|| async {})).layer(layer);
+
+    for uri in [
+        "/items",
+        "/items?offset=10&limit=5",
+        "/items?limit=1&offset=2",
+    ] {
+        let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let _res = app.clone().oneshot(req).await.unwrap();
+    }
+
+    let rendered = handle.render();
+    assert!(rendered.contains(
+        "axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/items\",query_keys=\"none\"} 1\n"
+    ));
+    assert!(rendered.contains(
+        "axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/items\",query_keys=\"limit,offset\"} 2\n"
+    ));
+}
@@ -0,0 +1,36 @@
+use axum::{body::Body, routing::get, Router};
+use http::Request;
+use tower::ServiceExt;
+
+#[derive(Clone)]
+struct Sampled;
+
+#[tokio::test]
+async fn unsampled_requests_not_recorded() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .record_only_sampled(|parts| parts.extensions.get::<Sampled>().is_some())
+        .with_default_metrics()
+        .build_pair();
+    let app = Router::new()
+        .route("/sampled", get(|| async {}))
+        .route("/unsampled", get(|| async {}))
+        .layer(layer);
+
+    let mut req = Request::builder()
+        .uri("/sampled")
+        .body(Body::empty())
+        .unwrap();
+    req.extensions_mut().insert(Sampled);
+    let _res = app.clone().oneshot(req).await.unwrap();
+    let req = Request::builder()
+        .uri("/unsampled")
+        .body(Body::empty())
+        .unwrap();
+    let _res = app.oneshot(req).await.unwrap();
+
+    let rendered = handle.render();
+    assert!(rendered.contains(
+        "axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/sampled\"} 1\n"
+    ));
+    assert!(!rendered.contains("/unsampled"));
+}
@@ -0,0 +1,33 @@
+mod common;
+use common::{echo, BoxBody};
+
+use http::Request;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn recording_disabled_at_runtime() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_default_metrics()
+        .build_pair();
+    let switch = layer.recording_switch();
+
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+
+    for (uri, enabled) in [("/on", true), ("/off", false)] {
+        if enabled {
+            switch.enable();
+        } else {
+            switch.disable();
+        }
+        let req = Request::builder()
+            .uri(uri)
+            .body(BoxBody::default())
+            .unwrap();
+        let _res = service.ready().await.unwrap().call(req).await.unwrap();
+    }
+
+    let rendered = handle.render();
+    assert!(rendered
+        .contains("axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/on\"} 1\n"));
+    assert!(!rendered.contains("endpoint=\"/off\""));
+}
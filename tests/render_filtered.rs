@@ -0,0 +1,28 @@
+use axum::{body::Body, routing::get, Router};
+use axum_prometheus::filter::{render_filtered, RenderOptions};
+use http::Request;
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn histogram_lines_stripped() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_default_metrics()
+        .build_pair();
+    let app = Router::new().route("/", get(|| async {})).layer(layer);
+    let req = Request::builder().uri("/").body(Body::empty()).unwrap();
+    let _res = app.oneshot(req).await.unwrap();
+
+    let unfiltered = render_filtered(&handle, &RenderOptions::new());
+    assert!(unfiltered.contains("axum_http_requests_duration_seconds_count{"));
+
+    let options = RenderOptions::new()
+        .without_sum_and_count()
+        .without_bucket("+Inf");
+    let rendered = render_filtered(&handle, &options);
+    assert!(!rendered.contains("axum_http_requests_duration_seconds_sum"));
+    assert!(!rendered.contains("axum_http_requests_duration_seconds_count"));
+    assert!(!rendered.contains("le=\"+Inf\""));
+    assert!(rendered.contains("le=\"0.005\"} 1\n"));
+    assert!(rendered.contains("# TYPE axum_http_requests_duration_seconds histogram\n"));
+    assert!(rendered.contains("axum_http_requests_total{"));
+}
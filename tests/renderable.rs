@@ -0,0 +1,22 @@
+mod common;
+use common::{echo, BoxBody};
+
+use axum_prometheus::Renderable;
+use http::Request;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+fn scrape(handle: &impl Renderable) -> String {
+    handle.try_render().unwrap()
+}
+
+#[tokio::test]
+async fn prometheus_handle_is_renderable() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayer::pair();
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+
+    let req = Request::builder().body(BoxBody::default()).unwrap();
+    let _res = service.ready().await.unwrap().call(req).await.unwrap();
+
+    assert!(scrape(&handle)
+        .contains("axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/\"} 1\n"));
+}
@@ -0,0 +1,32 @@
+use bytes::Bytes;
+use http::{Request, Response};
+use http_body_util::Empty;
+use tower::{BoxError, Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn request_header_size_recorded() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .enable_request_header_size(true)
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new()
+        .layer(layer)
+        .service_fn(|_req: Request<()>| async {
+            Ok::<_, BoxError>(Response::new(Empty::<Bytes>::new()))
+        });
+
+    let req = Request::builder()
+        .uri("/")
+        .header("x-a", "bc")
+        .header("cookie", "1234")
+        .body(())
+        .unwrap();
+    let _res = service.ready().await.unwrap().call(req).await.unwrap();
+
+    let rendered = handle.render();
+    assert!(rendered
+        .contains("axum_http_request_headers_bytes_sum{method=\"GET\",endpoint=\"/\"} 15\n"));
+    assert!(rendered
+        .contains("axum_http_request_headers_bytes_count{method=\"GET\",endpoint=\"/\"} 1\n"));
+}
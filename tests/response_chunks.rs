@@ -0,0 +1,49 @@
+mod common;
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use common::{echo, BoxBody};
+use http::Request;
+use http_body::Frame;
+use http_body_util::BodyExt;
+use tower::{BoxError, Service, ServiceBuilder, ServiceExt};
+
+struct Chunked(VecDeque<Bytes>);
+
+impl http_body::Body for Chunked {
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        Poll::Ready(self.0.pop_front().map(|chunk| Ok(Frame::data(chunk))))
+    }
+}
+
+#[tokio::test]
+async fn response_chunks_recorded_on_body_end() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .enable_response_chunks(true)
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+
+    let chunks = ["foo", "bar", "baz"].into_iter().map(Bytes::from).collect();
+    let req = Request::builder()
+        .body(BoxBody::new(Chunked(chunks)))
+        .unwrap();
+    let res = service.ready().await.unwrap().call(req).await.unwrap();
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(body, "foobarbaz");
+
+    let rendered = handle.render();
+    assert!(rendered.contains("axum_http_response_chunks_sum{method=\"GET\",endpoint=\"/\"} 3\n"));
+    assert!(rendered.contains("axum_http_response_chunks_count{method=\"GET\",endpoint=\"/\"} 1\n"));
+}
@@ -0,0 +1,36 @@
+use axum::{body::Body, routing::get, Router};
+use http::{header, Request, StatusCode};
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn retry_after_labeled_on_counter() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_retry_after_label(true)
+        .with_default_metrics()
+        .build_pair();
+    let app = Router::new()
+        .route("/", get(|| async {}))
+        .route(
+            "/busy",
+            get(|| async {
+                (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    [(header::RETRY_AFTER, "30")],
+                )
+            }),
+        )
+        .layer(layer);
+
+    for uri in ["/", "/busy"] {
+        let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let _res = app.clone().oneshot(req).await.unwrap();
+    }
+
+    let rendered = handle.render();
+    assert!(rendered.contains(
+        "axum_http_requests_total{method=\"GET\",status=\"200\",retry_after=\"false\",endpoint=\"/\"} 1\n"
+    ));
+    assert!(rendered.contains(
+        "axum_http_requests_total{method=\"GET\",status=\"503\",retry_after=\"true\",endpoint=\"/busy\"} 1\n"
+    ));
+}
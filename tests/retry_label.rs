@@ -0,0 +1,38 @@
+mod common;
+use common::{echo, BoxBody};
+
+use axum_prometheus::RetryCount;
+use http::Request;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn retries_labeled_on_counter() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_retry_label()
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+
+    for count in [None, Some(0), Some(1), Some(2)] {
+        let mut req = Request::builder()
+            .uri("/foo")
+            .body(BoxBody::default())
+            .unwrap();
+        if let Some(count) = count {
+            req.extensions_mut().insert(RetryCount(count));
+        }
+        let _res = service.ready().await.unwrap().call(req).await.unwrap();
+    }
+
+    let rendered = handle.render();
+    assert!(rendered.contains(
+        "axum_http_requests_total{method=\"GET\",status=\"200\",retry=\"false\",endpoint=\"/foo\"} 2\n"
+    ));
+    assert!(rendered.contains(
+        "axum_http_requests_total{method=\"GET\",status=\"200\",retry=\"true\",endpoint=\"/foo\"} 2\n"
+    ));
+    assert!(rendered.contains(
+        "axum_http_requests_duration_seconds_count{method=\"GET\",status=\"200\",endpoint=\"/foo\"} 4\n"
+    ));
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "ring-buffer")]
+
+mod common;
+use common::{echo, BoxBody};
+
+use axum_prometheus::{
+    ring_buffer::{RingBufferRecorder, SeriesKind},
+    GenericMetricLayer,
+};
+use http::Request;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn recent_samples_are_bounded() {
+    let (layer, handle) = GenericMetricLayer::pair_from(RingBufferRecorder::new(2));
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+    for _ in 0..3 {
+        let req = Request::builder().body(BoxBody::default()).unwrap();
+        let _res = service.ready().await.unwrap().call(req).await.unwrap();
+    }
+
+    let snapshot = handle.snapshot();
+    let total = snapshot
+        .iter()
+        .find(|series| {
+            series.kind == SeriesKind::Counter && series.name == "axum_http_requests_total"
+        })
+        .unwrap();
+    // Only the last two values of the counter are kept.
+    let values: Vec<_> = total.samples.iter().map(|sample| sample.value).collect();
+    assert_eq!(values, [2.0, 3.0]);
+    let duration = snapshot
+        .iter()
+        .find(|series| series.name == "axum_http_requests_duration_seconds")
+        .unwrap();
+    assert_eq!(duration.kind, SeriesKind::Histogram);
+    assert_eq!(duration.samples.len(), 2);
+}
@@ -0,0 +1,67 @@
+use std::{sync::OnceLock, time::Duration};
+
+use axum::{body::Body, extract::Path, routing::get, Router};
+use axum_prometheus::{EndpointLabel, PrometheusMetricLayerBuilder};
+use http::Request;
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+use tower::ServiceExt;
+
+/// The tests share the global recorder, and build their layers without installing one.
+fn handle() -> &'static PrometheusHandle {
+    static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+    HANDLE.get_or_init(|| PrometheusBuilder::new().install_recorder().unwrap())
+}
+
+#[tokio::test]
+async fn slow_requests_violate_slo() {
+    let handle = handle();
+    let layer = PrometheusMetricLayerBuilder::new()
+        .with_slo("/sleep/{ms}", Duration::from_millis(20))
+        .build();
+    let app = Router::new()
+        .route(
+            "/sleep/{ms}",
+            get(|Path(ms): Path<u64>| tokio::time::sleep(Duration::from_millis(ms))),
+        )
+        .route(
+            "/other",
+            get(|| tokio::time::sleep(Duration::from_millis(40))),
+        )
+        .layer(layer);
+
+    for uri in ["/sleep/0", "/sleep/40", "/other"] {
+        let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let _res = app.clone().oneshot(req).await.unwrap();
+    }
+
+    let rendered = handle.render();
+    assert!(rendered.contains("axum_http_slo_violations_total{endpoint=\"/sleep/{ms}\"} 1\n"));
+    assert!(!rendered.contains("axum_http_slo_violations_total{endpoint=\"/other\"}"));
+}
+
+#[tokio::test]
+async fn slo_matched_against_route_syntax() {
+    let handle = handle();
+    let layer = PrometheusMetricLayerBuilder::new()
+        .with_endpoint_label_type(EndpointLabel::MatchedPathWithMethod)
+        .with_root_label("root")
+        .with_slo("/users/{id}", Duration::from_millis(20))
+        .with_slo("/", Duration::from_millis(20))
+        .build();
+    let app = Router::new()
+        .route(
+            "/users/{id}",
+            get(|| tokio::time::sleep(Duration::from_millis(40))),
+        )
+        .route("/", get(|| tokio::time::sleep(Duration::from_millis(40))))
+        .layer(layer);
+
+    for uri in ["/users/1", "/"] {
+        let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let _res = app.clone().oneshot(req).await.unwrap();
+    }
+
+    let rendered = handle.render();
+    assert!(rendered.contains("axum_http_slo_violations_total{endpoint=\"GET /users/{id}\"} 1\n"));
+    assert!(rendered.contains("axum_http_slo_violations_total{endpoint=\"GET root\"} 1\n"));
+}
@@ -0,0 +1,34 @@
+mod common;
+use common::{echo, BoxBody};
+
+use bytes::Bytes;
+use http::Request;
+use http_body_util::{BodyExt, Full};
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn stream_duration_recorded_on_body_end() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .enable_stream_duration(true)
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+
+    let req = Request::builder()
+        .body(BoxBody::new(
+            Full::new(Bytes::from("foo")).map_err(Into::into),
+        ))
+        .unwrap();
+    let res = service.ready().await.unwrap().call(req).await.unwrap();
+
+    assert!(!handle
+        .render()
+        .contains("axum_http_stream_duration_seconds"));
+
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(body, "foo");
+    assert!(handle
+        .render()
+        .contains("axum_http_stream_duration_seconds_count{method=\"GET\",endpoint=\"/\"} 1\n"));
+}
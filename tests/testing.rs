@@ -0,0 +1,34 @@
+#![cfg(feature = "testing")]
+
+use axum_prometheus::{testing::drive_once, PrometheusMetricLayerBuilder};
+use http::Request;
+
+#[tokio::test]
+async fn drive_once_records_the_whole_lifecycle() {
+    let layer = PrometheusMetricLayerBuilder::new()
+        .enable_response_body_size(true)
+        .build();
+    let request = Request::builder()
+        .method("POST")
+        .uri("/foo")
+        .body(String::from("hello"))
+        .unwrap();
+
+    let metrics = drive_once(layer, request).await;
+
+    let labels = [("method", "POST"), ("endpoint", "/foo")];
+    let labels_with_status = [("method", "POST"), ("status", "200"), ("endpoint", "/foo")];
+    assert_eq!(
+        metrics.counter("axum_http_requests_total", &labels_with_status),
+        Some(1)
+    );
+    assert_eq!(
+        metrics.gauge("axum_http_requests_pending", &labels),
+        Some(0.0)
+    );
+    // Only recorded once the response body is drained.
+    assert_eq!(
+        metrics.histogram("axum_http_response_body_size", &labels),
+        Some(vec![5.0])
+    );
+}
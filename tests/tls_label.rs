@@ -0,0 +1,44 @@
+use bytes::Bytes;
+use http::{Request, Response};
+use http_body_util::Empty;
+use tower::{BoxError, Service, ServiceBuilder, ServiceExt};
+
+#[derive(Clone)]
+struct TlsInfo {
+    version: &'static str,
+}
+
+#[tokio::test]
+async fn tls_label_read_from_extensions() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_tls_label(|info: &TlsInfo| info.version.to_owned())
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new()
+        .layer(layer)
+        .service_fn(|_req: Request<()>| async {
+            Ok::<_, BoxError>(Response::new(Empty::<Bytes>::new()))
+        });
+
+    let mut req = Request::new(());
+    req.extensions_mut().insert(TlsInfo { version: "TLSv1.3" });
+    let _res = service.ready().await.unwrap().call(req).await.unwrap();
+    let _res = service
+        .ready()
+        .await
+        .unwrap()
+        .call(Request::new(()))
+        .await
+        .unwrap();
+
+    let rendered = handle.render();
+    assert!(rendered.contains(
+        "axum_http_requests_total{method=\"GET\",status=\"200\",tls=\"TLSv1.3\",endpoint=\"/\"} 1\n"
+    ));
+    assert!(rendered.contains(
+        "axum_http_requests_total{method=\"GET\",status=\"200\",tls=\"none\",endpoint=\"/\"} 1\n"
+    ));
+    // Only the counter is labeled.
+    assert!(!rendered.contains("axum_http_requests_pending{method=\"GET\",tls="));
+}
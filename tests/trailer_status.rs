@@ -0,0 +1,78 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use http::{HeaderMap, HeaderValue, Request, Response};
+use http_body::Frame;
+use http_body_util::{BodyExt, Empty};
+use tower::{BoxError, Service, ServiceBuilder, ServiceExt};
+
+/// A body that only produces the given trailers, like a gRPC response.
+struct Trailers(Option<HeaderMap>);
+
+impl http_body::Body for Trailers {
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        Poll::Ready(self.0.take().map(|trailers| Ok(Frame::trailers(trailers))))
+    }
+}
+
+#[tokio::test]
+async fn status_read_from_trailers() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_trailer_status("grpc-status")
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new()
+        .layer(layer)
+        .service_fn(|_req: Request<()>| async {
+            let mut trailers = HeaderMap::new();
+            trailers.insert("grpc-status", HeaderValue::from_static("5"));
+            Ok::<_, BoxError>(Response::new(Trailers(Some(trailers))))
+        });
+    let res = service
+        .ready()
+        .await
+        .unwrap()
+        .call(Request::new(()))
+        .await
+        .unwrap();
+    let mut body = res.into_body();
+    while body.frame().await.is_some() {}
+
+    // A trailers-only response.
+    let mut service = ServiceBuilder::new()
+        .layer(
+            axum_prometheus::PrometheusMetricLayerBuilder::new()
+                .with_trailer_status("grpc-status")
+                .build(),
+        )
+        .service_fn(|_req: Request<()>| async {
+            let res = Response::builder()
+                .header("grpc-status", "0")
+                .body(Empty::<Bytes>::new())
+                .unwrap();
+            Ok::<_, BoxError>(res)
+        });
+    let _res = service
+        .ready()
+        .await
+        .unwrap()
+        .call(Request::new(()))
+        .await
+        .unwrap();
+
+    let rendered = handle.render();
+    assert!(rendered
+        .contains("axum_grpc_requests_total{method=\"GET\",grpc_status=\"5\",endpoint=\"/\"} 1\n"));
+    assert!(rendered
+        .contains("axum_grpc_requests_total{method=\"GET\",grpc_status=\"0\",endpoint=\"/\"} 1\n"));
+}
@@ -0,0 +1,37 @@
+mod common;
+use common::{echo, BoxBody};
+
+use axum_prometheus::EndpointLabel;
+use http::Request;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn try_dynamic_labels_and_skips() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_endpoint_label_type(EndpointLabel::TryDynamic(|parts| {
+            let tenant = parts.headers.get("x-tenant")?.to_str().ok()?;
+            Some(format!("/{tenant}{}", parts.uri.path()))
+        }))
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+
+    let req = Request::builder()
+        .uri("/foo")
+        .header("x-tenant", "acme")
+        .body(BoxBody::default())
+        .unwrap();
+    let _res = service.ready().await.unwrap().call(req).await.unwrap();
+    let req = Request::builder()
+        .uri("/bar")
+        .body(BoxBody::default())
+        .unwrap();
+    let _res = service.ready().await.unwrap().call(req).await.unwrap();
+
+    let rendered = handle.render();
+    assert!(rendered.contains(
+        "axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/acme/foo\"} 1\n"
+    ));
+    assert!(!rendered.contains("/bar"));
+}
@@ -0,0 +1,26 @@
+mod common;
+use common::{echo, BoxBody};
+
+use http::Request;
+use tower::{Service, ServiceBuilder, ServiceExt};
+
+#[tokio::test]
+async fn version_label_attached_to_all_metrics() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_version_label("1.2.3")
+        .with_default_metrics()
+        .build_pair();
+
+    let mut service = ServiceBuilder::new().layer(layer).service_fn(echo);
+
+    let req = Request::builder().body(BoxBody::default()).unwrap();
+    let _res = service.ready().await.unwrap().call(req).await.unwrap();
+
+    let rendered = handle.render();
+    assert!(rendered.contains(
+        "axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/\",version=\"1.2.3\"} 1\n"
+    ));
+    assert!(rendered.contains(
+        "axum_http_requests_pending{method=\"GET\",endpoint=\"/\",version=\"1.2.3\"} 1\n"
+    ));
+}
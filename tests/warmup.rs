@@ -0,0 +1,25 @@
+use std::time::Duration;
+
+use axum::{body::Body, routing::get, Router};
+use http::Request;
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn requests_during_warmup_are_not_recorded() {
+    let (layer, handle) = axum_prometheus::PrometheusMetricLayerBuilder::new()
+        .with_warmup(Duration::from_millis(100))
+        .with_default_metrics()
+        .build_pair();
+    let app = Router::new().route("/", get(|| async {})).layer(layer);
+
+    let req = Request::builder().body(Body::empty()).unwrap();
+    let _res = app.clone().oneshot(req).await.unwrap();
+    assert!(!handle.render().contains("axum_http_requests_total"));
+
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    let req = Request::builder().body(Body::empty()).unwrap();
+    let _res = app.oneshot(req).await.unwrap();
+    assert!(handle
+        .render()
+        .contains("axum_http_requests_total{method=\"GET\",status=\"200\",endpoint=\"/\"} 1\n"));
+}